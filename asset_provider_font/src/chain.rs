@@ -0,0 +1,287 @@
+use std::collections::BTreeSet;
+
+use anyhow::anyhow;
+use asset_provider::{Asset, Assets};
+use sui::{comp::text::Font, raylib::ffi};
+
+use crate::CHARSET;
+
+/// raylib's `FONT_TTF_DEFAULT_CHARS_PADDING`
+const GLYPH_PADDING: i32 = 4;
+/// raylib's `FONT_DEFAULT`
+pub(crate) const FONT_TYPE_DEFAULT: i32 = 0;
+/// raylib's `FONT_SDF`
+pub(crate) const FONT_TYPE_SDF: i32 = 2;
+/// raylib's `TEXTURE_FILTER_BILINEAR`
+const TEXTURE_FILTER_BILINEAR: i32 = 1;
+
+/// a font made out of an ordered list of font files, where every glyph is taken from the first
+/// font file that has it (a primary font, then a cjk font, then a symbols font, etc). \
+/// glyphs get loaded on demand: every character [Font] notices missing while measuring text
+/// gets added to the font on the next [FontChain::update].
+///
+/// the [Font] returned by [FontChain::font] stays the same, rebuilding only swaps out
+/// what's inside it (see [Font::replace]), so text doesn't have to be rebuilt
+pub struct FontChain {
+	font: Font,
+	/// font files, in fallback order
+	faces: Vec<Asset>,
+	glyphs: BTreeSet<char>,
+
+	font_raster_size: i32,
+	base_size_multiplier: f32,
+	/// load the glyphs as signed distance fields, see [sui::comp::text::sdf]
+	sdf: bool,
+}
+impl std::fmt::Debug for FontChain {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("FontChain")
+			.field("font", &self.font)
+			.field("faces", &self.faces.len())
+			.field("glyphs", &self.glyphs.len())
+			.field("font_raster_size", &self.font_raster_size)
+			.field("base_size_multiplier", &self.base_size_multiplier)
+			.field("sdf", &self.sdf)
+			.finish()
+	}
+}
+impl FontChain {
+	/// loads every font in `keys`, in fallback order, and builds the font with [CHARSET]. \
+	/// see [crate::load_font_explicit] for `font_raster_size` and `base_size_multiplier`
+	pub async fn load<'a, A: Assets>(
+		assets: &A,
+		keys: &[&str],
+		d: &mut sui::Handle<'a>,
+		font_raster_size: i32,
+		base_size_multiplier: f32,
+	) -> anyhow::Result<Self> {
+		Self::load_explicit(
+			assets,
+			keys,
+			d,
+			font_raster_size,
+			base_size_multiplier,
+			false,
+		)
+		.await
+	}
+	/// same as [Self::load], but the glyphs are loaded as signed distance fields, so text stays sharp at any scale
+	pub async fn load_sdf<'a, A: Assets>(
+		assets: &A,
+		keys: &[&str],
+		d: &mut sui::Handle<'a>,
+		font_raster_size: i32,
+		base_size_multiplier: f32,
+	) -> anyhow::Result<Self> {
+		Self::load_explicit(
+			assets,
+			keys,
+			d,
+			font_raster_size,
+			base_size_multiplier,
+			true,
+		)
+		.await
+	}
+	async fn load_explicit<'a, A: Assets>(
+		assets: &A,
+		keys: &[&str],
+		d: &mut sui::Handle<'a>,
+		font_raster_size: i32,
+		base_size_multiplier: f32,
+		sdf: bool,
+	) -> anyhow::Result<Self> {
+		let mut faces = Vec::with_capacity(keys.len());
+		for key in keys {
+			let asset = assets.asset(key).await?;
+			ttf_parser::Face::parse(asset.as_slice(), 0)
+				.map_err(|err| anyhow!("while loading font {key} into a FontChain: {err}"))?;
+			faces.push(asset);
+		}
+
+		let glyphs = CHARSET.chars().collect();
+		let mut chain = Self {
+			// placeholder until the first build
+			font: Font::headless(Default::default()),
+			faces,
+			glyphs,
+			font_raster_size,
+			base_size_multiplier,
+			sdf,
+		};
+		chain.font = chain.build(d)?;
+		Ok(chain)
+	}
+
+	/// the font, updated in place every time glyphs get added
+	pub fn font(&self) -> Font {
+		self.font.clone()
+	}
+	pub fn glyphs(&self) -> &BTreeSet<char> {
+		&self.glyphs
+	}
+
+	/// adds the glyphs text has been measured with since the last call, and rebuilds the font if there were any. \
+	/// should be called every frame, from the main thread. returns whether the font was rebuilt
+	pub fn update(&mut self, d: &mut sui::Handle) -> anyhow::Result<bool> {
+		let missing = self.font.take_missing_glyphs();
+		self.add_glyphs(missing, d)
+	}
+	/// adds `chars` to the font, rebuilding it if any of them weren't loaded yet. returns whether the font was rebuilt
+	pub fn add_glyphs(
+		&mut self,
+		chars: impl IntoIterator<Item = char>,
+		d: &mut sui::Handle,
+	) -> anyhow::Result<bool> {
+		let len_before = self.glyphs.len();
+		self.glyphs.extend(chars);
+		if self.glyphs.len() == len_before {
+			return Ok(false);
+		}
+
+		let new = self.build(d)?;
+		self.font.replace(&new);
+		Ok(true)
+	}
+
+	/// `_d` is only here to make sure this gets called where raylib is loaded
+	fn build(&self, _d: &mut sui::Handle) -> anyhow::Result<Font> {
+		let faces = self
+			.faces
+			.iter()
+			.map(|asset| ttf_parser::Face::parse(asset.as_slice(), 0))
+			.collect::<Result<Vec<_>, _>>()
+			.map_err(|err| anyhow!("failed to parse font in FontChain: {err}"))?;
+
+		// glyphs nobody has go to the first font, so at least they render as its missing glyph box
+		let mut codepoints = vec![Vec::new(); faces.len()];
+		for c in self.glyphs.iter().copied() {
+			let i = faces
+				.iter()
+				.position(|face| face.glyph_index(c).is_some())
+				.unwrap_or(0);
+			codepoints[i].push(c as i32);
+		}
+
+		let files = self
+			.faces
+			.iter()
+			.map(|asset| asset.as_slice())
+			.collect::<Vec<_>>();
+		let font_type = if self.sdf {
+			FONT_TYPE_SDF
+		} else {
+			FONT_TYPE_DEFAULT
+		};
+		let mut raylib_font =
+			unsafe { load_font_merged(&files, &mut codepoints, self.font_raster_size, font_type) }?;
+		raylib_font.baseSize = (raylib_font.baseSize as f32 * self.base_size_multiplier) as i32;
+
+		if self.sdf {
+			Ok(Font::from_raylib_sdf(raylib_font, true))
+		} else {
+			Ok(Font::from_raylib(raylib_font, true))
+		}
+	}
+}
+
+/// raylib's `LoadFontFromMemory`, except glyphs can come from several font files. \
+/// `codepoints[i]` are the codepoints that get loaded from `files[i]`
+///
+/// has to be called from the main thread, with raylib initialized
+pub(crate) unsafe fn load_font_merged(
+	files: &[&[u8]],
+	codepoints: &mut [Vec<i32>],
+	font_size: i32,
+	font_type: i32,
+) -> anyhow::Result<sui::raylib::text::Font> {
+	let mut loaded: Vec<(*mut ffi::GlyphInfo, usize)> = Vec::with_capacity(files.len());
+	for (data, cps) in files.iter().zip(codepoints.iter_mut()) {
+		if cps.is_empty() {
+			continue;
+		}
+
+		let glyphs = unsafe {
+			ffi::LoadFontData(
+				data.as_ptr(),
+				data.len() as i32,
+				font_size,
+				cps.as_mut_ptr(),
+				cps.len() as i32,
+				font_type,
+			)
+		};
+		if glyphs.is_null() {
+			for (glyphs, count) in loaded {
+				unsafe { ffi::UnloadFontData(glyphs, count as i32) };
+			}
+			return Err(anyhow!("LoadFontData failed while building a merged font"));
+		}
+		loaded.push((glyphs, cps.len()));
+	}
+
+	let count = loaded.iter().map(|(_, count)| count).sum::<usize>();
+	if count == 0 {
+		return Err(anyhow!("attempted to build a merged font with no glyphs"));
+	}
+
+	// UnloadFont frees the glyphs with raylib's allocator, so they have to live in one raylib allocation
+	let glyphs = unsafe { ffi::MemAlloc((count * std::mem::size_of::<ffi::GlyphInfo>()) as _) }
+		as *mut ffi::GlyphInfo;
+	let mut offset = 0;
+	for (face_glyphs, face_count) in loaded {
+		unsafe {
+			std::ptr::copy_nonoverlapping(face_glyphs, glyphs.add(offset), face_count);
+			// the glyph images moved into `glyphs`, only the array itself is freed
+			ffi::MemFree(face_glyphs as *mut _);
+		}
+		offset += face_count;
+	}
+
+	// sdf glyphs come with their own padding, and raylib packs them with the skyline algorithm
+	let (padding, pack_method) = if font_type == FONT_TYPE_SDF {
+		(0, 1)
+	} else {
+		(GLYPH_PADDING, 0)
+	};
+
+	let mut recs: *mut ffi::Rectangle = std::ptr::null_mut();
+	let atlas = unsafe {
+		ffi::GenImageFontAtlas(
+			glyphs,
+			&mut recs,
+			count as i32,
+			font_size,
+			padding,
+			pack_method,
+		)
+	};
+	let texture = unsafe { ffi::LoadTextureFromImage(atlas) };
+	if font_type == FONT_TYPE_SDF {
+		// the sdf shader relies on interpolating between texels
+		unsafe { ffi::SetTextureFilter(texture, TEXTURE_FILTER_BILINEAR) };
+	}
+
+	// same as LoadFontFromMemory, the glyph images get replaced with their part of the atlas
+	{
+		let glyph_slice = unsafe { std::slice::from_raw_parts_mut(glyphs, count) };
+		let rec_slice = unsafe { std::slice::from_raw_parts(recs, count) };
+		for (glyph, rec) in glyph_slice.iter_mut().zip(rec_slice) {
+			unsafe {
+				ffi::UnloadImage(glyph.image);
+				glyph.image = ffi::ImageFromImage(atlas, *rec);
+			}
+		}
+	}
+	unsafe { ffi::UnloadImage(atlas) };
+
+	let font = ffi::Font {
+		baseSize: font_size,
+		glyphCount: count as i32,
+		glyphPadding: padding,
+		texture,
+		recs,
+		glyphs,
+	};
+	Ok(unsafe { sui::raylib::text::Font::from_raw(font) })
+}
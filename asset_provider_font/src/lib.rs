@@ -1,11 +1,17 @@
 use std::path::PathBuf;
 
-use anyhow::Context;
+use anyhow::{Context, anyhow};
 use asset_provider::Assets;
-use sui::comp::text::Font;
+use sui::comp::text::{Font, GlyphMetrics};
 use temp_dir::TempDir;
 use tokio::io::AsyncWriteExt;
 
+pub mod chain;
+pub use chain::FontChain;
+
+/// the characters every font gets loaded with
+pub const CHARSET: &str = "!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~ÁÉÍÓÖŐÚÜŰáéíóöőúüű";
+
 pub async fn load_font_explicit<'a, A: Assets>(
 	assets: &A,
 	key: &str,
@@ -36,7 +42,7 @@ pub async fn load_font_explicit<'a, A: Assets>(
 
 		let font_size = font_raster_size;
 
-		let chars = Some("!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~ÁÉÍÓÖŐÚÜŰáéíóöőúüű");
+		let chars = Some(CHARSET);
 
 		rl.load_font_from_memory(th, file_type, file_data, font_size, chars)
 	}
@@ -50,6 +56,92 @@ pub async fn load_font_explicit<'a, A: Assets>(
 	Ok(font)
 }
 
+/// same as [load_font_explicit], but the glyphs are loaded as signed distance fields and rendered
+/// through raylib's sdf shader, so text stays sharp at any scale
+pub async fn load_font_sdf<'a, A: Assets>(
+	assets: &A,
+	key: &str,
+	_d: &mut sui::Handle<'a>,
+	font_raster_size: i32,
+	base_size_multiplier: f32,
+) -> anyhow::Result<Font> {
+	let asset = assets.asset(key).await?;
+
+	let mut codepoints = [CHARSET.chars().map(|c| c as i32).collect::<Vec<_>>()];
+	let mut raylib_font = unsafe {
+		chain::load_font_merged(
+			&[asset.as_slice()],
+			&mut codepoints,
+			font_raster_size,
+			chain::FONT_TYPE_SDF,
+		)
+	}
+	.with_context(|| format!("while loading sdf font from {key}"))?;
+
+	raylib_font.baseSize = (raylib_font.baseSize as f32 * base_size_multiplier) as i32;
+
+	Ok(Font::from_raylib_sdf(raylib_font, true))
+}
+
+/// loads the font without a raylib context, only for measuring. \
+/// measures exactly like the font [load_font_explicit] returns with the same arguments,
+/// but renders with raylib's default font. see [Font::headless]
+pub async fn load_font_headless<A: Assets>(
+	assets: &A,
+	key: &str,
+	font_raster_size: i32,
+	base_size_multiplier: f32,
+) -> anyhow::Result<Font> {
+	let asset = assets.asset(key).await?;
+
+	let metrics = GlyphMetrics::from_ttf(asset.as_slice(), font_raster_size, CHARSET.chars())
+		.map_err(|err| anyhow!("while loading font from {key}, headless: {err}"))?;
+	let metrics = metrics.with_base_size((font_raster_size as f32 * base_size_multiplier) as i32);
+
+	Ok(Font::headless(metrics))
+}
+
+/// loads every font in the [registry](sui::comp::text::registry) that's been asked for but isn't
+/// loaded yet, and registers them. call this regularly (every frame is fine), fonts get swapped in
+/// for everything already using them. \
+/// returns how many fonts got loaded. a font that fails to load doesn't get retried
+pub async fn load_requested_fonts<'a, A: Assets>(
+	assets: &A,
+	d: &mut sui::Handle<'a>,
+	font_raster_size: i32,
+	base_size_multiplier: f32,
+) -> anyhow::Result<usize> {
+	let requested = sui::comp::text::registry::take_requested();
+
+	let mut loaded = 0;
+	let mut first_err = None;
+	for (key, asset_key) in requested {
+		let font = load_font_explicit(
+			assets,
+			&asset_key,
+			d,
+			font_raster_size,
+			base_size_multiplier,
+		)
+		.await
+		.with_context(|| format!("while loading {key:?} for the font registry"));
+		match font {
+			Ok(font) => {
+				sui::comp::text::registry::register(key, font);
+				loaded += 1;
+			}
+			Err(err) => {
+				first_err.get_or_insert(err);
+			}
+		}
+	}
+
+	match first_err {
+		Some(err) => Err(err),
+		None => Ok(loaded),
+	}
+}
+
 pub async fn into_temp_dir<A: Assets>(assets: &A, key: &str) -> anyhow::Result<PathBuf> {
 	let asset = assets.asset(key).await?;
 
@@ -0,0 +1,137 @@
+//! loads [sui::l10n] string tables through [Assets]. \
+//! string tables are toml files, one per locale (`en.toml`, `hu.toml`, ...):
+//!
+//! ```toml
+//! greeting = "hello, {name}!"
+//!
+//! [inbox]
+//! title = "inbox"
+//! # tables whose keys are all plural categories (and include `other`) are plural messages
+//! unread.one = "{count} unread message"
+//! unread.other = "{count} unread messages"
+//! ```
+//!
+//! nested tables become dotted keys, so the above has `greeting`, `inbox.title` and `inbox.unread`
+
+use std::collections::HashMap;
+
+use anyhow::{Context, anyhow};
+use asset_provider::Assets;
+use sui::l10n::{self, Message, PluralCategory, StringTable};
+
+/// loads `{locale}.toml` and adds it to the strings of `locale`
+pub async fn load_locale<A: Assets>(assets: &A, locale: &str) -> anyhow::Result<()> {
+	load_locale_from(assets, locale, &format!("{locale}.toml")).await
+}
+/// loads the string table at `key` and adds it to the strings of `locale`
+pub async fn load_locale_from<A: Assets>(
+	assets: &A,
+	locale: &str,
+	key: &str,
+) -> anyhow::Result<()> {
+	let table = load_table(assets, key).await?;
+	l10n::add_table(locale, table);
+	Ok(())
+}
+/// loads `{locale}.toml` unless the locale already has strings, then makes it the active locale. \
+/// the locale is left alone if loading fails
+pub async fn switch_locale<A: Assets>(assets: &A, locale: &str) -> anyhow::Result<()> {
+	if !l10n::has_locale(locale) {
+		load_locale(assets, locale).await?;
+	}
+	l10n::set_locale(locale.to_owned());
+	Ok(())
+}
+
+pub async fn load_table<A: Assets>(assets: &A, key: &str) -> anyhow::Result<StringTable> {
+	let asset = assets.asset(key).await?;
+	let src = asset
+		.as_str()
+		.with_context(|| format!("string table {key} isn't valid utf-8"))?;
+
+	parse_table(&src).with_context(|| format!("while parsing string table {key}"))
+}
+
+pub fn parse_table(src: &str) -> anyhow::Result<StringTable> {
+	let root: toml::Table = src.parse()?;
+
+	let mut table = StringTable::new();
+	flatten(&mut table, "", &root)?;
+	Ok(table)
+}
+fn flatten(out: &mut StringTable, prefix: &str, table: &toml::Table) -> anyhow::Result<()> {
+	for (name, value) in table {
+		let key = if prefix.is_empty() {
+			name.clone()
+		} else {
+			format!("{prefix}.{name}")
+		};
+
+		match value {
+			toml::Value::String(s) => out.insert(key, s.as_str()),
+			toml::Value::Table(t) if is_plural(t) => {
+				let variants = t
+					.iter()
+					.filter_map(|(category, s)| {
+						Some((PluralCategory::from_name(category)?, s.as_str()?.to_owned()))
+					})
+					.collect::<HashMap<_, _>>();
+				out.insert(key, Message::Plural(variants));
+			}
+			toml::Value::Table(t) => flatten(out, &key, t)?,
+			other => {
+				return Err(anyhow!(
+					"{key} is a {}, string tables can only contain strings and tables",
+					other.type_str()
+				));
+			}
+		}
+	}
+	Ok(())
+}
+fn is_plural(table: &toml::Table) -> bool {
+	table.contains_key(PluralCategory::Other.name())
+		&& table
+			.iter()
+			.all(|(k, v)| PluralCategory::from_name(k).is_some() && v.is_str())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_table() {
+		let table = parse_table(
+			r#"
+			greeting = "hello, {name}!"
+
+			[inbox]
+			title = "inbox"
+			unread.one = "{count} unread message"
+			unread.other = "{count} unread messages"
+			"#,
+		)
+		.unwrap();
+
+		assert_eq!(
+			table.get("greeting"),
+			Some(&Message::Simple("hello, {name}!".into()))
+		);
+		assert_eq!(
+			table.get("inbox.title"),
+			Some(&Message::Simple("inbox".into()))
+		);
+		let unread = table.get("inbox.unread").unwrap();
+		assert_eq!(
+			unread.template("en", Some(1)),
+			Some("{count} unread message")
+		);
+		assert_eq!(
+			unread.template("en", Some(2)),
+			Some("{count} unread messages")
+		);
+
+		assert!(parse_table("number = 5").is_err());
+	}
+}
@@ -0,0 +1,291 @@
+use raylib::prelude::RaylibDraw;
+
+use crate::{
+	core::{Event, MouseEvent, ReturnEvent, Store},
+	form::{control, FocusCommand, UniqueId},
+	Color, Details, Layable,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub enum ButtonState {
+	#[default]
+	Normal,
+	Hover,
+	/// pressed down with the mouse, fires if it gets released while still inside
+	Pressed,
+	Focused,
+	Disabled,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+/// how a [Button] looks in one [ButtonState]
+pub struct ButtonLook {
+	pub background: Color,
+	pub border: Option<Color>,
+}
+impl ButtonLook {
+	pub const fn new(background: Color, border: Option<Color>) -> Self {
+		Self { background, border }
+	}
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ButtonStyle {
+	pub normal: ButtonLook,
+	pub hover: ButtonLook,
+	pub pressed: ButtonLook,
+	pub focused: ButtonLook,
+	pub disabled: ButtonLook,
+	/// space between the edge of the button and its content, unscaled
+	pub padding: i32,
+}
+impl Default for ButtonStyle {
+	fn default() -> Self {
+		Self {
+			normal: ButtonLook::new(crate::color(60, 60, 60, 255), None),
+			hover: ButtonLook::new(crate::color(80, 80, 80, 255), None),
+			pressed: ButtonLook::new(crate::color(40, 40, 40, 255), None),
+			focused: ButtonLook::new(
+				crate::color(60, 60, 60, 255),
+				Some(crate::color(110, 170, 255, 255)),
+			),
+			disabled: ButtonLook::new(crate::color(35, 35, 35, 255), None),
+			padding: 6,
+		}
+	}
+}
+impl ButtonStyle {
+	pub fn look(&self, state: ButtonState) -> ButtonLook {
+		match state {
+			ButtonState::Normal => self.normal,
+			ButtonState::Hover => self.hover,
+			ButtonState::Pressed => self.pressed,
+			ButtonState::Focused => self.focused,
+			ButtonState::Disabled => self.disabled,
+		}
+	}
+}
+
+#[derive(Clone)]
+/// a button around any layable. \
+/// unlike [Clickable](crate::comp::Clickable), it fires when the mouse gets released inside it
+/// (after being pressed inside it), can be focused and activated with enter or space, can be disabled,
+/// and looks different in every [ButtonState]
+pub struct Button<L, F, T>
+where
+	T: 'static,
+	F: FnMut() -> T,
+	L: Layable,
+{
+	layable: L,
+	on_press: F,
+	uid: UniqueId,
+	style: ButtonStyle,
+	disabled: Store<bool>,
+
+	/// only ever [ButtonState::Normal], [ButtonState::Hover] or [ButtonState::Pressed],
+	/// focused and disabled are worked out when rendering
+	mouse_state: ButtonState,
+}
+impl<L: Layable + std::fmt::Debug, T, F: FnMut() -> T> std::fmt::Debug for Button<L, F, T> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("Button")
+			.field("layable", &self.layable)
+			.field("uid", &self.uid)
+			.field("style", &self.style)
+			.field("disabled", &self.disabled)
+			.field("mouse_state", &self.mouse_state)
+			.finish()
+	}
+}
+impl<L: Layable, T, F: FnMut() -> T> Button<L, F, T> {
+	pub fn new(layable: L, on_press: F) -> Self {
+		Self {
+			layable,
+			on_press,
+			uid: UniqueId::new(),
+			style: ButtonStyle::default(),
+			disabled: Store::new(false),
+			mouse_state: ButtonState::Normal,
+		}
+	}
+	pub fn with_style(self, style: ButtonStyle) -> Self {
+		Self { style, ..self }
+	}
+	/// the button is disabled while `disabled` is true
+	pub fn with_disabled(self, disabled: Store<bool>) -> Self {
+		Self { disabled, ..self }
+	}
+
+	pub fn uid(&self) -> UniqueId {
+		self.uid
+	}
+	pub fn is_disabled(&self) -> bool {
+		self.disabled.get()
+	}
+	/// the state the button's in, given what's focused right now
+	pub fn state(&self, focus: UniqueId) -> ButtonState {
+		if self.is_disabled() {
+			return ButtonState::Disabled;
+		}
+		match self.mouse_state {
+			ButtonState::Normal if focus == self.uid => ButtonState::Focused,
+			state => state,
+		}
+	}
+
+	fn bounds(&self, det: Details, scale: f32) -> Details {
+		let (w, h) = self.size();
+		Details {
+			aw: (w as f32 * scale) as i32,
+			ah: (h as f32 * scale) as i32,
+			..det
+		}
+	}
+	fn content_det(&self, det: Details, scale: f32) -> Details {
+		let bounds = self.bounds(det, scale);
+		let padding = (self.style.padding as f32 * scale) as i32;
+		Details {
+			x: bounds.x + padding,
+			y: bounds.y + padding,
+			aw: bounds.aw - padding * 2,
+			ah: bounds.ah - padding * 2,
+		}
+	}
+}
+
+impl<L: Layable, T, F: FnMut() -> T> Layable for Button<L, F, T> {
+	fn size(&self) -> (i32, i32) {
+		let (w, h) = self.layable.size();
+		(w + self.style.padding * 2, h + self.style.padding * 2)
+	}
+	fn render(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		let look = self.style.look(self.state(d.focus()));
+		let bounds = self.bounds(det, scale);
+
+		d.draw_rectangle(bounds.x, bounds.y, bounds.aw, bounds.ah, look.background);
+		if let Some(border) = look.border {
+			d.draw_rectangle_lines(bounds.x, bounds.y, bounds.aw, bounds.ah, border);
+		}
+
+		self.layable.render(d, self.content_det(det, scale), scale);
+	}
+
+	fn tick(&mut self) {
+		self.layable.tick();
+	}
+	fn pass_events(
+		&mut self,
+		events: impl Iterator<Item = Event>,
+		det: Details,
+		scale: f32,
+		ret_events: &mut Vec<ReturnEvent>,
+	) {
+		if self.is_disabled() {
+			self.mouse_state = ButtonState::Normal;
+			return;
+		}
+		let bounds = self.bounds(det, scale);
+
+		// the mouse can leave without us getting an event about it,
+		// so hover and pressed only last as long as events keep saying so
+		let (mut inside, mut held) = (false, false);
+		for event in events {
+			match event {
+				Event::MouseEvent(m_event) if !bounds.is_inside_tuple(m_event.at()) => {}
+				Event::MouseEvent(MouseEvent::MouseMove { .. }) => inside = true,
+				Event::MouseEvent(MouseEvent::MouseClick { .. }) => {
+					(inside, held) = (true, true);
+					self.mouse_state = ButtonState::Pressed;
+					ret_events.push(Event::ret(FocusCommand::Request(self.uid)));
+				}
+				Event::MouseEvent(MouseEvent::MouseHeld { .. }) => (inside, held) = (true, true),
+				Event::MouseEvent(MouseEvent::MouseRelease { .. }) => {
+					inside = true;
+					if self.mouse_state == ButtonState::Pressed {
+						ret_events.push(Event::ret((self.on_press)()));
+					}
+				}
+				event if control::is_activate_key(&event, self.uid) => {
+					ret_events.push(Event::ret((self.on_press)()))
+				}
+				_ => {}
+			}
+		}
+
+		self.mouse_state = match (self.mouse_state, inside, held) {
+			(ButtonState::Pressed, true, true) => ButtonState::Pressed,
+			(_, true, _) => ButtonState::Hover,
+			_ => ButtonState::Normal,
+		};
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::comp::Space;
+
+	fn mouse(event: MouseEvent) -> Event {
+		Event::MouseEvent(event)
+	}
+	fn pressed(ret_events: Vec<ReturnEvent>) -> usize {
+		ret_events
+			.into_iter()
+			.filter(|e| e.can_take::<&str>())
+			.count()
+	}
+
+	#[test]
+	fn test_button_fires_on_release() {
+		let mut button = Button::new(Space::new(10, 10), || "pressed");
+		let det = Details::new(0, 0, 100, 100);
+
+		let mut ret = Vec::new();
+		button.pass_events(
+			[
+				mouse(MouseEvent::MouseClick { x: 5, y: 5 }),
+				mouse(MouseEvent::MouseHeld { x: 5, y: 5 }),
+			]
+			.into_iter(),
+			det,
+			1.0,
+			&mut ret,
+		);
+		assert_eq!(button.state(UniqueId::null()), ButtonState::Pressed);
+		assert_eq!(pressed(ret), 0);
+
+		let mut ret = Vec::new();
+		button.pass_events(
+			[mouse(MouseEvent::MouseRelease { x: 5, y: 5 })].into_iter(),
+			det,
+			1.0,
+			&mut ret,
+		);
+		assert_eq!(pressed(ret), 1);
+		assert_eq!(button.state(UniqueId::null()), ButtonState::Hover);
+
+		// pressed, then dragged out and released
+		let mut ret = Vec::new();
+		button.pass_events(
+			[mouse(MouseEvent::MouseClick { x: 5, y: 5 })].into_iter(),
+			det,
+			1.0,
+			&mut ret,
+		);
+		button.pass_events(
+			[mouse(MouseEvent::MouseHeld { x: 50, y: 50 })].into_iter(),
+			det,
+			1.0,
+			&mut ret,
+		);
+		button.pass_events(
+			[mouse(MouseEvent::MouseRelease { x: 50, y: 50 })].into_iter(),
+			det,
+			1.0,
+			&mut ret,
+		);
+		assert_eq!(pressed(ret), 0);
+		assert_eq!(button.state(UniqueId::null()), ButtonState::Normal);
+	}
+}
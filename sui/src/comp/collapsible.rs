@@ -0,0 +1,360 @@
+use std::time::{Duration, Instant};
+
+use raylib::prelude::RaylibDraw;
+
+use super::tree::draw_arrow;
+use crate::{
+	core::{Event, MouseEvent, ReturnEvent, Store},
+	form::{control, Changed, FocusCommand, UniqueId},
+	Details, Layable,
+};
+
+const PADDING: i32 = 4;
+/// size of the expand/collapse arrow, unscaled
+const ARROW_SIZE: i32 = 12;
+
+#[derive(Clone, Debug)]
+/// a header with an arrow, and under it a body that's only shown while expanded. \
+/// clicking the header (or pressing enter or space while it's focused) expands or collapses it and
+/// returns a [Changed<bool>]. \
+/// whether it's expanded lives in a `Store<bool>`, so it survives being rebuilt, and it follows the store
+/// if that's changed from the outside. \
+/// with [Collapsible::animated], the body slides open and closed instead of just appearing
+pub struct Collapsible<H: Layable, B: Layable> {
+	uid: UniqueId,
+	expanded: Store<bool>,
+	header: H,
+	body: B,
+	duration: Option<Duration>,
+	/// when the last expand or collapse started, for the animation
+	transition: Option<Instant>,
+	/// what the store said last time we looked, to notice it changing from the outside
+	shown: bool,
+}
+impl<H: Layable, B: Layable> Collapsible<H, B> {
+	pub fn new(expanded: Store<bool>, header: H, body: B) -> Self {
+		let shown = expanded.get();
+		Self {
+			uid: UniqueId::new(),
+			expanded,
+			header,
+			body,
+			duration: None,
+			transition: None,
+			shown,
+		}
+	}
+	/// the body's height is animated over `duration` when it's expanded or collapsed
+	pub fn animated(self, duration: Duration) -> Self {
+		Self {
+			duration: Some(duration),
+			..self
+		}
+	}
+
+	/// the uid [Changed] events are returned with
+	pub fn uid(&self) -> UniqueId {
+		self.uid
+	}
+	pub fn expanded_store(&self) -> &Store<bool> {
+		&self.expanded
+	}
+	pub fn is_expanded(&self) -> bool {
+		self.expanded.get()
+	}
+	pub fn header(&self) -> &H {
+		&self.header
+	}
+	pub fn body(&self) -> &B {
+		&self.body
+	}
+
+	pub fn set_expanded(&mut self, expanded: bool, ret_events: &mut Vec<ReturnEvent>) {
+		if self.expanded.get() == expanded {
+			return;
+		}
+		self.expanded.set(expanded);
+		self.shown = expanded;
+		self.start_transition();
+		ret_events.push(Event::ret(Changed {
+			uid: self.uid,
+			value: expanded,
+		}));
+	}
+	fn start_transition(&mut self) {
+		let Some(duration) = self.duration else {
+			return;
+		};
+		let now = Instant::now();
+		// turning around halfway starts from where it's at
+		self.transition = match self.transition.map(|start| now - start) {
+			Some(elapsed) if elapsed < duration => Some(now - (duration - elapsed)),
+			_ => Some(now),
+		};
+	}
+
+	/// how much of the body is shown, from 0 to 1
+	fn openness(&self) -> f32 {
+		let expanded = self.expanded.get();
+		let (Some(duration), Some(start)) = (self.duration, self.transition) else {
+			return if expanded { 1.0 } else { 0.0 };
+		};
+		let t = (start.elapsed().as_secs_f32() / duration.as_secs_f32().max(f32::EPSILON)).min(1.0);
+		let eased = t * t * (3.0 - 2.0 * t);
+		if expanded {
+			eased
+		} else {
+			1.0 - eased
+		}
+	}
+
+	/// unscaled
+	fn header_height(&self) -> i32 {
+		self.header.size().1.max(ARROW_SIZE) + PADDING * 2
+	}
+	/// the header row, the arrow, where the header goes and the part of the body that's shown
+	fn split(&self, det: Details, scale: f32) -> (Details, Details, Details, Details) {
+		let s = |v: i32| (v as f32 * scale) as i32;
+		let row = Details {
+			ah: s(self.header_height()),
+			..det
+		};
+		let arrow = Details {
+			x: row.x + s(PADDING),
+			y: row.y + (row.ah - s(ARROW_SIZE)) / 2,
+			aw: s(ARROW_SIZE),
+			ah: s(ARROW_SIZE),
+		};
+		let header_x = arrow.x + arrow.aw + s(PADDING);
+		let header = Details {
+			x: header_x,
+			y: row.y + s(PADDING),
+			aw: (row.aw - (header_x - row.x)).max(0),
+			ah: row.ah - s(PADDING) * 2,
+		};
+		let body = Details {
+			y: row.y + row.ah,
+			ah: s((self.body.size().1 as f32 * self.openness()) as i32),
+			..det
+		};
+		(row, arrow, header, body)
+	}
+}
+
+impl<H: Layable, B: Layable> Layable for Collapsible<H, B> {
+	fn size(&self) -> (i32, i32) {
+		let (header_w, _) = self.header.size();
+		let (body_w, body_h) = self.body.size();
+		let w = (ARROW_SIZE + PADDING * 3 + header_w).max(body_w);
+		let h = self.header_height() + (body_h as f32 * self.openness()) as i32;
+		(w, h)
+	}
+	fn render(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		let (row, arrow, header, body) = self.split(det, scale);
+		if d.focus() == self.uid {
+			d.draw_rectangle_lines(row.x, row.y, row.aw, row.ah, control::ACCENT);
+		}
+		draw_arrow(d, arrow, self.expanded.get());
+		self.header.render(d, header, scale);
+
+		let openness = self.openness();
+		if openness >= 1.0 {
+			self.body.render(d, body, scale);
+		} else if openness > 0.0 {
+			// the body gets drawn at full height, just cut off
+			let full = Details {
+				ah: (self.body.size().1 as f32 * scale) as i32,
+				..body
+			};
+			unsafe { raylib::ffi::BeginScissorMode(body.x, body.y, body.aw, body.ah) };
+			self.body.render(d, full, scale);
+			unsafe { raylib::ffi::EndScissorMode() };
+		}
+	}
+
+	fn tick(&mut self) {
+		self.header.tick();
+		let expanded = self.expanded.get();
+		if expanded != self.shown {
+			self.shown = expanded;
+			self.start_transition();
+		}
+		if self.openness() > 0.0 {
+			self.body.tick();
+		}
+	}
+	fn pass_events(
+		&mut self,
+		events: impl Iterator<Item = Event>,
+		det: Details,
+		scale: f32,
+		ret_events: &mut Vec<ReturnEvent>,
+	) {
+		let events = events.collect::<Vec<_>>();
+		let (row, _, header, body) = self.split(det, scale);
+
+		let header_events = events.iter().copied().filter(|event| match event {
+			Event::MouseEvent(m_event) => header.is_inside_tuple(m_event.at()),
+			_ => true,
+		});
+		self.header
+			.pass_events(header_events, header, scale, ret_events);
+
+		for event in events.iter() {
+			match *event {
+				Event::MouseEvent(MouseEvent::MouseClick { x, y }) if row.is_inside(x, y) => {
+					ret_events.push(Event::ret(FocusCommand::Request(self.uid)));
+					self.set_expanded(!self.expanded.get(), ret_events);
+				}
+				event if control::is_activate_key(&event, self.uid) => {
+					self.set_expanded(!self.expanded.get(), ret_events);
+				}
+				_ => {}
+			}
+		}
+
+		if !self.expanded.get() {
+			return;
+		}
+		// a body that's sliding around doesn't get the mouse
+		let openness = self.openness();
+		let body_events = events.iter().copied().filter(|event| match event {
+			Event::MouseEvent(m_event) => openness >= 1.0 && body.is_inside_tuple(m_event.at()),
+			_ => true,
+		});
+		self.body.pass_events(body_events, body, scale, ret_events);
+	}
+}
+
+#[derive(Clone, Debug)]
+/// [Collapsible]s on top of each other, where expanding one collapses the rest
+pub struct Accordion<H: Layable, B: Layable> {
+	sections: Vec<Collapsible<H, B>>,
+}
+impl<H: Layable, B: Layable> Accordion<H, B> {
+	/// if more than one section starts out expanded, only the first one stays that way
+	pub fn new(sections: Vec<Collapsible<H, B>>) -> Self {
+		let mut accordion = Self { sections };
+		if let Some(open) = accordion.sections.iter().position(|s| s.is_expanded()) {
+			accordion.collapse_others(open, &mut Vec::new());
+		}
+		// nothing to animate yet
+		for section in accordion.sections.iter_mut() {
+			section.shown = section.expanded.get();
+			section.transition = None;
+		}
+		accordion
+	}
+	pub fn sections(&self) -> &[Collapsible<H, B>] {
+		&self.sections
+	}
+	/// the index of the expanded section, if there is one
+	pub fn expanded(&self) -> Option<usize> {
+		self.sections.iter().position(|s| s.is_expanded())
+	}
+
+	fn collapse_others(&mut self, open: usize, ret_events: &mut Vec<ReturnEvent>) {
+		for (i, section) in self.sections.iter_mut().enumerate() {
+			if i != open {
+				section.set_expanded(false, ret_events);
+			}
+		}
+	}
+	fn for_each_det(&self, det: Details, scale: f32) -> Vec<Details> {
+		let mut y = det.y;
+		(self.sections.iter())
+			.map(|section| {
+				let h = (section.size().1 as f32 * scale) as i32;
+				y += h;
+				Details {
+					y: y - h,
+					ah: h,
+					..det
+				}
+			})
+			.collect()
+	}
+}
+
+impl<H: Layable, B: Layable> Layable for Accordion<H, B> {
+	fn size(&self) -> (i32, i32) {
+		(self.sections.iter())
+			.map(|section| section.size())
+			.fold((0, 0), |(w, h), (s_w, s_h)| (w.max(s_w), h + s_h))
+	}
+	fn render(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		for (section, s_det) in self.sections.iter().zip(self.for_each_det(det, scale)) {
+			section.render(d, s_det, scale);
+		}
+	}
+
+	fn tick(&mut self) {
+		// the stores may have been changed already, what each section showed last is what it was before
+		let was_shown = (self.sections.iter())
+			.map(|section| section.shown)
+			.collect::<Vec<_>>();
+		for section in self.sections.iter_mut() {
+			section.tick();
+		}
+		// one got expanded through its store
+		let opened = (self.sections.iter().zip(was_shown))
+			.position(|(section, was_shown)| section.is_expanded() && !was_shown);
+		if let Some(open) = opened {
+			self.collapse_others(open, &mut Vec::new());
+		}
+	}
+	fn pass_events(
+		&mut self,
+		events: impl Iterator<Item = Event>,
+		det: Details,
+		scale: f32,
+		ret_events: &mut Vec<ReturnEvent>,
+	) {
+		let events = events.collect::<Vec<_>>();
+		let dets = self.for_each_det(det, scale);
+		for (i, s_det) in dets.into_iter().enumerate() {
+			let was_expanded = self.sections[i].is_expanded();
+			let s_events = events.iter().copied().filter(|event| match event {
+				Event::MouseEvent(m_event) => s_det.is_inside_tuple(m_event.at()),
+				_ => true,
+			});
+			self.sections[i].pass_events(s_events, s_det, scale, ret_events);
+			if !was_expanded && self.sections[i].is_expanded() {
+				self.collapse_others(i, ret_events);
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::comp::Space;
+
+	#[test]
+	fn test_accordion_store() {
+		let stores = (0..3).map(|_| Store::new(false)).collect::<Vec<_>>();
+		stores[1].set(true);
+		let sections = (stores.iter())
+			.map(|store| Collapsible::new(store.clone(), Space::new(10, 10), Space::new(10, 10)))
+			.collect();
+		let mut accordion = Accordion::new(sections);
+		assert_eq!(accordion.expanded(), Some(1));
+
+		// opening one above the expanded one from the outside keeps it, and closes the other
+		stores[0].set(true);
+		accordion.tick();
+		assert_eq!(accordion.expanded(), Some(0));
+		assert!(!stores[1].get());
+
+		stores[2].set(true);
+		accordion.tick();
+		assert_eq!(accordion.expanded(), Some(2));
+		assert!(!stores[0].get());
+
+		// closing it from the outside doesn't open anything
+		stores[2].set(false);
+		accordion.tick();
+		assert_eq!(accordion.expanded(), None);
+	}
+}
@@ -212,7 +212,7 @@ impl<D: DivComponents> Layable for Div<D> {
 
 		self.for_each_mut(det, scale, |comp, l_det| {
 			let l_events = events.iter().cloned().filter_map(|event| match event {
-				Event::KeyboardEvent(..) => Some(event),
+				Event::KeyboardEvent(..) | Event::LayerEvent(..) => Some(event),
 				Event::MouseEvent(m_event) => {
 					if l_det.is_inside_tuple(m_event.at()) {
 						Some(event)
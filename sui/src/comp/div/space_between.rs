@@ -140,8 +140,13 @@ impl<D: DivComponents> Layable for SpaceBetween<D> {
 					}
 				}
 			}
-			Event::MouseEvent(m_event) => {
-				if !det.is_inside_tuple(m_event.at()) {
+			// layer events go to everyone, they need the right det to know where their layer is though
+			Event::MouseEvent(_) | Event::LayerEvent(..) => {
+				let m_event = match event {
+					Event::MouseEvent(m_event) => Some(m_event),
+					_ => None,
+				};
+				if m_event.is_some_and(|m_event| !det.is_inside_tuple(m_event.at())) {
 					return;
 				}
 
@@ -165,10 +170,10 @@ impl<D: DivComponents> Layable for SpaceBetween<D> {
 					if PASS_EVENT_DEBUG {
 						println!(
 							"SpaceBetween handling mouse event; {l_det:?}, {:?}",
-							m_event.at()
+							m_event.map(|m_event| m_event.at())
 						);
 					}
-					if l_det.is_inside_tuple(m_event.at()) {
+					if m_event.is_none_or(|m_event| l_det.is_inside_tuple(m_event.at())) {
 						comp.pass_events(std::iter::once(event), l_det, scale, ret_events);
 					}
 
@@ -7,6 +7,9 @@ pub use fixed_size::FixedSize;
 pub mod scrollable;
 pub use scrollable::{Scrollable, View};
 
+pub mod virtual_list;
+pub use virtual_list::VirtualList;
+
 pub mod crop;
 pub use crop::Crop;
 
@@ -9,8 +9,8 @@ use super::Crop;
 
 pub const SCROLLBAR_WIDTH: f32 = 10.0; // it's getting multiplied by scale anyway so we just savin a step
 pub const SCROLLBAR_LENGTH: f32 = SCROLLBAR_WIDTH * 4.0;
-const SCROLLBAR_BG_COLOR: raylib::color::Color = crate::color(33, 35, 38, 255);
-const SCROLLBAR_HANDLE_COLOR: raylib::color::Color = crate::color(106, 113, 122, 255);
+pub(crate) const SCROLLBAR_BG_COLOR: raylib::color::Color = crate::color(33, 35, 38, 255);
+pub(crate) const SCROLLBAR_HANDLE_COLOR: raylib::color::Color = crate::color(106, 113, 122, 255);
 
 const DEBUG: bool = false;
 const DEBUG_SCROLLBAR: bool = false;
@@ -128,13 +128,13 @@ impl<L: Layable> Scrollable<L> {
 		}
 	}
 
-	fn for_each_scrollbar(
+	/// the scrollbars that are shown, the one on the side first
+	fn scrollbars(
 		&self,
 		l_size: Option<(i32, i32)>,
 		view_det: crate::Details,
 		scale: f32,
-		mut f: impl FnMut((i32, i32, i32, i32), (i32, i32, i32, i32), bool), // last arg is whether the scrollbar's on the bottom
-	) {
+	) -> Vec<Scrollbar> {
 		let (l_w, l_h) = l_size.unwrap_or_else(|| self.layable.size());
 		let (scrollbar_at_side, scrollbar_at_bottom) = self.mode.bools();
 
@@ -145,61 +145,41 @@ impl<L: Layable> Scrollable<L> {
 			dbg!(scrollbar_at_side, scrollbar_at_bottom);
 		}
 
+		let scrollbar_w = (SCROLLBAR_WIDTH * scale) as i32;
+		let mut scrollbars = Vec::with_capacity(2);
 		if scrollbar_at_side {
-			let (scrollbar_base_x, scrollbar_base_y) = (view_det.x + l_w, view_det.y);
-			let (scrollbar_w, scrollbar_h) = ((SCROLLBAR_WIDTH * scale) as i32, view_det.ah);
-
-			let (_, scroll_y) = (self.state.scroll_x, self.state.scroll_y);
-
-			let (scrollbar_handle_w, scrollbar_handle_h) = (
-				(SCROLLBAR_WIDTH * scale) as i32,
-				(SCROLLBAR_LENGTH * scale) as i32,
-			);
-			let (scrollbar_handle_base_x, scrollbar_handle_base_y) = (
-				scrollbar_base_x,
-				scrollbar_base_y
-					+ (scroll_y as f32 / (l_h - view_det.ah) as f32
-						* (view_det.ah as f32 - scrollbar_handle_h as f32)) as i32,
-			);
-			f(
-				(scrollbar_base_x, scrollbar_base_y, scrollbar_w, scrollbar_h),
-				(
-					scrollbar_handle_base_x,
-					scrollbar_handle_base_y,
-					scrollbar_handle_w,
-					scrollbar_handle_h,
-				),
+			let track = crate::Details {
+				x: view_det.x + l_w,
+				y: view_det.y,
+				aw: scrollbar_w,
+				ah: view_det.ah,
+			};
+			let max_scroll = l_h - view_det.ah;
+			scrollbars.push(Scrollbar::new(
+				track,
 				false,
-			);
+				self.state.scroll_y,
+				max_scroll,
+				scale,
+			));
 		}
 		if scrollbar_at_bottom {
-			let (scrollbar_base_x, scrollbar_base_y) = (view_det.x, view_det.y + l_h);
-			let (scrollbar_w, scrollbar_h) = (view_det.aw, (SCROLLBAR_WIDTH * scale) as i32);
-
-			let (scroll_x, _) = (self.state.scroll_x, self.state.scroll_y);
-
-			let (scrollbar_handle_w, scrollbar_handle_h) = (
-				// ((l_w as f32 - det.aw as f32) / l_w as f32 * det.aw as f32 * scale) as i32,
-				(SCROLLBAR_LENGTH * scale) as i32,
-				(SCROLLBAR_WIDTH * scale) as i32,
-			);
-			let (scrollbar_handle_base_x, scrollbar_handle_base_y) = (
-				scrollbar_base_x
-					+ (scroll_x as f32 / (l_w - view_det.aw) as f32
-						* (view_det.aw as f32 - scrollbar_handle_w as f32)) as i32,
-				scrollbar_base_y,
-			);
-			f(
-				(scrollbar_base_x, scrollbar_base_y, scrollbar_w, scrollbar_h),
-				(
-					scrollbar_handle_base_x,
-					scrollbar_handle_base_y,
-					scrollbar_handle_w,
-					scrollbar_handle_h,
-				),
+			let track = crate::Details {
+				x: view_det.x,
+				y: view_det.y + l_h,
+				aw: view_det.aw,
+				ah: scrollbar_w,
+			};
+			let max_scroll = l_w - view_det.aw;
+			scrollbars.push(Scrollbar::new(
+				track,
 				true,
-			);
+				self.state.scroll_x,
+				max_scroll,
+				scale,
+			));
 		}
+		scrollbars
 	}
 	fn clamp(&mut self, det: Details, l_size: Option<(i32, i32)>) {
 		let (vert, horiz) = self.mode.bools();
@@ -224,33 +204,12 @@ impl<L: Layable> Layable for Scrollable<L> {
 		let view_det = self.l_det(det, scale, Some((l_w, l_h)));
 		view.render(d, view_det, scale);
 
-		self.for_each_scrollbar(
-			Some((l_w, l_h)),
-			view_det,
-			scale,
-			|(scrollbar_base_x, scrollbar_base_y, scrollbar_w, scrollbar_h),
-			 (handle_base_x, handle_base_y, handle_w, handle_h),
-			 _| {
-				if DEBUG_SCROLLBAR {
-					dbg!(scrollbar_base_x, scrollbar_base_y, scrollbar_w, scrollbar_h);
-				}
-
-				d.draw_rectangle(
-					scrollbar_base_x,
-					scrollbar_base_y,
-					scrollbar_w,
-					scrollbar_h,
-					SCROLLBAR_BG_COLOR,
-				);
-				d.draw_rectangle(
-					handle_base_x,
-					handle_base_y,
-					handle_w,
-					handle_h,
-					SCROLLBAR_HANDLE_COLOR,
-				);
-			},
-		);
+		for scrollbar in self.scrollbars(Some((l_w, l_h)), view_det, scale) {
+			if DEBUG_SCROLLBAR {
+				dbg!(scrollbar.track);
+			}
+			scrollbar.render(d);
+		}
 
 		if DEBUG {
 			d.draw_text(
@@ -293,77 +252,29 @@ impl<L: Layable> Layable for Scrollable<L> {
 					}
 					self.clamp(det, None);
 				}
-				Event::MouseEvent(MouseEvent::MouseClick {
-					x: mouse_x,
-					y: mouse_y,
-				}) => {
-					let mut state = self.state;
-					self.for_each_scrollbar(
-						Some((l_w, l_h)),
-						view_det,
-						scale,
-						|_, handle, bottom| {
-							let handle_det = crate::Details {
-								x: handle.0,
-								y: handle.1,
-								aw: handle.2,
-								ah: handle.3,
-							};
-
-							if handle_det.is_inside(mouse_x, mouse_y) {
-								state.action = if bottom {
-									ScrollbarAction::ScrollingXFrom {
-										before: state.scroll_x,
-										drag_start_c: mouse_x,
-									}
-								} else {
-									ScrollbarAction::ScrollingYFrom {
-										before: state.scroll_y,
-										drag_start_c: mouse_y,
-									}
-								}
-							}
-						},
-					);
-					self.state = state;
+				Event::MouseEvent(MouseEvent::MouseClick { x, y }) => {
+					let scrollbars = self.scrollbars(Some((l_w, l_h)), view_det, scale);
+					let action = (scrollbars.iter())
+						.find_map(|scrollbar| scrollbar.click(&self.state, (x, y)));
+					if let Some(action) = action {
+						self.state.action = action;
+					}
 				}
-				Event::MouseEvent(MouseEvent::MouseHeld {
-					x: mouse_x,
-					y: mouse_y,
-				}) => {
-					match self.state.action {
-						ScrollbarAction::ScrollingXFrom {
-							before,
-							drag_start_c,
-						} => {
-							let og = before
-								+ ((mouse_x - drag_start_c) as f32
-									/ (view_det.aw as f32 - SCROLLBAR_LENGTH * scale)
-									* (l_w - view_det.aw) as f32) as i32;
-
-							// i know this is a little unreadable :(
-							// basically all this math is just the inverse of how we calculate where the scrollbar handle should be
-							// same for ScrollingFromY
-
-							self.state.scroll_x = og
+				Event::MouseEvent(MouseEvent::MouseHeld { x, y }) => {
+					for scrollbar in self.scrollbars(Some((l_w, l_h)), view_det, scale) {
+						let Some(scroll) = scrollbar.drag(self.state.action, (x, y)) else {
+							continue;
+						};
+						if scrollbar.horizontal {
+							self.state.scroll_x = scroll
 								.min(l_w - det.aw + (SCROLLBAR_WIDTH * scale * mul_x) as i32)
 								.max(0);
-						}
-						ScrollbarAction::ScrollingYFrom {
-							before,
-							drag_start_c,
-						} => {
-							let og = before
-								+ ((mouse_y - drag_start_c) as f32
-									/ (view_det.ah as f32 - SCROLLBAR_LENGTH * scale)
-									* (l_h - view_det.ah) as f32) as i32;
-
-							self.state.scroll_y = og
+						} else {
+							self.state.scroll_y = scroll
 								.min(l_h - det.ah + (SCROLLBAR_WIDTH * scale * mul_y) as i32)
 								.max(0);
 						}
-						_ => (), // no action has been started
-					};
+					}
 				}
 				Event::MouseEvent(MouseEvent::MouseRelease { .. }) => {
 					// expects everything to be handled in Event::MouseHeld
@@ -378,6 +289,100 @@ impl<L: Layable> Layable for Scrollable<L> {
 	}
 }
 
+#[derive(Copy, Clone, Debug)]
+/// one scrollbar: its track and its handle, scaled and in window coords. \
+/// shared by [Scrollable] and [VirtualList](super::VirtualList)
+pub(crate) struct Scrollbar {
+	pub track: Details,
+	pub handle: Details,
+	/// whether it's on the bottom, scrolling sideways
+	pub horizontal: bool,
+	/// how far the content can be scrolled, unscaled
+	max_scroll: i32,
+}
+impl Scrollbar {
+	/// the handle sits as far along `track` as `scroll` is along `max_scroll`
+	pub fn new(track: Details, horizontal: bool, scroll: i32, max_scroll: i32, scale: f32) -> Self {
+		let track_len = if horizontal { track.aw } else { track.ah };
+		let handle_len = ((SCROLLBAR_LENGTH * scale) as i32).min(track_len);
+		let scroll = scroll.clamp(0, max_scroll.max(0)) as f32 / max_scroll.max(1) as f32;
+		let offset = ((track_len - handle_len) as f32 * scroll) as i32;
+		let handle = if horizontal {
+			Details {
+				x: track.x + offset,
+				aw: handle_len,
+				..track
+			}
+		} else {
+			Details {
+				y: track.y + offset,
+				ah: handle_len,
+				..track
+			}
+		};
+		Self {
+			track,
+			handle,
+			horizontal,
+			max_scroll,
+		}
+	}
+	pub fn render(&self, d: &mut crate::Handle) {
+		let (track, handle) = (self.track, self.handle);
+		d.draw_rectangle(track.x, track.y, track.aw, track.ah, SCROLLBAR_BG_COLOR);
+		d.draw_rectangle(
+			handle.x,
+			handle.y,
+			handle.aw,
+			handle.ah,
+			SCROLLBAR_HANDLE_COLOR,
+		);
+	}
+	/// the drag a click at `(x, y)` starts, if it's on the handle
+	pub fn click(&self, state: &ScrollableState, (x, y): (i32, i32)) -> Option<ScrollbarAction> {
+		if !self.handle.is_inside(x, y) {
+			return None;
+		}
+		Some(match self.horizontal {
+			true => ScrollbarAction::ScrollingXFrom {
+				before: state.scroll_x,
+				drag_start_c: x,
+			},
+			false => ScrollbarAction::ScrollingYFrom {
+				before: state.scroll_y,
+				drag_start_c: y,
+			},
+		})
+	}
+	/// where `action` scrolls to with the pointer at `(x, y)`, if it's dragging this scrollbar. not clamped
+	pub fn drag(&self, action: ScrollbarAction, (x, y): (i32, i32)) -> Option<i32> {
+		let (before, drag_start_c, c) = match (action, self.horizontal) {
+			(
+				ScrollbarAction::ScrollingXFrom {
+					before,
+					drag_start_c,
+				},
+				true,
+			) => (before, drag_start_c, x),
+			(
+				ScrollbarAction::ScrollingYFrom {
+					before,
+					drag_start_c,
+				},
+				false,
+			) => (before, drag_start_c, y),
+			_ => return None,
+		};
+		// the inverse of how the handle's position is worked out
+		let (track_len, handle_len) = match self.horizontal {
+			true => (self.track.aw, self.handle.aw),
+			false => (self.track.ah, self.handle.ah),
+		};
+		let moved = (c - drag_start_c) as f32 / (track_len - handle_len).max(1) as f32;
+		Some(before + (moved * self.max_scroll as f32) as i32)
+	}
+}
+
 #[derive(Clone, Debug)]
 /// Renders `self.layable`, with an offset that it will appear as though `self.layable` is rendering from `(self.base_x, self.base_y)`
 ///
@@ -427,7 +432,8 @@ impl<L: Layable> Layable for View<L> {
 		ret_events: &mut Vec<ReturnEvent>,
 	) {
 		let event_f = |event| match event {
-			Event::KeyboardEvent(_, _) => event,
+			// layer events are in window coords no matter what
+			Event::KeyboardEvent(_, _) | Event::LayerEvent(_, _) => event,
 			Event::MouseEvent(a) => Event::MouseEvent(
 				a.with_cursor_pos_transform(|(x, y)| (x + self.base_x, y + self.base_y)),
 			),
@@ -438,3 +444,29 @@ impl<L: Layable> Layable for View<L> {
 			.pass_events(events, self.l_det(det, scale), scale, ret_events)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_scrollbar() {
+		// the handle is 40 long, so it has 100 to move along
+		let scrollbar = Scrollbar::new(Details::new(0, 0, 10, 140), false, 50, 200, 1.0);
+		let handle = scrollbar.handle;
+		assert_eq!((handle.x, handle.y, handle.aw, handle.ah), (0, 25, 10, 40));
+
+		let state = ScrollableState {
+			scroll_y: 50,
+			..Default::default()
+		};
+		assert_eq!(scrollbar.click(&state, (5, 10)), None);
+		let action = scrollbar.click(&state, (5, 30)).unwrap();
+		// half of the way it can move is half of the way the content can scroll
+		assert_eq!(scrollbar.drag(action, (5, 80)), Some(150));
+
+		// it's not the one being dragged
+		let horizontal = Scrollbar::new(Details::new(0, 0, 140, 10), true, 0, 200, 1.0);
+		assert_eq!(horizontal.drag(action, (5, 80)), None);
+	}
+}
@@ -0,0 +1,295 @@
+use std::cell::RefCell;
+
+use crate::{
+	core::{Event, MouseEvent, ReturnEvent},
+	Details, Layable,
+};
+
+use super::scrollable::{ScrollableState, Scrollbar, ScrollbarAction, SCROLLBAR_WIDTH};
+
+/// a vertically scrolling list of `len` rows, where only the rows that are actually on screen get built,
+/// measured and rendered. \
+/// rows are built by calling `builder` with their index every time they're needed, so anything a row
+/// needs to remember has to live in a [Store](crate::core::Store). \
+/// rows that haven't been on screen yet are assumed to be as tall as the first row that was, so the
+/// scrollbar might shift a bit as new rows get measured. use [VirtualList::with_row_height] if every row
+/// is the same height, that also skips measuring entirely. \
+/// like [Scrollable](super::Scrollable), this returns the size of the whole list, so put it in a
+/// [FixedSize](super::FixedSize)
+pub struct VirtualList<L: Layable, F: Fn(usize) -> L> {
+	state: ScrollableState,
+	len: usize,
+	builder: F,
+	/// if every row is this tall, unscaled
+	row_height: Option<i32>,
+	/// unscaled sizes of the rows that have been on screen. only grows once rows get measured
+	sizes: RefCell<Vec<Option<(i32, i32)>>>,
+}
+impl<L: Layable, F: Fn(usize) -> L> std::fmt::Debug for VirtualList<L, F> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("VirtualList")
+			.field("state", &self.state)
+			.field("len", &self.len)
+			.field("row_height", &self.row_height)
+			.finish_non_exhaustive()
+	}
+}
+impl<L: Layable, F: Fn(usize) -> L> VirtualList<L, F> {
+	pub fn new(state: ScrollableState, len: usize, builder: F) -> Self {
+		Self {
+			state,
+			len,
+			builder,
+			row_height: None,
+			sizes: RefCell::new(Vec::new()),
+		}
+	}
+	/// every row is `row_height` tall (unscaled), so they don't have to be measured
+	pub fn with_row_height(self, row_height: i32) -> Self {
+		Self {
+			row_height: Some(row_height.max(1)),
+			..self
+		}
+	}
+
+	pub fn state(&self) -> ScrollableState {
+		self.state
+	}
+	pub fn len(&self) -> usize {
+		self.len
+	}
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+	/// for lists that grow or shrink, like logs. rows that were already measured stay measured
+	pub fn set_len(&mut self, len: usize) {
+		self.len = len;
+		self.sizes.get_mut().truncate(len);
+	}
+	/// scrolls so row `index` is at the top
+	pub fn scroll_to(&mut self, index: usize) {
+		let index = index.min(self.len);
+		self.state.scroll_y = (0..index).map(|i| self.row_height_of(i)).sum();
+	}
+
+	/// unscaled
+	fn row_height_of(&self, index: usize) -> i32 {
+		if let Some(row_height) = self.row_height {
+			return row_height;
+		}
+		let sizes = self.sizes.borrow();
+		match sizes.get(index).copied().flatten() {
+			Some((_, h)) => h,
+			None => sizes.iter().flatten().next().map(|(_, h)| *h).unwrap_or(0),
+		}
+	}
+	/// unscaled
+	fn total_height(&self) -> i32 {
+		match self.row_height {
+			Some(row_height) => row_height * self.len as i32,
+			None => (0..self.len).map(|i| self.row_height_of(i)).sum(),
+		}
+	}
+	/// where the rows and the scrollbar go. the total height is O(len) for rows of different heights,
+	/// so this gets worked out once per render or pass_events
+	fn layout(&self, det: Details, scale: f32) -> ListLayout {
+		let total = self.total_height();
+		let max_scroll = (total - (det.ah as f32 / scale) as i32).max(0);
+		if (total as f32 * scale) as i32 <= det.ah {
+			return ListLayout {
+				rows: det,
+				track: None,
+				max_scroll,
+			};
+		}
+		let bar_w = (SCROLLBAR_WIDTH * scale) as i32;
+		let rows = Details {
+			aw: det.aw - bar_w,
+			..det
+		};
+		let track = Details {
+			x: det.x + det.aw - bar_w,
+			aw: bar_w,
+			..det
+		};
+		ListLayout {
+			rows,
+			track: Some(track),
+			max_scroll,
+		}
+	}
+	fn scrollbar(&self, layout: &ListLayout, scale: f32) -> Option<Scrollbar> {
+		let track = layout.track?;
+		let scroll = self.state.scroll_y;
+		Some(Scrollbar::new(
+			track,
+			false,
+			scroll,
+			layout.max_scroll,
+			scale,
+		))
+	}
+
+	/// builds every row that's on screen, measuring the ones that haven't been yet
+	fn for_each_visible(
+		&self,
+		det: Details,
+		layout: &ListLayout,
+		scale: f32,
+		mut f: impl FnMut(L, Details),
+	) {
+		let rows_det = layout.rows;
+		let scroll = self.state.scroll_y.clamp(0, layout.max_scroll);
+		let viewport = (det.ah as f32 / scale) as i32;
+
+		let (mut index, mut y) = match self.row_height {
+			Some(row_height) => {
+				let index = (scroll / row_height) as usize;
+				(index, index as i32 * row_height)
+			}
+			None => row_at(scroll, self.len, |i| self.row_height_of(i)),
+		};
+		while index < self.len && y < scroll + viewport {
+			let row = (self.builder)(index);
+			let h = match self.row_height {
+				Some(row_height) => row_height,
+				None => {
+					let size = row.size();
+					let mut sizes = self.sizes.borrow_mut();
+					if sizes.len() <= index {
+						sizes.resize(self.len, None);
+					}
+					sizes[index] = Some(size);
+					size.1
+				}
+			};
+			let row_det = Details {
+				x: rows_det.x,
+				y: rows_det.y + ((y - scroll) as f32 * scale) as i32,
+				aw: rows_det.aw,
+				ah: (h as f32 * scale) as i32,
+			};
+			f(row, row_det);
+
+			index += 1;
+			y += h;
+		}
+	}
+}
+
+#[derive(Copy, Clone, Debug)]
+/// see [VirtualList::layout]
+struct ListLayout {
+	/// the part of `det` the rows go in
+	rows: Details,
+	/// the scrollbar's track, if the rows don't fit
+	track: Option<Details>,
+	/// the furthest the list can be scrolled, unscaled
+	max_scroll: i32,
+}
+
+/// the first row that ends after `offset` (or starts at it, for rows that haven't been measured yet),
+/// and where it starts. `height` is the height of row `i`
+pub fn row_at(offset: i32, len: usize, height: impl Fn(usize) -> i32) -> (usize, i32) {
+	let mut y = 0;
+	for i in 0..len {
+		let h = height(i);
+		if y + h > offset || y >= offset {
+			return (i, y);
+		}
+		y += h;
+	}
+	(len, y)
+}
+
+impl<L: Layable, F: Fn(usize) -> L> Layable for VirtualList<L, F> {
+	fn size(&self) -> (i32, i32) {
+		let w = (self.sizes.borrow().iter().flatten())
+			.map(|(w, _)| *w)
+			.max()
+			.unwrap_or_default();
+		(w, self.total_height())
+	}
+	fn render(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		let layout = self.layout(det, scale);
+		let rows = layout.rows;
+		unsafe { raylib::ffi::BeginScissorMode(rows.x, rows.y, rows.aw, rows.ah) };
+		self.for_each_visible(det, &layout, scale, |row, row_det| {
+			row.render(d, row_det, scale)
+		});
+		unsafe { raylib::ffi::EndScissorMode() };
+
+		if let Some(scrollbar) = self.scrollbar(&layout, scale) {
+			scrollbar.render(d);
+		}
+	}
+
+	fn pass_events(
+		&mut self,
+		events: impl Iterator<Item = Event>,
+		det: Details,
+		scale: f32,
+		ret_events: &mut Vec<ReturnEvent>,
+	) {
+		let events = events.collect::<Vec<_>>();
+		let layout = self.layout(det, scale);
+		for event in events.iter().copied() {
+			match event {
+				Event::MouseEvent(MouseEvent::Scroll { amount, .. }) => {
+					self.state.scroll_y -= (amount * 10.0) as i32;
+				}
+				Event::MouseEvent(MouseEvent::MouseClick { x, y }) => {
+					let Some(scrollbar) = self.scrollbar(&layout, scale) else {
+						continue;
+					};
+					if let Some(action) = scrollbar.click(&self.state, (x, y)) {
+						self.state.action = action;
+					}
+				}
+				Event::MouseEvent(MouseEvent::MouseHeld { x, y }) => {
+					let Some(scrollbar) = self.scrollbar(&layout, scale) else {
+						continue;
+					};
+					if let Some(scroll) = scrollbar.drag(self.state.action, (x, y)) {
+						self.state.scroll_y = scroll;
+					}
+				}
+				Event::MouseEvent(MouseEvent::MouseRelease { .. }) => {
+					self.state.action = ScrollbarAction::None;
+				}
+				_ => {}
+			}
+			self.state.scroll_y = self.state.scroll_y.clamp(0, layout.max_scroll);
+		}
+
+		// the rows get thrown away after this, but whatever they return doesn't
+		let rows_det = layout.rows;
+		self.for_each_visible(det, &layout, scale, |mut row, row_det| {
+			let row_events = events.iter().copied().filter(|event| match event {
+				Event::MouseEvent(m_event) => {
+					rows_det.is_inside_tuple(m_event.at()) && row_det.is_inside_tuple(m_event.at())
+				}
+				_ => true,
+			});
+			row.pass_events(row_events, row_det, scale, ret_events);
+		});
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_row_at() {
+		let heights = [10, 20, 30];
+		let height = |i: usize| heights[i];
+		assert_eq!(row_at(0, 3, height), (0, 0));
+		assert_eq!(row_at(9, 3, height), (0, 0));
+		assert_eq!(row_at(10, 3, height), (1, 10));
+		assert_eq!(row_at(35, 3, height), (2, 30));
+		assert_eq!(row_at(60, 3, height), (3, 60));
+		assert_eq!(row_at(5, 0, height), (0, 0));
+		assert_eq!(row_at(0, 3, |_| 0), (0, 0));
+	}
+}
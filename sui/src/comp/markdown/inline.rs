@@ -0,0 +1,58 @@
+use super::LinkClicked;
+use crate::{
+	comp::{text::Run, WrappedText},
+	core::{Event, MouseEvent, ReturnEvent},
+	Details, Layable,
+};
+
+#[derive(Clone, Debug)]
+/// a paragraph made of runs with different styles, word wrapped as a whole by a
+/// [styled](WrappedText::styled) [WrappedText]. \
+/// runs with a link are underlined, and return a [LinkClicked] when they're clicked
+pub struct Inline {
+	text: WrappedText<'static>,
+	/// the link of every run, if it has one
+	links: Vec<Option<String>>,
+}
+impl Inline {
+	pub fn new(runs: Vec<(Run, Option<String>)>) -> Self {
+		let (runs, links): (Vec<_>, Vec<_>) = runs
+			.into_iter()
+			.map(|(run, link)| {
+				let underline = run.underline || link.is_some();
+				(Run { underline, ..run }, link)
+			})
+			.unzip();
+		Self {
+			text: WrappedText::styled(runs),
+			links,
+		}
+	}
+}
+
+impl Layable for Inline {
+	fn size(&self) -> (i32, i32) {
+		self.text.size()
+	}
+	fn render(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		self.text.render(d, det, scale)
+	}
+
+	fn pass_events(
+		&mut self,
+		events: impl Iterator<Item = Event>,
+		det: Details,
+		scale: f32,
+		ret_events: &mut Vec<ReturnEvent>,
+	) {
+		for event in events {
+			let Event::MouseEvent(MouseEvent::MouseClick { x, y }) = event else {
+				continue;
+			};
+			let clicked = self.text.run_at(det, scale, x, y);
+			if let Some(url) = clicked.and_then(|run| self.links[run].clone()) {
+				ret_events.push(Event::ret(LinkClicked { url }));
+			}
+		}
+	}
+}
@@ -0,0 +1,246 @@
+//! commonmark rendering, built out of [Div]s, [Margin](crate::comp::Margin)s and [Text](crate::Text)s. \
+//! requires the `markdown` feature
+
+use crate::{
+	comp::{
+		self,
+		primitives::space::MarginValues,
+		text::{registry, Font, FontKey, Run, DEFAULT_COLOR},
+		Comp, Div,
+	},
+	core::{Event, ReturnEvent},
+	Color, Details, Layable, LayableExt,
+};
+
+pub mod parse;
+pub use parse::{Block, Span, SpanStyle};
+
+pub mod inline;
+pub use inline::Inline;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// returned when a link in a [Markdown] gets clicked
+pub struct LinkClicked {
+	pub url: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct MarkdownStyle {
+	pub size: i32,
+	/// sizes of h1 to h6
+	pub heading_sizes: [i32; 6],
+
+	pub font: Font,
+	/// falls back to [Self::font]
+	pub bold_font: Option<Font>,
+	/// falls back to [Self::font]
+	pub italic_font: Option<Font>,
+	/// falls back to [Self::font]
+	pub code_font: Option<Font>,
+
+	pub color: Color,
+	pub link_color: Color,
+	pub code_color: Color,
+	pub code_background: Color,
+	pub rule_color: Color,
+
+	/// how far lists and quotes are indented
+	pub indent: i32,
+	/// space between blocks
+	pub spacing: i32,
+}
+impl Default for MarkdownStyle {
+	fn default() -> Self {
+		Self {
+			size: 16,
+			heading_sizes: [32, 26, 22, 18, 16, 16],
+
+			font: Font::default(),
+			bold_font: None,
+			italic_font: None,
+			code_font: None,
+
+			color: DEFAULT_COLOR,
+			link_color: crate::color(110, 170, 255, 255),
+			code_color: crate::color(230, 200, 130, 255),
+			code_background: crate::color(40, 40, 40, 255),
+			rule_color: crate::color(90, 90, 90, 255),
+
+			indent: 20,
+			spacing: 8,
+		}
+	}
+}
+impl MarkdownStyle {
+	/// takes the regular, bold, italic fonts of `family` from the [font registry](registry),
+	/// and `code_family`'s regular font for code
+	pub fn with_families(family: &str, code_family: Option<&str>) -> Self {
+		Self {
+			font: registry::get(&FontKey::new(family)),
+			bold_font: Some(registry::get(&FontKey::new(family).bold())),
+			italic_font: Some(registry::get(&FontKey::new(family).italic())),
+			code_font: code_family.map(|family| registry::get(&FontKey::new(family))),
+			..Default::default()
+		}
+	}
+
+	fn run(&self, span: &Span, size: i32) -> (Run, Option<String>) {
+		let font = if span.style.code {
+			self.code_font.as_ref()
+		} else if span.style.strong {
+			self.bold_font.as_ref()
+		} else if span.style.emphasis {
+			self.italic_font.as_ref()
+		} else {
+			None
+		};
+		let color = if span.link.is_some() {
+			self.link_color
+		} else if span.style.code {
+			self.code_color
+		} else {
+			self.color
+		};
+
+		let run = Run {
+			background: span.style.code.then_some(self.code_background),
+			..Run::new(&span.text, size, font.unwrap_or(&self.font).clone(), color)
+		};
+		(run, span.link.clone())
+	}
+	fn inline(&self, spans: &[Span], size: i32) -> Inline {
+		Inline::new(spans.iter().map(|span| self.run(span, size)).collect())
+	}
+}
+
+#[derive(Clone, Debug)]
+/// renders commonmark: headings, paragraphs, emphasis, lists, quotes, code and links. \
+/// clicking a link returns a [LinkClicked]
+pub struct Markdown {
+	div: Div<Vec<Comp<'static>>>,
+}
+impl Markdown {
+	pub fn new(src: &str) -> Self {
+		Self::with_style(src, &MarkdownStyle::default())
+	}
+	pub fn with_style(src: &str, style: &MarkdownStyle) -> Self {
+		Self::from_blocks(&parse::parse(src), style)
+	}
+	pub fn from_blocks(blocks: &[Block], style: &MarkdownStyle) -> Self {
+		Self {
+			div: build_blocks(blocks, style),
+		}
+	}
+}
+
+fn build_blocks(blocks: &[Block], style: &MarkdownStyle) -> Div<Vec<Comp<'static>>> {
+	let mut div = Div::empty_with_capacity(blocks.len());
+	for block in blocks {
+		let spaced = comp::Margin::new(
+			MarginValues {
+				b: style.spacing,
+				..Default::default()
+			},
+			build_block(block, style),
+		);
+		div.push(Comp::Dynamic(crate::custom(spaced)));
+	}
+	div
+}
+fn build_block(block: &Block, style: &MarkdownStyle) -> Comp<'static> {
+	match block {
+		Block::Heading(level, spans) => {
+			let size = style.heading_sizes[(*level as usize).clamp(1, 6) - 1];
+			let spans = spans
+				.iter()
+				.map(|span| Span {
+					style: SpanStyle {
+						strong: true,
+						..span.style
+					},
+					..span.clone()
+				})
+				.collect::<Vec<_>>();
+			Comp::Dynamic(crate::custom(style.inline(&spans, size)))
+		}
+		Block::Paragraph(spans) => Comp::Dynamic(crate::custom(style.inline(spans, style.size))),
+		Block::List { start, items } => {
+			let mut div = Div::empty_with_capacity(items.len());
+			for (i, item) in items.iter().enumerate() {
+				let marker = match start {
+					Some(start) => format!("{}. ", start + i as u64),
+					None => "- ".to_owned(),
+				};
+
+				// the marker goes in front of the item's first paragraph
+				let mut item = item.clone();
+				match item.first_mut() {
+					Some(Block::Paragraph(spans)) => spans.insert(0, Span::plain(marker)),
+					_ => item.insert(0, Block::Paragraph(vec![Span::plain(marker)])),
+				}
+
+				let item = build_blocks(
+					&item,
+					&MarkdownStyle {
+						spacing: 0,
+						..style.clone()
+					},
+				);
+				div.push(Comp::Dynamic(crate::custom(item)));
+			}
+			Comp::Dynamic(crate::custom(indented(div, style.indent)))
+		}
+		Block::CodeBlock(code) => {
+			let font = (style.code_font.clone()).unwrap_or_else(|| style.font.clone());
+			let run = Run::new(code, style.size, font, style.code_color);
+			let code = Inline::new(vec![(run, None)]).margin(6);
+			let block = comp::Overlay::new(comp::Color::new(style.code_background), code);
+			Comp::Dynamic(crate::custom(block))
+		}
+		Block::Quote(blocks) => {
+			let quote = build_blocks(
+				blocks,
+				&MarkdownStyle {
+					spacing: 0,
+					..style.clone()
+				},
+			);
+			Comp::Dynamic(crate::custom(indented(quote, style.indent)))
+		}
+		Block::Rule => {
+			let rule = comp::Color::new(style.rule_color).fix_h(1).margin_v(4);
+			Comp::Dynamic(crate::custom(rule))
+		}
+	}
+}
+fn indented<L: Layable>(layable: L, indent: i32) -> comp::Margin<L> {
+	comp::Margin::new(
+		MarginValues {
+			l: indent,
+			..Default::default()
+		},
+		layable,
+	)
+}
+
+impl Layable for Markdown {
+	fn size(&self) -> (i32, i32) {
+		self.div.size()
+	}
+	fn render(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		self.div.render(d, det, scale)
+	}
+
+	fn tick(&mut self) {
+		self.div.tick();
+	}
+	fn pass_events(
+		&mut self,
+		events: impl Iterator<Item = Event>,
+		det: Details,
+		scale: f32,
+		ret_events: &mut Vec<ReturnEvent>,
+	) {
+		self.div.pass_events(events, det, scale, ret_events)
+	}
+}
@@ -0,0 +1,293 @@
+use pulldown_cmark::{Event, HeadingLevel, Parser, Tag, TagEnd};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SpanStyle {
+	pub strong: bool,
+	pub emphasis: bool,
+	pub code: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// a piece of inline text with the same style all the way through
+pub struct Span {
+	pub text: String,
+	pub style: SpanStyle,
+	pub link: Option<String>,
+}
+impl Span {
+	pub fn plain(text: impl Into<String>) -> Self {
+		Self {
+			text: text.into(),
+			style: SpanStyle::default(),
+			link: None,
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Block {
+	/// level is 1 to 6
+	Heading(u8, Vec<Span>),
+	Paragraph(Vec<Span>),
+	List {
+		/// the number of the first item for ordered lists
+		start: Option<u64>,
+		items: Vec<Vec<Block>>,
+	},
+	CodeBlock(String),
+	Quote(Vec<Block>),
+	Rule,
+}
+
+enum Container {
+	Root(Vec<Block>),
+	Quote(Vec<Block>),
+	List {
+		start: Option<u64>,
+		items: Vec<Vec<Block>>,
+	},
+	Item(Vec<Block>),
+}
+
+#[derive(Default)]
+struct Builder {
+	stack: Vec<Container>,
+	/// the heading or paragraph currently being built. None for paragraphs means a
+	/// paragraph that was started implicitly, like the text of tight list items
+	inline: Option<(Option<u8>, Vec<Span>)>,
+	code_block: Option<String>,
+
+	strong: usize,
+	emphasis: usize,
+	links: Vec<String>,
+}
+impl Builder {
+	fn blocks(&mut self) -> &mut Vec<Block> {
+		match self.stack.last_mut() {
+			Some(Container::Root(blocks))
+			| Some(Container::Quote(blocks))
+			| Some(Container::Item(blocks)) => blocks,
+			// blocks only end up directly in a list if the markdown is weird, give them an item
+			Some(Container::List { items, .. }) => {
+				if items.is_empty() {
+					items.push(Vec::new());
+				}
+				items.last_mut().expect("we just pushed an item")
+			}
+			None => unreachable!("the root container is never popped"),
+		}
+	}
+
+	fn flush_inline(&mut self) {
+		if let Some((heading, spans)) = self.inline.take() {
+			let block = match heading {
+				Some(level) => Block::Heading(level, spans),
+				None if spans.is_empty() => return,
+				None => Block::Paragraph(spans),
+			};
+			self.blocks().push(block);
+		}
+	}
+	fn push_span(&mut self, text: &str, code: bool) {
+		let style = SpanStyle {
+			strong: self.strong > 0,
+			emphasis: self.emphasis > 0,
+			code,
+		};
+		let link = self.links.last().cloned();
+
+		let (_, spans) = self.inline.get_or_insert_with(|| (None, Vec::new()));
+		match spans.last_mut() {
+			Some(last) if last.style == style && last.link == link => last.text.push_str(text),
+			_ => spans.push(Span {
+				text: text.to_owned(),
+				style,
+				link,
+			}),
+		}
+	}
+	fn push_container(&mut self, container: Container) {
+		self.flush_inline();
+		self.stack.push(container);
+	}
+
+	fn event(&mut self, event: Event) {
+		match event {
+			Event::Start(tag) => self.start(tag),
+			Event::End(tag) => self.end(tag),
+
+			Event::Text(text) => match &mut self.code_block {
+				Some(code) => code.push_str(&text),
+				None => self.push_span(&text, false),
+			},
+			Event::Code(code) => self.push_span(&code, true),
+			Event::SoftBreak => self.push_span(" ", false),
+			Event::HardBreak => self.push_span("\n", false),
+			Event::Rule => {
+				self.flush_inline();
+				self.blocks().push(Block::Rule);
+			}
+			Event::TaskListMarker(checked) => {
+				self.push_span(if checked { "[x] " } else { "[ ] " }, false)
+			}
+			_ => {}
+		}
+	}
+	fn start(&mut self, tag: Tag) {
+		match tag {
+			Tag::Paragraph => {
+				self.flush_inline();
+				self.inline = Some((None, Vec::new()));
+			}
+			Tag::Heading { level, .. } => {
+				self.flush_inline();
+				self.inline = Some((Some(heading_level(level)), Vec::new()));
+			}
+			Tag::BlockQuote(_) => self.push_container(Container::Quote(Vec::new())),
+			Tag::CodeBlock(_) => {
+				self.flush_inline();
+				self.code_block = Some(String::new());
+			}
+			Tag::List(start) => self.push_container(Container::List {
+				start,
+				items: Vec::new(),
+			}),
+			Tag::Item => self.push_container(Container::Item(Vec::new())),
+
+			Tag::Emphasis => self.emphasis += 1,
+			Tag::Strong => self.strong += 1,
+			Tag::Link { dest_url, .. } => self.links.push(dest_url.to_string()),
+			_ => {}
+		}
+	}
+	fn end(&mut self, tag: TagEnd) {
+		match tag {
+			TagEnd::Paragraph | TagEnd::Heading(_) => self.flush_inline(),
+			TagEnd::CodeBlock => {
+				if let Some(code) = self.code_block.take() {
+					let code = code.strip_suffix('\n').unwrap_or(&code).to_owned();
+					self.blocks().push(Block::CodeBlock(code));
+				}
+			}
+			TagEnd::BlockQuote(_) => {
+				self.flush_inline();
+				if let Some(Container::Quote(blocks)) = self.stack.pop() {
+					self.blocks().push(Block::Quote(blocks));
+				}
+			}
+			TagEnd::List(_) => {
+				self.flush_inline();
+				if let Some(Container::List { start, items }) = self.stack.pop() {
+					self.blocks().push(Block::List { start, items });
+				}
+			}
+			TagEnd::Item => {
+				self.flush_inline();
+				if let Some(Container::Item(blocks)) = self.stack.pop() {
+					if let Some(Container::List { items, .. }) = self.stack.last_mut() {
+						items.push(blocks);
+					}
+				}
+			}
+
+			TagEnd::Emphasis => self.emphasis = self.emphasis.saturating_sub(1),
+			TagEnd::Strong => self.strong = self.strong.saturating_sub(1),
+			TagEnd::Link => {
+				self.links.pop();
+			}
+			_ => {}
+		}
+	}
+
+	fn finish(mut self) -> Vec<Block> {
+		self.flush_inline();
+		match self.stack.into_iter().next() {
+			Some(Container::Root(blocks)) => blocks,
+			_ => Vec::new(),
+		}
+	}
+}
+
+fn heading_level(level: HeadingLevel) -> u8 {
+	match level {
+		HeadingLevel::H1 => 1,
+		HeadingLevel::H2 => 2,
+		HeadingLevel::H3 => 3,
+		HeadingLevel::H4 => 4,
+		HeadingLevel::H5 => 5,
+		HeadingLevel::H6 => 6,
+	}
+}
+
+/// parses commonmark into blocks. anything [Block] can't represent (html, images, tables) gets
+/// dropped, with the text inside kept where possible
+pub fn parse(src: &str) -> Vec<Block> {
+	let mut builder = Builder {
+		stack: vec![Container::Root(Vec::new())],
+		..Default::default()
+	};
+	for event in Parser::new(src) {
+		builder.event(event);
+	}
+	builder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse() {
+		let blocks = parse(
+			"# patch notes\n\nfixed *a lot* of **bugs**, see [the wiki](https://example.com)\n\n- one\n- `two`\n\n```\nlet a = 5;\n```\n",
+		);
+
+		let bold = SpanStyle {
+			strong: true,
+			..Default::default()
+		};
+		let italic = SpanStyle {
+			emphasis: true,
+			..Default::default()
+		};
+		let code = SpanStyle {
+			code: true,
+			..Default::default()
+		};
+
+		assert_eq!(
+			blocks,
+			vec![
+				Block::Heading(1, vec![Span::plain("patch notes")]),
+				Block::Paragraph(vec![
+					Span::plain("fixed "),
+					Span {
+						style: italic,
+						..Span::plain("a lot")
+					},
+					Span::plain(" of "),
+					Span {
+						style: bold,
+						..Span::plain("bugs")
+					},
+					Span::plain(", see "),
+					Span {
+						link: Some("https://example.com".into()),
+						..Span::plain("the wiki")
+					},
+				]),
+				Block::List {
+					start: None,
+					items: vec![
+						vec![Block::Paragraph(vec![Span::plain("one")])],
+						vec![Block::Paragraph(vec![Span {
+							style: code,
+							..Span::plain("two")
+						}])],
+					]
+				},
+				Block::CodeBlock("let a = 5;".into()),
+			]
+		);
+	}
+}
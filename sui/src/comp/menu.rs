@@ -0,0 +1,636 @@
+use std::{cell::Cell, rc::Rc};
+
+use raylib::{ffi::KeyboardKey, prelude::RaylibDraw};
+
+use super::{tooltip::flip, tree::draw_arrow};
+use crate::{
+	core::{Event, LayerEvent, MouseEvent, ReturnEvent, Store},
+	form::{control, FocusCommand, UniqueId},
+	Color, Details, Layable, Text,
+};
+
+const PADDING: i32 = 4;
+/// size of the checkmark and the submenu arrow, unscaled
+const MARK_SIZE: i32 = 12;
+const BACKGROUND: Color = crate::color(35, 35, 35, 255);
+const HIGHLIGHT: Color = crate::color(60, 80, 110, 255);
+const SEPARATOR: Color = crate::color(70, 70, 70, 255);
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+/// returned by a [ContextMenu] when one of its items gets picked
+pub struct MenuPicked<T> {
+	/// the context menu the item is in
+	pub uid: UniqueId,
+	pub value: T,
+}
+
+#[derive(Clone)]
+enum MenuItem<T> {
+	Action {
+		label: Text<'static>,
+		value: T,
+	},
+	Check {
+		label: Text<'static>,
+		checked: Store<bool>,
+		on_toggle: Rc<dyn Fn(bool) -> T>,
+	},
+	Separator,
+	Submenu {
+		label: Text<'static>,
+		menu: Menu<T>,
+	},
+}
+impl<T: std::fmt::Debug> std::fmt::Debug for MenuItem<T> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Action { label, value } => f
+				.debug_struct("Action")
+				.field("label", label)
+				.field("value", value)
+				.finish(),
+			Self::Check { label, checked, .. } => f
+				.debug_struct("Check")
+				.field("label", label)
+				.field("checked", checked)
+				.finish_non_exhaustive(),
+			Self::Separator => f.write_str("Separator"),
+			Self::Submenu { label, menu } => f
+				.debug_struct("Submenu")
+				.field("label", label)
+				.field("menu", menu)
+				.finish(),
+		}
+	}
+}
+impl<T> MenuItem<T> {
+	fn label(&self) -> Option<&Text<'static>> {
+		match self {
+			Self::Action { label, .. }
+			| Self::Check { label, .. }
+			| Self::Submenu { label, .. } => Some(label),
+			Self::Separator => None,
+		}
+	}
+	/// unscaled
+	fn height(&self) -> i32 {
+		match self.label() {
+			Some(label) => label.size().1.max(MARK_SIZE) + PADDING * 2,
+			None => PADDING * 2 + 1,
+		}
+	}
+}
+
+#[derive(Clone, Debug)]
+/// the items of a [ContextMenu] (or of one of its submenus). \
+/// picking an item returns a [MenuPicked] with its `T`
+pub struct Menu<T> {
+	text_size: i32,
+	items: Vec<MenuItem<T>>,
+}
+impl<T: Clone + 'static> Menu<T> {
+	pub fn new(text_size: i32) -> Self {
+		Self {
+			text_size,
+			items: Vec::new(),
+		}
+	}
+	/// returns a [MenuPicked] with `value` when picked
+	pub fn item(mut self, label: impl Into<std::borrow::Cow<'static, str>>, value: T) -> Self {
+		self.items.push(MenuItem::Action {
+			label: Text::new(label, self.text_size),
+			value,
+		});
+		self
+	}
+	/// shows a checkmark if `checked`. picking it flips `checked` and returns a [MenuPicked] with
+	/// `on_toggle(checked)`
+	pub fn checkable(
+		mut self,
+		label: impl Into<std::borrow::Cow<'static, str>>,
+		checked: Store<bool>,
+		on_toggle: impl Fn(bool) -> T + 'static,
+	) -> Self {
+		self.items.push(MenuItem::Check {
+			label: Text::new(label, self.text_size),
+			checked,
+			on_toggle: Rc::new(on_toggle),
+		});
+		self
+	}
+	pub fn separator(mut self) -> Self {
+		self.items.push(MenuItem::Separator);
+		self
+	}
+	/// opens `menu` next to this one when hovered
+	pub fn submenu(
+		mut self,
+		label: impl Into<std::borrow::Cow<'static, str>>,
+		menu: Menu<T>,
+	) -> Self {
+		self.items.push(MenuItem::Submenu {
+			label: Text::new(label, self.text_size),
+			menu,
+		});
+		self
+	}
+
+	pub fn len(&self) -> usize {
+		self.items.len()
+	}
+	pub fn is_empty(&self) -> bool {
+		self.items.is_empty()
+	}
+
+	/// unscaled
+	fn size(&self) -> (i32, i32) {
+		let label_w = (self.items.iter())
+			.filter_map(|item| item.label())
+			.map(|label| label.size().0)
+			.max()
+			.unwrap_or_default();
+		let h = self.items.iter().map(|item| item.height()).sum();
+		(label_w + MARK_SIZE * 2 + PADDING * 5, h)
+	}
+	/// where item `index` starts, unscaled
+	fn item_y(&self, index: usize) -> i32 {
+		self.items[..index].iter().map(|item| item.height()).sum()
+	}
+	/// the item `y` (unscaled, from the top of the menu) is on, if it's not a separator
+	fn item_at(&self, y: i32) -> Option<usize> {
+		let mut top = 0;
+		for (i, item) in self.items.iter().enumerate() {
+			top += item.height();
+			if y < top {
+				return (y >= 0 && !matches!(item, MenuItem::Separator)).then_some(i);
+			}
+		}
+		None
+	}
+	fn selectable(&self) -> Vec<bool> {
+		(self.items.iter())
+			.map(|item| !matches!(item, MenuItem::Separator))
+			.collect()
+	}
+}
+
+/// the next item that isn't a separator after (or before, if not `down`) `from`, wrapping around. \
+/// starts from the first (or last) one if `from` is none
+pub fn next_selectable(selectable: &[bool], from: Option<usize>, down: bool) -> Option<usize> {
+	let len = selectable.len();
+	(1..=len)
+		.map(|step| match (from, down) {
+			(Some(from), true) => (from + step) % len,
+			(Some(from), false) => (from + len * 2 - step) % len,
+			(None, true) => step - 1,
+			(None, false) => len - step,
+		})
+		.find(|i| selectable[*i])
+}
+
+/// draws a menu in a layer. `det` is in window coords and scaled
+fn render_menu<T>(
+	d: &mut crate::Handle,
+	menu: &Menu<T>,
+	det: Details,
+	highlighted: Option<usize>,
+	scale: f32,
+) {
+	let s = |v: i32| (v as f32 * scale) as i32;
+	d.draw_rectangle(det.x, det.y, det.aw, det.ah, BACKGROUND);
+
+	let mut y = det.y;
+	for (i, item) in menu.items.iter().enumerate() {
+		let h = s(item.height());
+		let row = Details { y, ah: h, ..det };
+		y += h;
+
+		let Some(label) = item.label() else {
+			let line_y = row.y + row.ah / 2;
+			d.draw_line(
+				row.x + s(PADDING),
+				line_y,
+				row.x + row.aw - s(PADDING),
+				line_y,
+				SEPARATOR,
+			);
+			continue;
+		};
+		if Some(i) == highlighted {
+			d.draw_rectangle(row.x, row.y, row.aw, row.ah, HIGHLIGHT);
+		}
+
+		let mark = Details {
+			x: row.x + s(PADDING),
+			y: row.y + (row.ah - s(MARK_SIZE)) / 2,
+			aw: s(MARK_SIZE),
+			ah: s(MARK_SIZE),
+		};
+		match item {
+			MenuItem::Check { checked, .. } if checked.get() => {
+				let inset = s(3);
+				d.draw_rectangle(
+					mark.x + inset,
+					mark.y + inset,
+					mark.aw - inset * 2,
+					mark.ah - inset * 2,
+					control::ACCENT,
+				);
+			}
+			MenuItem::Submenu { .. } => {
+				let arrow = Details {
+					x: row.x + row.aw - s(PADDING + MARK_SIZE),
+					..mark
+				};
+				draw_arrow(d, arrow, false);
+			}
+			_ => {}
+		}
+
+		let label_x = mark.x + mark.aw + s(PADDING);
+		let label_det = Details {
+			x: label_x,
+			y: row.y + s(PADDING),
+			aw: row.aw - (label_x - row.x) - s(MARK_SIZE + PADDING * 2),
+			ah: row.ah - s(PADDING) * 2,
+		};
+		label.render(d, label_det, scale);
+	}
+	d.draw_rectangle_lines(det.x, det.y, det.aw, det.ah, control::INACTIVE);
+}
+
+#[derive(Copy, Clone, Debug)]
+/// one open popup: the menu itself or a submenu
+struct Level {
+	/// window coords, scaled
+	det: Details,
+	highlighted: Option<usize>,
+}
+
+#[derive(Clone, Debug)]
+/// opens a [Menu] where `layable` gets right-clicked. \
+/// the menu and its submenus are each their own [layer](crate::layer), stacked on top of each other, and
+/// stay open until an item gets picked, something outside of them gets clicked or escape is pressed. \
+/// while the menu's open it has focus: up/down move between items, right opens a submenu, left closes
+/// one and enter or space pick the item. closing it gives focus back to whatever had it before. \
+/// it doesn't go on the [dialog](crate::dialog) stack: that would need the [Handler](crate::dialog::Handler)
+/// to be reachable from anywhere a context menu is, and picked items would come back out of the
+/// dialog instead of out of the menu's own `pass_events`. layers are what popups that belong to a
+/// component use, like [Select](crate::form::Select) does
+pub struct ContextMenu<L: Layable, T: Clone + 'static> {
+	uid: UniqueId,
+	layable: L,
+	menu: Menu<T>,
+	/// the menu and every submenu open on top of it, in order
+	open: Vec<Level>,
+	/// what had focus the last time it was rendered
+	focus: Cell<UniqueId>,
+	/// what had focus before the menu opened, gets it back when the menu closes
+	previous_focus: UniqueId,
+}
+impl<L: Layable, T: Clone + 'static> ContextMenu<L, T> {
+	pub fn new(layable: L, menu: Menu<T>) -> Self {
+		Self {
+			uid: UniqueId::new(),
+			layable,
+			menu,
+			open: Vec::new(),
+			focus: Cell::new(UniqueId::null()),
+			previous_focus: UniqueId::null(),
+		}
+	}
+	pub fn take(self) -> L {
+		self.layable
+	}
+	/// the uid [MenuPicked] events are returned with
+	pub fn uid(&self) -> UniqueId {
+		self.uid
+	}
+	pub fn is_open(&self) -> bool {
+		!self.open.is_empty()
+	}
+
+	/// the menu shown at `level`
+	fn menu_at(&self, level: usize) -> &Menu<T> {
+		let mut menu = &self.menu;
+		for open in &self.open[..level] {
+			let Some(MenuItem::Submenu { menu: submenu, .. }) =
+				open.highlighted.and_then(|i| menu.items.get(i))
+			else {
+				unreachable!("only submenus get a level")
+			};
+			menu = submenu;
+		}
+		menu
+	}
+	fn scaled_size(menu: &Menu<T>, scale: f32) -> (i32, i32) {
+		let (w, h) = menu.size();
+		((w as f32 * scale) as i32, (h as f32 * scale) as i32)
+	}
+
+	pub fn open_at(&mut self, (x, y): (i32, i32), scale: f32, ret_events: &mut Vec<ReturnEvent>) {
+		let (w, h) = Self::scaled_size(&self.menu, scale);
+		let (screen_w, screen_h) = screen_size();
+		let det = Details {
+			x: flip(x, x, w, screen_w),
+			y: flip(y, y, h, screen_h),
+			aw: w,
+			ah: h,
+		};
+		self.open = vec![Level {
+			det,
+			highlighted: None,
+		}];
+		// reopening it somewhere else shouldn't forget what was focused before
+		let focus = self.focus.get();
+		if focus != self.uid {
+			self.previous_focus = focus;
+		}
+		ret_events.push(Event::ret(FocusCommand::Request(self.uid)));
+	}
+	/// closes the menu and gives focus back to what had it before the menu opened
+	pub fn close(&mut self, ret_events: &mut Vec<ReturnEvent>) {
+		if self.open.is_empty() {
+			return;
+		}
+		self.open.clear();
+		let command = match self.previous_focus {
+			previous if previous == UniqueId::null() => FocusCommand::Drop,
+			previous => FocusCommand::Request(previous),
+		};
+		ret_events.push(Event::ret(command));
+	}
+
+	/// highlights `index` in `level`, closing the submenus above it and opening the one at `index`
+	fn highlight(&mut self, level: usize, index: Option<usize>, scale: f32) {
+		self.open.truncate(level + 1);
+		self.open[level].highlighted = index;
+
+		let parent = self.open[level];
+		let menu = self.menu_at(level);
+		let Some(i) = index else { return };
+		let MenuItem::Submenu { menu: submenu, .. } = &menu.items[i] else {
+			return;
+		};
+		let (w, h) = Self::scaled_size(submenu, scale);
+		let (screen_w, screen_h) = screen_size();
+		let row_y = parent.det.y + (menu.item_y(i) as f32 * scale) as i32;
+		let row_h = (menu.items[i].height() as f32 * scale) as i32;
+		let det = Details {
+			x: flip(parent.det.x + parent.det.aw, parent.det.x, w, screen_w),
+			y: flip(row_y, row_y + row_h, h, screen_h),
+			aw: w,
+			ah: h,
+		};
+		self.open.push(Level {
+			det,
+			highlighted: None,
+		});
+	}
+	/// picks item `index` in `level`
+	fn activate(
+		&mut self,
+		level: usize,
+		index: usize,
+		scale: f32,
+		ret_events: &mut Vec<ReturnEvent>,
+	) {
+		match &self.menu_at(level).items[index] {
+			MenuItem::Action { value, .. } => {
+				ret_events.push(Event::ret(MenuPicked {
+					uid: self.uid,
+					value: value.clone(),
+				}));
+				self.close(ret_events);
+			}
+			MenuItem::Check {
+				checked, on_toggle, ..
+			} => {
+				let now = !checked.get();
+				checked.set(now);
+				ret_events.push(Event::ret(MenuPicked {
+					uid: self.uid,
+					value: on_toggle(now),
+				}));
+				self.close(ret_events);
+			}
+			MenuItem::Submenu { .. } => {
+				self.highlight(level, Some(index), scale);
+				let first = next_selectable(&self.menu_at(level + 1).selectable(), None, true);
+				self.open[level + 1].highlighted = first;
+			}
+			MenuItem::Separator => {}
+		}
+	}
+	fn handle_key(&mut self, key: KeyboardKey, scale: f32, ret_events: &mut Vec<ReturnEvent>) {
+		let top = self.open.len() - 1;
+		let highlighted = self.open[top].highlighted;
+		match key {
+			KeyboardKey::KEY_DOWN | KeyboardKey::KEY_UP => {
+				let down = key == KeyboardKey::KEY_DOWN;
+				let next = next_selectable(&self.menu_at(top).selectable(), highlighted, down);
+				self.open[top].highlighted = next;
+			}
+			KeyboardKey::KEY_RIGHT => {
+				if let Some(i) = highlighted {
+					if matches!(self.menu_at(top).items[i], MenuItem::Submenu { .. }) {
+						self.activate(top, i, scale, ret_events);
+					}
+				}
+			}
+			KeyboardKey::KEY_LEFT if top > 0 => {
+				self.open.pop();
+			}
+			KeyboardKey::KEY_ESCAPE => self.close(ret_events),
+			KeyboardKey::KEY_ENTER | KeyboardKey::KEY_KP_ENTER | KeyboardKey::KEY_SPACE => {
+				if let Some(i) = highlighted {
+					self.activate(top, i, scale, ret_events);
+				}
+			}
+			_ => {}
+		}
+	}
+}
+
+fn screen_size() -> (i32, i32) {
+	unsafe {
+		(
+			raylib::ffi::GetScreenWidth(),
+			raylib::ffi::GetScreenHeight(),
+		)
+	}
+}
+
+impl<L: Layable, T: Clone + 'static> Layable for ContextMenu<L, T> {
+	fn size(&self) -> (i32, i32) {
+		self.layable.size()
+	}
+	fn render(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		self.focus.set(d.focus());
+		self.layable.render(d, det, scale);
+
+		// the layers get rendered later, so they need their own copies
+		for (i, level) in self.open.iter().enumerate() {
+			let menu = self.menu_at(i).clone();
+			let Level {
+				det: menu_det,
+				highlighted,
+			} = *level;
+			d.layer(self.uid, menu_det, move |d| {
+				render_menu(d, &menu, menu_det, highlighted, scale)
+			});
+		}
+	}
+
+	fn tick(&mut self) {
+		self.layable.tick();
+	}
+	fn pass_events(
+		&mut self,
+		events: impl Iterator<Item = Event>,
+		det: Details,
+		scale: f32,
+		ret_events: &mut Vec<ReturnEvent>,
+	) {
+		let events = events.collect::<Vec<_>>();
+		for event in events.iter().copied() {
+			match event {
+				Event::MouseEvent(MouseEvent::RightClick { x, y }) if det.is_inside(x, y) => {
+					self.open_at((x, y), scale, ret_events);
+				}
+				Event::LayerEvent(uid, LayerEvent::ClickedOutside { .. }) if uid == self.uid => {
+					// whatever got clicked might have taken focus already
+					if ret_events
+						.iter()
+						.any(|event| event.can_take::<FocusCommand>())
+					{
+						self.open.clear();
+					} else {
+						self.close(ret_events);
+					}
+				}
+				Event::LayerEvent(uid, LayerEvent::Mouse(m_event)) if uid == self.uid => {
+					let (x, y) = m_event.at();
+					let Some(level) = self.open.iter().rposition(|l| l.det.is_inside(x, y)) else {
+						continue;
+					};
+					let menu_y = ((y - self.open[level].det.y) as f32 / scale) as i32;
+					let index = self.menu_at(level).item_at(menu_y);
+					match m_event {
+						MouseEvent::MouseMove { .. } if index != self.open[level].highlighted => {
+							// moving off an item onto the submenu it opened shouldn't close it
+							if index.is_some() || level + 1 == self.open.len() {
+								self.highlight(level, index, scale);
+							}
+						}
+						MouseEvent::MouseClick { .. } => {
+							if let Some(index) = index {
+								self.activate(level, index, scale, ret_events);
+							}
+						}
+						_ => {}
+					}
+				}
+				event if self.is_open() => {
+					if let Some(key) = control::pressed_key(&event, self.uid) {
+						self.handle_key(key, scale, ret_events);
+					}
+				}
+				_ => {}
+			}
+		}
+
+		self.layable
+			.pass_events(events.into_iter(), det, scale, ret_events);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_next_selectable() {
+		// a separator in the middle
+		let items = [true, false, true];
+		assert_eq!(next_selectable(&items, None, true), Some(0));
+		assert_eq!(next_selectable(&items, None, false), Some(2));
+		assert_eq!(next_selectable(&items, Some(0), true), Some(2));
+		assert_eq!(next_selectable(&items, Some(2), true), Some(0));
+		assert_eq!(next_selectable(&items, Some(0), false), Some(2));
+		assert_eq!(next_selectable(&[false, false], None, true), None);
+		assert_eq!(next_selectable(&[], Some(0), true), None);
+	}
+
+	#[test]
+	fn test_picked() {
+		let checked = Store::new(false);
+		let menu = Menu::new(16).item("first", 1).separator().checkable(
+			"second",
+			checked.clone(),
+			|checked| if checked { 2 } else { 3 },
+		);
+		let mut context_menu = ContextMenu::new(crate::comp::Space::new(10, 10), menu);
+		let open = |context_menu: &mut ContextMenu<crate::comp::Space, i32>| {
+			context_menu.open = vec![Level {
+				det: Details::new(0, 0, 100, 100),
+				highlighted: None,
+			}];
+		};
+		let picked = |ret_events: Vec<ReturnEvent>| {
+			(ret_events.into_iter())
+				.find_map(|event| event.take::<MenuPicked<i32>>())
+				.unwrap()
+		};
+
+		open(&mut context_menu);
+		let mut ret_events = Vec::new();
+		context_menu.handle_key(KeyboardKey::KEY_DOWN, 1.0, &mut ret_events);
+		context_menu.handle_key(KeyboardKey::KEY_ENTER, 1.0, &mut ret_events);
+		let uid = context_menu.uid();
+		assert_eq!(picked(ret_events), MenuPicked { uid, value: 1 });
+		assert!(!context_menu.is_open());
+
+		// the separator gets skipped
+		open(&mut context_menu);
+		let mut ret_events = Vec::new();
+		context_menu.handle_key(KeyboardKey::KEY_UP, 1.0, &mut ret_events);
+		context_menu.handle_key(KeyboardKey::KEY_SPACE, 1.0, &mut ret_events);
+		assert_eq!(picked(ret_events), MenuPicked { uid, value: 2 });
+		assert!(checked.get());
+	}
+
+	#[test]
+	fn test_focus() {
+		let menu = Menu::new(16).item("first", 1);
+		let mut context_menu = ContextMenu::new(crate::comp::Space::new(10, 10), menu);
+		let uid = context_menu.uid();
+		let focus_commands = |ret_events: Vec<ReturnEvent>| {
+			(ret_events.into_iter())
+				.filter_map(|event| event.take::<FocusCommand>())
+				.collect::<Vec<_>>()
+		};
+		let open_and_close = |context_menu: &mut ContextMenu<crate::comp::Space, i32>| {
+			let mut ret_events = Vec::new();
+			context_menu.open_at((0, 0), 1.0, &mut ret_events);
+			// the menu got rendered with focus
+			context_menu.focus.set(uid);
+			context_menu.handle_key(KeyboardKey::KEY_ESCAPE, 1.0, &mut ret_events);
+			focus_commands(ret_events)
+		};
+
+		let previous = UniqueId::new();
+		context_menu.focus.set(previous);
+		assert_eq!(
+			open_and_close(&mut context_menu),
+			[FocusCommand::Request(uid), FocusCommand::Request(previous)]
+		);
+
+		// nothing had focus
+		context_menu.focus.set(UniqueId::null());
+		assert_eq!(
+			open_and_close(&mut context_menu),
+			[FocusCommand::Request(uid), FocusCommand::Drop]
+		);
+	}
+}
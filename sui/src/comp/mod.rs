@@ -1,6 +1,9 @@
 pub mod clickable;
 pub use clickable::{Clickable, OptionalClickable};
 
+pub mod button;
+pub use button::{Button, ButtonState, ButtonStyle};
+
 pub mod div;
 pub use div::Div;
 
@@ -11,7 +14,7 @@ pub mod primitives;
 pub use primitives::*;
 
 pub mod text;
-pub use text::{Text, WrappedText};
+pub use text::{LocalizedText, Text, WrappedText};
 
 pub mod align;
 pub use align::*;
@@ -19,12 +22,38 @@ pub use align::*;
 pub mod overlay;
 pub use overlay::Overlay;
 
+pub mod menu;
+pub use menu::{ContextMenu, Menu, MenuPicked};
+
+pub mod tooltip;
+pub use tooltip::{Tooltip, TooltipPlacement};
+
+pub mod table;
+pub use table::{SelectionChanged, SortOrder, Table};
+
+pub mod collapsible;
+pub use collapsible::{Accordion, Collapsible};
+
+pub mod split;
+pub use split::Split;
+
+pub mod tabs;
+pub use tabs::Tabs;
+
+pub mod tree;
+pub use tree::{NodeExpanded, NodeSelected, Tree, TreeNode, TreeState};
+
 pub mod debug;
 pub use debug::Debug;
 
 pub mod cast_events;
 pub use cast_events::CastEvents;
 
+#[cfg(feature = "markdown")]
+pub mod markdown;
+#[cfg(feature = "markdown")]
+pub use markdown::{LinkClicked, Markdown};
+
 use crate::{
 	core::{Event, ReturnEvent},
 	Layable,
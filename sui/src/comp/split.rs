@@ -0,0 +1,274 @@
+use raylib::prelude::RaylibDraw;
+
+use crate::{
+	core::{Event, MouseEvent, ReturnEvent, Store},
+	Color, Details, Layable,
+};
+
+/// how thick the divider is, unscaled
+const DIVIDER: i32 = 6;
+const DIVIDER_COLOR: Color = crate::color(45, 45, 45, 255);
+const DIVIDER_ACTIVE_COLOR: Color = crate::color(80, 80, 80, 255);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+enum SplitAction {
+	#[default]
+	None,
+	/// `grab` is how far into the divider it was grabbed
+	Dragging { grab: i32 },
+}
+
+#[derive(Clone, Debug)]
+/// two layables next to each other (or on top of each other if not horizontal, like [Div](crate::Div)),
+/// filling the space they're given, with a divider between them that can be dragged around. \
+/// where the divider is lives in a `Store<f32>`, as the part of the space the first side gets, so it
+/// stays put when the split gets rebuilt. \
+/// once the divider's grabbed, it follows the mouse until it's released, even if the mouse gets ahead
+/// of it, and the sides don't get any mouse events in the meantime
+pub struct Split<A: Layable, B: Layable> {
+	horizontal: bool,
+	ratio: Store<f32>,
+	first: A,
+	second: B,
+	/// (min, max) size of the first side, unscaled
+	first_limits: (i32, i32),
+	/// (min, max) size of the second side, unscaled
+	second_limits: (i32, i32),
+	action: SplitAction,
+	hovered: bool,
+}
+impl<A: Layable, B: Layable> Split<A, B> {
+	pub fn new(horizontal: bool, ratio: Store<f32>, first: A, second: B) -> Self {
+		Self {
+			horizontal,
+			ratio,
+			first,
+			second,
+			first_limits: (0, i32::MAX),
+			second_limits: (0, i32::MAX),
+			action: SplitAction::None,
+			hovered: false,
+		}
+	}
+	/// `first` on the left, `second` on the right
+	pub fn horizontal(ratio: Store<f32>, first: A, second: B) -> Self {
+		Self::new(true, ratio, first, second)
+	}
+	/// `first` on top, `second` under it
+	pub fn vertical(ratio: Store<f32>, first: A, second: B) -> Self {
+		Self::new(false, ratio, first, second)
+	}
+	/// keeps the first side between `min` and `max` wide (or tall), unscaled
+	pub fn first_limits(self, min: i32, max: i32) -> Self {
+		Self {
+			first_limits: (min, max),
+			..self
+		}
+	}
+	/// keeps the second side between `min` and `max` wide (or tall), unscaled
+	pub fn second_limits(self, min: i32, max: i32) -> Self {
+		Self {
+			second_limits: (min, max),
+			..self
+		}
+	}
+
+	pub fn ratio(&self) -> &Store<f32> {
+		&self.ratio
+	}
+	pub fn first(&self) -> &A {
+		&self.first
+	}
+	pub fn second(&self) -> &B {
+		&self.second
+	}
+
+	/// the coordinate along the split
+	fn along(&self, (x, y): (i32, i32)) -> i32 {
+		if self.horizontal {
+			x
+		} else {
+			y
+		}
+	}
+	/// how much space the sides share and where that starts, scaled
+	fn span(&self, det: Details, scale: f32) -> (i32, i32) {
+		let divider = (DIVIDER as f32 * scale) as i32;
+		match self.horizontal {
+			true => (det.x, (det.aw - divider).max(0)),
+			false => (det.y, (det.ah - divider).max(0)),
+		}
+	}
+	/// the first side's size, scaled
+	fn first_size(&self, det: Details, scale: f32) -> i32 {
+		let (_, avail) = self.span(det, scale);
+		let first = (avail as f32 * self.ratio.with_borrow(|ratio| *ratio)) as i32;
+		// `as` saturates, so no max stays no max
+		let s = |(min, max): (i32, i32)| ((min as f32 * scale) as i32, (max as f32 * scale) as i32);
+		clamp_first(first, avail, s(self.first_limits), s(self.second_limits))
+	}
+	/// the first side, the divider and the second side
+	fn split(&self, det: Details, scale: f32) -> (Details, Details, Details) {
+		let divider = (DIVIDER as f32 * scale) as i32;
+		let first = self.first_size(det, scale);
+		if self.horizontal {
+			(
+				Details { aw: first, ..det },
+				Details {
+					x: det.x + first,
+					aw: divider,
+					..det
+				},
+				Details {
+					x: det.x + first + divider,
+					aw: (det.aw - first - divider).max(0),
+					..det
+				},
+			)
+		} else {
+			(
+				Details { ah: first, ..det },
+				Details {
+					y: det.y + first,
+					ah: divider,
+					..det
+				},
+				Details {
+					y: det.y + first + divider,
+					ah: (det.ah - first - divider).max(0),
+					..det
+				},
+			)
+		}
+	}
+	/// moves the divider so it starts at `at`, scaled
+	fn drag_to(&mut self, at: i32, det: Details, scale: f32) {
+		let (start, avail) = self.span(det, scale);
+		if avail <= 0 {
+			return;
+		}
+		let ratio = ((at - start) as f32 / avail as f32).clamp(0.0, 1.0);
+		self.ratio.set(ratio);
+		// so the store doesn't end up past the limits
+		let first = self.first_size(det, scale);
+		self.ratio.set(first as f32 / avail as f32);
+	}
+}
+
+/// the first side's size, so that both sides stay within their (min, max). \
+/// if they can't both fit, the first side's minimum wins
+pub fn clamp_first(
+	first: i32,
+	avail: i32,
+	first_limits: (i32, i32),
+	second_limits: (i32, i32),
+) -> i32 {
+	let (first_min, first_max) = first_limits;
+	let (second_min, second_max) = second_limits;
+	let lo = first_min.max(avail.saturating_sub(second_max));
+	let hi = first_max.min(avail - second_min);
+	first.min(hi).max(lo).clamp(0, avail.max(0))
+}
+
+impl<A: Layable, B: Layable> Layable for Split<A, B> {
+	fn size(&self) -> (i32, i32) {
+		let (a_w, a_h) = self.first.size();
+		let (b_w, b_h) = self.second.size();
+		match self.horizontal {
+			true => (a_w + DIVIDER + b_w, a_h.max(b_h)),
+			false => (a_w.max(b_w), a_h + DIVIDER + b_h),
+		}
+	}
+	fn render(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		let (first, divider, second) = self.split(det, scale);
+		self.first.render(d, first, scale);
+		self.second.render(d, second, scale);
+
+		let color = match self.hovered || self.action != SplitAction::None {
+			true => DIVIDER_ACTIVE_COLOR,
+			false => DIVIDER_COLOR,
+		};
+		d.draw_rectangle(divider.x, divider.y, divider.aw, divider.ah, color);
+	}
+
+	fn tick(&mut self) {
+		self.first.tick();
+		self.second.tick();
+	}
+	fn pass_events(
+		&mut self,
+		events: impl Iterator<Item = Event>,
+		det: Details,
+		scale: f32,
+		ret_events: &mut Vec<ReturnEvent>,
+	) {
+		let events = events.collect::<Vec<_>>();
+		// MouseMove comes every frame the mouse is over us, so if there's one without a MouseHeld the
+		// button got released somewhere we didn't hear about
+		let moved = (events.iter())
+			.any(|event| matches!(event, Event::MouseEvent(MouseEvent::MouseMove { .. })));
+		let held = (events.iter())
+			.any(|event| matches!(event, Event::MouseEvent(MouseEvent::MouseHeld { .. })));
+		if moved && !held {
+			self.action = SplitAction::None;
+		}
+
+		for event in events.iter().copied() {
+			let (_, divider, _) = self.split(det, scale);
+			let divider_start = self.along((divider.x, divider.y));
+			match event {
+				Event::MouseEvent(MouseEvent::MouseMove { x, y }) => {
+					self.hovered = divider.is_inside(x, y);
+				}
+				Event::MouseEvent(MouseEvent::MouseClick { x, y }) if divider.is_inside(x, y) => {
+					self.action = SplitAction::Dragging {
+						grab: self.along((x, y)) - divider_start,
+					};
+				}
+				Event::MouseEvent(MouseEvent::MouseHeld { x, y }) => {
+					if let SplitAction::Dragging { grab } = self.action {
+						self.drag_to(self.along((x, y)) - grab, det, scale);
+					}
+				}
+				Event::MouseEvent(MouseEvent::MouseRelease { .. }) => {
+					self.action = SplitAction::None;
+				}
+				_ => {}
+			}
+		}
+
+		let (first, _, second) = self.split(det, scale);
+		let dragging = self.action != SplitAction::None;
+		let filter = |side: Details| {
+			move |event: &Event| match event {
+				Event::MouseEvent(m_event) => !dragging && side.is_inside_tuple(m_event.at()),
+				_ => true,
+			}
+		};
+		let first_events = events.iter().copied().filter(filter(first));
+		self.first
+			.pass_events(first_events, first, scale, ret_events);
+		let second_events = events.iter().copied().filter(filter(second));
+		self.second
+			.pass_events(second_events, second, scale, ret_events);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_clamp_first() {
+		let none = (0, i32::MAX);
+		assert_eq!(clamp_first(50, 100, none, none), 50);
+		assert_eq!(clamp_first(150, 100, none, none), 100);
+		assert_eq!(clamp_first(10, 100, (20, 80), none), 20);
+		assert_eq!(clamp_first(90, 100, (20, 80), none), 80);
+		assert_eq!(clamp_first(90, 100, none, (30, 60)), 70);
+		assert_eq!(clamp_first(10, 100, none, (30, 60)), 40);
+		// both minimums can't fit, the first side gets its minimum
+		assert_eq!(clamp_first(50, 100, (70, 90), (50, 90)), 70);
+		assert_eq!(clamp_first(50, 0, (20, 80), none), 0);
+	}
+}
@@ -0,0 +1,600 @@
+use std::{cmp::Ordering, collections::BTreeSet, fmt::Display, rc::Rc};
+
+use raylib::{ffi::KeyboardKey, math::Vector2, prelude::RaylibDraw};
+
+use super::{fit::scrollable::ScrollableState, VirtualList};
+use crate::{
+	core::{Event, KeyboardEvent, MouseEvent, ReturnEvent, Store},
+	form::{control, FocusCommand, UniqueId},
+	Color, Details, Layable, Text,
+};
+
+const PADDING: i32 = 4;
+/// how close to a column's right edge the mouse has to be to resize it instead of sorting, unscaled
+const RESIZE_GRAB: i32 = 4;
+const MIN_COLUMN_WIDTH: i32 = 24;
+const HEADER_BACKGROUND: Color = crate::color(45, 45, 45, 255);
+const ROW_BACKGROUND: Color = crate::color(30, 30, 30, 255);
+const ROW_BACKGROUND_ALT: Color = crate::color(35, 35, 35, 255);
+const SELECTED_BACKGROUND: Color = crate::color(60, 80, 110, 255);
+const SEPARATOR: Color = crate::color(70, 70, 70, 255);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SortOrder {
+	Ascending,
+	Descending,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// returned by a [Table] every time its selection changes
+pub struct SelectionChanged {
+	pub uid: UniqueId,
+	/// indices into the table's rows, ascending
+	pub selected: Vec<usize>,
+}
+
+#[derive(Clone)]
+struct Column<T> {
+	title: Text<'static>,
+	/// unscaled
+	width: i32,
+	display: Rc<dyn Fn(&T) -> String>,
+	/// unsortable if none
+	cmp: Option<Rc<dyn Fn(&T, &T) -> Ordering>>,
+}
+impl<T> std::fmt::Debug for Column<T> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("Column")
+			.field("title", &self.title.text)
+			.field("width", &self.width)
+			.field("sortable", &self.cmp.is_some())
+			.finish()
+	}
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum TableAction {
+	None,
+	Resizing {
+		column: usize,
+		before: i32,
+		drag_start_x: i32,
+	},
+}
+
+/// what rows return when they're clicked, so the table can pick it up. the position in the sorted order
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct RowClicked(usize);
+
+#[derive(Clone, Debug)]
+/// a table of the rows in a `Store<Vec<T>>`, with a header that stays put while the rows scroll under it. \
+/// only the rows on screen get rendered (see [VirtualList]), so it's fine with thousands of them. \
+/// clicking a column's title sorts by it (ascending, descending, then back to unsorted), dragging the edge
+/// between two titles resizes the column on the left. \
+/// clicking a row selects it. if [Table::multi_select] was called, ctrl-clicking toggles rows and
+/// shift-clicking selects every row from the last click. up/down move the selection while the table is focused.
+/// every change returns a [SelectionChanged]. \
+/// rows are only re-sorted when the sort or the number of rows changes, call [Table::refresh] after
+/// editing them in place
+pub struct Table<T: 'static> {
+	uid: UniqueId,
+	rows: Store<Vec<T>>,
+	columns: Vec<Column<T>>,
+	text_size: i32,
+	multi: bool,
+	sort: Option<(usize, SortOrder)>,
+
+	/// indices into rows, in the order they're shown
+	order: Vec<usize>,
+	/// indices into rows
+	selected: BTreeSet<usize>,
+	/// the row shift-selections start from, index into rows
+	anchor: Option<usize>,
+	/// the row the arrow keys move from, index into rows
+	cursor: Option<usize>,
+	scroll: ScrollableState,
+	action: TableAction,
+}
+impl<T: 'static> Table<T> {
+	pub fn new(rows: Store<Vec<T>>, text_size: i32) -> Self {
+		let mut table = Self {
+			uid: UniqueId::new(),
+			rows,
+			columns: Vec::new(),
+			text_size,
+			multi: false,
+			sort: None,
+			order: Vec::new(),
+			selected: BTreeSet::new(),
+			anchor: None,
+			cursor: None,
+			scroll: ScrollableState::default(),
+			action: TableAction::None,
+		};
+		table.refresh();
+		table
+	}
+	/// adds a column showing `key` of every row, sorted by it too
+	pub fn column<K: PartialOrd + Display>(
+		mut self,
+		title: impl Into<std::borrow::Cow<'static, str>>,
+		width: i32,
+		key: impl Fn(&T) -> K + 'static,
+	) -> Self {
+		let key = Rc::new(key);
+		let cmp_key = key.clone();
+		self.columns.push(Column {
+			title: Text::new(title, self.text_size),
+			width,
+			display: Rc::new(move |row| key(row).to_string()),
+			cmp: Some(Rc::new(move |a, b| {
+				cmp_key(a)
+					.partial_cmp(&cmp_key(b))
+					.unwrap_or(Ordering::Equal)
+			})),
+		});
+		self
+	}
+	/// adds a column that can't be sorted by
+	pub fn column_unsorted(
+		mut self,
+		title: impl Into<std::borrow::Cow<'static, str>>,
+		width: i32,
+		display: impl Fn(&T) -> String + 'static,
+	) -> Self {
+		self.columns.push(Column {
+			title: Text::new(title, self.text_size),
+			width,
+			display: Rc::new(display),
+			cmp: None,
+		});
+		self
+	}
+	/// lets more than one row be selected with ctrl and shift
+	pub fn multi_select(self) -> Self {
+		Self {
+			multi: true,
+			..self
+		}
+	}
+	pub fn with_scroll(self, scroll: ScrollableState) -> Self {
+		Self { scroll, ..self }
+	}
+
+	pub fn uid(&self) -> UniqueId {
+		self.uid
+	}
+	pub fn rows(&self) -> &Store<Vec<T>> {
+		&self.rows
+	}
+	/// indices into the rows, ascending
+	pub fn selected(&self) -> Vec<usize> {
+		self.selected.iter().copied().collect()
+	}
+	pub fn scroll(&self) -> ScrollableState {
+		self.scroll
+	}
+	pub fn sort(&self) -> Option<(usize, SortOrder)> {
+		self.sort
+	}
+	/// sorts by column `column`, or puts the rows back in their original order if `None`
+	pub fn sort_by(&mut self, sort: Option<(usize, SortOrder)>) {
+		self.sort = sort.filter(|(column, _)| {
+			(self.columns.get(*column)).is_some_and(|column| column.cmp.is_some())
+		});
+		self.refresh();
+	}
+	/// re-sorts the rows and forgets selected rows that don't exist anymore
+	pub fn refresh(&mut self) {
+		let len = self.rows.with_borrow(|rows| rows.len());
+		self.order = (0..len).collect();
+		if let Some((column, order)) = self.sort {
+			let cmp = self.columns[column]
+				.cmp
+				.clone()
+				.expect("sort_by checks this");
+			self.rows.with_borrow(|rows| {
+				self.order.sort_by(|a, b| match order {
+					SortOrder::Ascending => cmp(&rows[*a], &rows[*b]),
+					SortOrder::Descending => cmp(&rows[*b], &rows[*a]),
+				})
+			});
+		}
+		self.selected.retain(|i| *i < len);
+		self.anchor = self.anchor.filter(|i| *i < len);
+		self.cursor = self.cursor.filter(|i| *i < len);
+	}
+
+	/// unscaled, the header's too
+	fn row_h(&self) -> i32 {
+		self.text_size + PADDING * 2
+	}
+	/// scaled x and width of every column
+	fn column_spans(&self, det: Details, scale: f32) -> Vec<(i32, i32)> {
+		let mut x = det.x;
+		(self.columns.iter())
+			.map(|column| {
+				let w = (column.width as f32 * scale) as i32;
+				x += w;
+				(x - w, w)
+			})
+			.collect()
+	}
+	/// the header's det and the rows'
+	fn split(&self, det: Details, scale: f32) -> (Details, Details) {
+		let header_h = (self.row_h() as f32 * scale) as i32;
+		let header = Details {
+			ah: header_h,
+			..det
+		};
+		let body = Details {
+			y: det.y + header_h,
+			ah: (det.ah - header_h).max(0),
+			..det
+		};
+		(header, body)
+	}
+	fn list(&self) -> VirtualList<TableRow<'_, T>, impl Fn(usize) -> TableRow<'_, T>> {
+		VirtualList::new(self.scroll, self.order.len(), move |pos| TableRow {
+			table: self,
+			pos,
+		})
+		.with_row_height(self.row_h())
+	}
+
+	/// selects the row at `pos` in the shown order, like clicking it would
+	fn select(&mut self, pos: usize, shift: bool, ctrl: bool) {
+		let Some(index) = self.order.get(pos).copied() else {
+			return;
+		};
+		let anchor_pos =
+			(self.anchor).and_then(|anchor| self.order.iter().position(|i| *i == anchor));
+		match (self.multi, anchor_pos) {
+			(true, Some(anchor_pos)) if shift => {
+				if !ctrl {
+					self.selected.clear();
+				}
+				let (from, to) = (anchor_pos.min(pos), anchor_pos.max(pos));
+				self.selected.extend(self.order[from..=to].iter().copied());
+			}
+			(true, _) if ctrl => {
+				if !self.selected.remove(&index) {
+					self.selected.insert(index);
+				}
+				self.anchor = Some(index);
+			}
+			_ => {
+				self.selected.clear();
+				self.selected.insert(index);
+				self.anchor = Some(index);
+			}
+		}
+		self.cursor = Some(index);
+	}
+	/// scrolls just enough for the row at `pos` to be on screen
+	fn scroll_into_view(&mut self, pos: usize, body: Details, scale: f32) {
+		let row_h = self.row_h();
+		let viewport = (body.ah as f32 / scale) as i32;
+		let top = pos as i32 * row_h;
+		let scroll = &mut self.scroll.scroll_y;
+		if top < *scroll {
+			*scroll = top;
+		} else if top + row_h > *scroll + viewport {
+			*scroll = top + row_h - viewport;
+		}
+	}
+}
+
+impl<T: 'static> Layable for Table<T> {
+	fn size(&self) -> (i32, i32) {
+		let w = self.columns.iter().map(|column| column.width).sum();
+		(w, self.row_h() * (self.order.len() as i32 + 1))
+	}
+	fn render(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		let (header, body) = self.split(det, scale);
+		let padding = (PADDING as f32 * scale) as i32;
+
+		self.list().render(d, body, scale);
+
+		d.draw_rectangle(header.x, header.y, header.aw, header.ah, HEADER_BACKGROUND);
+		let spans = self.column_spans(det, scale);
+		for (i, (column, (x, w))) in self.columns.iter().zip(spans).enumerate() {
+			// the next column's background covers titles that are too long
+			if i > 0 {
+				d.draw_rectangle(x, header.y, w, header.ah, HEADER_BACKGROUND);
+			}
+			let title_det = Details {
+				x: x + padding,
+				y: header.y + padding,
+				aw: w - padding * 2,
+				ah: header.ah - padding * 2,
+			};
+			column.title.render(d, title_det, scale);
+
+			if let Some((_, order)) = self.sort.filter(|(sorted, _)| *sorted == i) {
+				sort_arrow(d, order, x + w - padding, header.y + header.ah / 2, scale);
+			}
+			d.draw_line(x + w, header.y, x + w, header.y + header.ah, SEPARATOR);
+		}
+		d.draw_line(
+			header.x,
+			header.y + header.ah,
+			header.x + header.aw,
+			header.y + header.ah,
+			SEPARATOR,
+		);
+		if d.focus() == self.uid {
+			d.draw_rectangle_lines(det.x, det.y, det.aw, det.ah, control::ACCENT);
+		}
+	}
+
+	fn tick(&mut self) {
+		let len = self.rows.with_borrow(|rows| rows.len());
+		if len != self.order.len() {
+			self.refresh();
+		}
+	}
+	fn pass_events(
+		&mut self,
+		events: impl Iterator<Item = Event>,
+		det: Details,
+		scale: f32,
+		ret_events: &mut Vec<ReturnEvent>,
+	) {
+		let events = events.collect::<Vec<_>>();
+		let held = |keys: [KeyboardKey; 2]| {
+			events.iter().any(|event| {
+				matches!(event, Event::KeyboardEvent(_, KeyboardEvent::KeyDown(key)) if keys.contains(key))
+			})
+		};
+		let shift = held([KeyboardKey::KEY_LEFT_SHIFT, KeyboardKey::KEY_RIGHT_SHIFT]);
+		let ctrl = held([
+			KeyboardKey::KEY_LEFT_CONTROL,
+			KeyboardKey::KEY_RIGHT_CONTROL,
+		]);
+
+		let (header, body) = self.split(det, scale);
+		let before = self.selected.clone();
+		let grab = (RESIZE_GRAB as f32 * scale) as i32;
+
+		for event in events.iter().copied() {
+			match event {
+				Event::MouseEvent(MouseEvent::MouseClick { x, y }) if header.is_inside(x, y) => {
+					ret_events.push(Event::ret(FocusCommand::Request(self.uid)));
+					let spans = self.column_spans(det, scale);
+					if let Some(column) = spans
+						.iter()
+						.position(|(c_x, w)| (x - (c_x + w)).abs() <= grab)
+					{
+						self.action = TableAction::Resizing {
+							column,
+							before: self.columns[column].width,
+							drag_start_x: x,
+						};
+					} else if let Some(column) =
+						spans.iter().position(|(c_x, w)| x >= *c_x && x < c_x + w)
+					{
+						let sort = match self.sort {
+							Some((sorted, SortOrder::Ascending)) if sorted == column => {
+								Some((column, SortOrder::Descending))
+							}
+							Some((sorted, SortOrder::Descending)) if sorted == column => None,
+							_ => Some((column, SortOrder::Ascending)),
+						};
+						self.sort_by(sort);
+					}
+				}
+				Event::MouseEvent(MouseEvent::MouseHeld { x, .. }) => {
+					if let TableAction::Resizing {
+						column,
+						before,
+						drag_start_x,
+					} = self.action
+					{
+						let moved = ((x - drag_start_x) as f32 / scale) as i32;
+						self.columns[column].width = (before + moved).max(MIN_COLUMN_WIDTH);
+					}
+				}
+				Event::MouseEvent(MouseEvent::MouseRelease { .. }) => {
+					self.action = TableAction::None;
+				}
+				event => {
+					let cursor_pos = (self.cursor)
+						.and_then(|cursor| self.order.iter().position(|i| *i == cursor));
+					let last = self.order.len().saturating_sub(1);
+					let page = ((body.ah as f32 / scale) as i32 / self.row_h()).max(1) as usize;
+					let pos = match (control::pressed_key(&event, self.uid), cursor_pos) {
+						(Some(KeyboardKey::KEY_UP), Some(pos)) => pos.saturating_sub(1),
+						(Some(KeyboardKey::KEY_DOWN), Some(pos)) => (pos + 1).min(last),
+						(Some(KeyboardKey::KEY_PAGE_UP), Some(pos)) => pos.saturating_sub(page),
+						(Some(KeyboardKey::KEY_PAGE_DOWN), Some(pos)) => (pos + page).min(last),
+						(Some(KeyboardKey::KEY_UP | KeyboardKey::KEY_DOWN), None) => 0,
+						(Some(KeyboardKey::KEY_HOME), _) => 0,
+						(Some(KeyboardKey::KEY_END), _) => last,
+						_ => continue,
+					};
+					if self.order.is_empty() {
+						continue;
+					}
+					self.select(pos, shift, false);
+					self.scroll_into_view(pos, body, scale);
+				}
+			}
+		}
+
+		// rows only get built for this, whatever they return is picked up here
+		let mut row_events = Vec::new();
+		let body_events = events.iter().copied().filter(|event| match event {
+			Event::MouseEvent(MouseEvent::MouseClick { x, y }) => body.is_inside(*x, *y),
+			_ => self.action == TableAction::None,
+		});
+		let scroll = {
+			let mut list = self.list();
+			list.pass_events(body_events, body, scale, &mut row_events);
+			list.state()
+		};
+		self.scroll = scroll;
+
+		for event in row_events {
+			if let Some(RowClicked(pos)) = event.take() {
+				ret_events.push(Event::ret(FocusCommand::Request(self.uid)));
+				self.select(pos, shift, ctrl);
+			}
+		}
+
+		if self.selected != before {
+			ret_events.push(Event::ret(SelectionChanged {
+				uid: self.uid,
+				selected: self.selected(),
+			}));
+		}
+	}
+}
+
+fn sort_arrow(d: &mut crate::Handle, order: SortOrder, right: i32, center_y: i32, scale: f32) {
+	let half = 4.0 * scale;
+	let (x, y) = (right as f32 - half, center_y as f32);
+	let (tip, base) = match order {
+		SortOrder::Ascending => (y - half / 2.0, y + half / 2.0),
+		SortOrder::Descending => (y + half / 2.0, y - half / 2.0),
+	};
+	let (left, right) = (Vector2::new(x - half, base), Vector2::new(x + half, base));
+	// raylib wants the points counter-clockwise
+	match order {
+		SortOrder::Ascending => d.draw_triangle(Vector2::new(x, tip), left, right, Color::WHITE),
+		SortOrder::Descending => d.draw_triangle(Vector2::new(x, tip), right, left, Color::WHITE),
+	}
+}
+
+/// a single row of a [Table], only ever built for a frame
+struct TableRow<'a, T: 'static> {
+	table: &'a Table<T>,
+	/// position in the sorted order
+	pos: usize,
+}
+impl<T: 'static> Layable for TableRow<'_, T> {
+	fn size(&self) -> (i32, i32) {
+		let w = self.table.columns.iter().map(|column| column.width).sum();
+		(w, self.table.row_h())
+	}
+	fn render(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		let table = self.table;
+		let Some(index) = table.order.get(self.pos).copied() else {
+			return;
+		};
+		let background = if table.selected.contains(&index) {
+			SELECTED_BACKGROUND
+		} else if self.pos % 2 == 1 {
+			ROW_BACKGROUND_ALT
+		} else {
+			ROW_BACKGROUND
+		};
+		let padding = (PADDING as f32 * scale) as i32;
+
+		d.draw_rectangle(det.x, det.y, det.aw, det.ah, background);
+		table.rows.with_borrow(|rows| {
+			let Some(row) = rows.get(index) else {
+				return;
+			};
+			let spans = table.column_spans(det, scale);
+			for (column, (x, w)) in table.columns.iter().zip(spans) {
+				// same as the header, each cell covers whatever the one before it spilled
+				d.draw_rectangle(x, det.y, w, det.ah, background);
+				let text_det = Details {
+					x: x + padding,
+					y: det.y + padding,
+					aw: w - padding * 2,
+					ah: det.ah - padding * 2,
+				};
+				Text::new((column.display)(row), table.text_size).render(d, text_det, scale);
+			}
+		});
+	}
+
+	fn pass_events(
+		&mut self,
+		events: impl Iterator<Item = Event>,
+		det: Details,
+		_scale: f32,
+		ret_events: &mut Vec<ReturnEvent>,
+	) {
+		for event in events {
+			if let Event::MouseEvent(MouseEvent::MouseClick { x, y }) = event {
+				if det.is_inside(x, y) {
+					ret_events.push(Event::ret(RowClicked(self.pos)));
+				}
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn table(rows: Vec<i32>) -> Table<i32> {
+		let mut table = Table::new(Store::new(rows), 16)
+			.column("n", 40, |n: &i32| *n)
+			.multi_select();
+		table.sort_by(Some((0, SortOrder::Ascending)));
+		table
+	}
+
+	#[test]
+	fn test_select() {
+		let mut table = table(vec![30, 10, 40, 20]);
+		assert_eq!(table.order, vec![1, 3, 0, 2]);
+
+		table.select(1, false, false);
+		assert_eq!(table.selected(), vec![3]);
+		// shift goes by the sorted order, not the rows'
+		table.select(2, true, false);
+		assert_eq!(table.selected(), vec![0, 3]);
+		// the anchor stays where the last plain click was
+		table.select(0, true, false);
+		assert_eq!(table.selected(), vec![1, 3]);
+
+		table.select(3, false, true);
+		assert_eq!(table.selected(), vec![1, 2, 3]);
+		table.select(0, false, true);
+		assert_eq!(table.selected(), vec![2, 3]);
+		// ctrl moved the anchor to the first row, ctrl+shift adds to what's there
+		table.select(1, true, true);
+		assert_eq!(table.selected(), vec![1, 2, 3]);
+
+		// without multi_select, shift and ctrl don't do anything
+		table.multi = false;
+		table.select(2, true, true);
+		assert_eq!(table.selected(), vec![0]);
+	}
+
+	#[test]
+	fn test_refresh() {
+		let mut table = table(vec![30, 10, 40, 20]);
+		table.select(3, false, false);
+		table.select(0, false, true);
+		assert_eq!(table.selected(), vec![1, 2]);
+
+		// editing in place doesn't re-sort on its own
+		table.rows.with_mut_borrow(|rows| rows[0] = 5);
+		table.tick();
+		assert_eq!(table.order, vec![1, 3, 0, 2]);
+		table.refresh();
+		assert_eq!(table.order, vec![0, 1, 3, 2]);
+
+		// the number of rows changing does, and forgets rows that are gone
+		table.rows.with_mut_borrow(|rows| rows.truncate(2));
+		table.tick();
+		assert_eq!(table.order, vec![0, 1]);
+		assert_eq!(table.selected(), vec![1]);
+		assert_eq!(table.anchor, Some(1));
+		assert_eq!(table.cursor, Some(1));
+
+		table.rows.with_mut_borrow(|rows| rows.push(0));
+		table.tick();
+		assert_eq!(table.order, vec![2, 0, 1]);
+
+		table.sort_by(None);
+		assert_eq!(table.order, vec![0, 1, 2]);
+	}
+}
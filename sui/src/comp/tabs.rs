@@ -0,0 +1,367 @@
+use std::collections::HashSet;
+
+use raylib::{ffi::KeyboardKey, prelude::RaylibDraw};
+
+use crate::{
+	core::{Event, KeyboardEvent, MouseEvent, ReturnEvent, Store},
+	form::{control, Changed, FocusCommand, UniqueId},
+	Color, Details, Layable, Text,
+};
+
+/// space around a tab's title, unscaled
+const PADDING: i32 = 6;
+/// how thick the line under the active tab is, unscaled
+const UNDERLINE: i32 = 2;
+const STRIP_BACKGROUND: Color = crate::color(35, 35, 35, 255);
+const TAB_HOVER: Color = crate::color(50, 50, 50, 255);
+const TAB_ACTIVE: Color = crate::color(60, 60, 60, 255);
+
+#[derive(Clone, Debug)]
+/// a strip of tab titles with the active tab's content under it. \
+/// the active tab's index lives in a `Store<usize>`, so it stays put when the tabs get rebuilt. \
+/// clicking a title or pressing ctrl+tab (ctrl+shift+tab to go back) switches tabs and returns a
+/// [Changed<usize>]. \
+/// ctrl+tab only works while the tabs have focus: after a title got clicked, or while something the content
+/// asked to focus is focused. so tabs next to each other don't switch together. \
+/// only the active tab's content gets `tick` and `pass_events`. use [Comp](crate::Comp) as `L` for tabs
+/// with different kinds of content
+pub struct Tabs<L: Layable> {
+	uid: UniqueId,
+	active: Store<usize>,
+	text_size: i32,
+	tabs: Vec<(Text<'static>, L)>,
+	/// the tab the mouse is over
+	hovered: Option<usize>,
+	/// every uid the content has asked to focus, ctrl+tab works while one of them is focused
+	content_focus: HashSet<UniqueId>,
+}
+impl<L: Layable> Tabs<L> {
+	pub fn new(active: Store<usize>, text_size: i32) -> Self {
+		Self {
+			uid: UniqueId::new(),
+			active,
+			text_size,
+			tabs: Vec::new(),
+			hovered: None,
+			content_focus: HashSet::new(),
+		}
+	}
+	pub fn tab(mut self, title: impl Into<std::borrow::Cow<'static, str>>, content: L) -> Self {
+		self.tabs.push((Text::new(title, self.text_size), content));
+		self
+	}
+
+	/// the uid [Changed] events are returned with
+	pub fn uid(&self) -> UniqueId {
+		self.uid
+	}
+	pub fn active_store(&self) -> &Store<usize> {
+		&self.active
+	}
+	/// the active tab's index, clamped to the tabs there are. `None` if there aren't any
+	pub fn active(&self) -> Option<usize> {
+		let len = self.tabs.len();
+		(len > 0).then(|| self.active.with_borrow(|active| (*active).min(len - 1)))
+	}
+	pub fn len(&self) -> usize {
+		self.tabs.len()
+	}
+	pub fn is_empty(&self) -> bool {
+		self.tabs.is_empty()
+	}
+
+	/// whether `event` is a ctrl+tab for these tabs
+	fn is_switch(&self, event: &Event, ctrl: bool) -> bool {
+		match event {
+			Event::KeyboardEvent(uid, KeyboardEvent::KeyPressed(KeyboardKey::KEY_TAB)) => {
+				ctrl && (*uid == self.uid || self.content_focus.contains(uid))
+			}
+			_ => false,
+		}
+	}
+	fn switch_to(&mut self, index: usize, ret_events: &mut Vec<ReturnEvent>) {
+		if Some(index) == self.active() {
+			return;
+		}
+		self.active.set(index);
+		ret_events.push(Event::ret(Changed {
+			uid: self.uid,
+			value: index,
+		}));
+	}
+
+	/// unscaled
+	fn strip_height(&self) -> i32 {
+		let title_h = (self.tabs.iter())
+			.map(|(title, _)| title.size().1)
+			.max()
+			.unwrap_or(self.text_size);
+		title_h + PADDING * 2 + UNDERLINE
+	}
+	/// the strip and where the content goes
+	fn split(&self, det: Details, scale: f32) -> (Details, Details) {
+		let strip_h = ((self.strip_height() as f32 * scale) as i32).min(det.ah);
+		let strip = Details { ah: strip_h, ..det };
+		let content = Details {
+			y: det.y + strip_h,
+			ah: det.ah - strip_h,
+			..det
+		};
+		(strip, content)
+	}
+	/// every tab's title in the strip
+	fn title_dets(&self, strip: Details, scale: f32) -> Vec<Details> {
+		let mut x = strip.x;
+		(self.tabs.iter())
+			.map(|(title, _)| {
+				let w = ((title.size().0 + PADDING * 2) as f32 * scale) as i32;
+				x += w;
+				Details {
+					x: x - w,
+					aw: w,
+					..strip
+				}
+			})
+			.collect()
+	}
+}
+
+impl<L: Layable> Layable for Tabs<L> {
+	fn size(&self) -> (i32, i32) {
+		let strip_w = (self.tabs.iter())
+			.map(|(title, _)| title.size().0 + PADDING * 2)
+			.sum::<i32>();
+		let (content_w, content_h) = match self.active() {
+			Some(active) => self.tabs[active].1.size(),
+			None => (0, 0),
+		};
+		(strip_w.max(content_w), self.strip_height() + content_h)
+	}
+	fn render(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		let (strip, content) = self.split(det, scale);
+		d.draw_rectangle(strip.x, strip.y, strip.aw, strip.ah, STRIP_BACKGROUND);
+
+		let active = self.active();
+		let padding = (PADDING as f32 * scale) as i32;
+		let underline = ((UNDERLINE as f32 * scale) as i32).max(1);
+		for (i, ((title, _), title_det)) in self
+			.tabs
+			.iter()
+			.zip(self.title_dets(strip, scale))
+			.enumerate()
+		{
+			let t = title_det;
+			if Some(i) == active {
+				d.draw_rectangle(t.x, t.y, t.aw, t.ah, TAB_ACTIVE);
+				d.draw_rectangle(
+					t.x,
+					t.y + t.ah - underline,
+					t.aw,
+					underline,
+					control::ACCENT,
+				);
+			} else if Some(i) == self.hovered {
+				d.draw_rectangle(t.x, t.y, t.aw, t.ah, TAB_HOVER);
+			}
+			let text_det = Details {
+				x: t.x + padding,
+				y: t.y + padding,
+				aw: t.aw - padding * 2,
+				ah: t.ah - padding * 2 - underline,
+			};
+			title.render(d, text_det, scale);
+		}
+
+		if let Some(active) = active {
+			self.tabs[active].1.render(d, content, scale);
+		}
+	}
+
+	fn tick(&mut self) {
+		if let Some(active) = self.active() {
+			self.tabs[active].1.tick();
+		}
+	}
+	fn pass_events(
+		&mut self,
+		events: impl Iterator<Item = Event>,
+		det: Details,
+		scale: f32,
+		ret_events: &mut Vec<ReturnEvent>,
+	) {
+		let events = events.collect::<Vec<_>>();
+		let held = |keys: [KeyboardKey; 2]| {
+			events.iter().any(|event| {
+				matches!(event, Event::KeyboardEvent(_, KeyboardEvent::KeyDown(key)) if keys.contains(key))
+			})
+		};
+		let ctrl = held([
+			KeyboardKey::KEY_LEFT_CONTROL,
+			KeyboardKey::KEY_RIGHT_CONTROL,
+		]);
+		let shift = held([KeyboardKey::KEY_LEFT_SHIFT, KeyboardKey::KEY_RIGHT_SHIFT]);
+		let switches = (events.iter())
+			.map(|event| self.is_switch(event, ctrl))
+			.collect::<Vec<_>>();
+
+		let (strip, content) = self.split(det, scale);
+		let titles = self.title_dets(strip, scale);
+		for (event, is_switch) in events.iter().zip(switches.iter()) {
+			match *event {
+				Event::MouseEvent(MouseEvent::MouseMove { x, y }) => {
+					self.hovered = titles.iter().position(|t| t.is_inside(x, y));
+				}
+				Event::MouseEvent(MouseEvent::MouseClick { x, y }) => {
+					if let Some(i) = titles.iter().position(|t| t.is_inside(x, y)) {
+						ret_events.push(Event::ret(FocusCommand::Request(self.uid)));
+						self.switch_to(i, ret_events);
+					}
+				}
+				_ if *is_switch && !self.tabs.is_empty() => {
+					let len = self.tabs.len();
+					let active = self.active().unwrap_or_default();
+					let next = match shift {
+						true => (active + len - 1) % len,
+						false => (active + 1) % len,
+					};
+					self.switch_to(next, ret_events);
+				}
+				_ => {}
+			}
+		}
+
+		let Some(active) = self.active() else {
+			return;
+		};
+		let content_events = (events.iter().copied().zip(switches))
+			.filter(|(event, is_switch)| match event {
+				Event::MouseEvent(m_event) => content.is_inside_tuple(m_event.at()),
+				// so nested tabs don't switch along
+				_ => !is_switch,
+			})
+			.map(|(event, _)| event);
+		let start = ret_events.len();
+		self.tabs[active]
+			.1
+			.pass_events(content_events, content, scale, ret_events);
+		for event in &ret_events[start..] {
+			if let Some(FocusCommand::Request(uid)) = event.peek::<FocusCommand>() {
+				self.content_focus.insert(*uid);
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{cell::Cell, rc::Rc};
+
+	use super::*;
+
+	#[derive(Clone, Debug)]
+	/// counts its ticks and events, and asks for focus when it gets a click
+	struct Content {
+		uid: UniqueId,
+		ticks: Rc<Cell<usize>>,
+		events: Rc<Cell<usize>>,
+	}
+	impl Layable for Content {
+		fn size(&self) -> (i32, i32) {
+			(100, 100)
+		}
+		fn render(&self, _: &mut crate::Handle, _: Details, _: f32) {}
+		fn tick(&mut self) {
+			self.ticks.set(self.ticks.get() + 1);
+		}
+		fn pass_events(
+			&mut self,
+			events: impl Iterator<Item = Event>,
+			_: Details,
+			_: f32,
+			ret_events: &mut Vec<ReturnEvent>,
+		) {
+			for event in events {
+				self.events.set(self.events.get() + 1);
+				if let Event::MouseEvent(MouseEvent::MouseClick { .. }) = event {
+					ret_events.push(Event::ret(FocusCommand::Request(self.uid)));
+				}
+			}
+		}
+	}
+	fn content() -> Content {
+		Content {
+			uid: UniqueId::new(),
+			ticks: Rc::default(),
+			events: Rc::default(),
+		}
+	}
+	fn tabs(contents: &[Content]) -> Tabs<Content> {
+		(contents.iter()).fold(Tabs::new(Store::new(0), 16), |tabs, content| {
+			tabs.tab("tab", content.clone())
+		})
+	}
+	const DET: Details = Details {
+		x: 0,
+		y: 0,
+		aw: 200,
+		ah: 200,
+	};
+	fn press(tabs: &mut Tabs<Content>, uid: UniqueId, shift: bool) {
+		let mut events = vec![Event::KeyboardEvent(
+			uid,
+			KeyboardEvent::KeyDown(KeyboardKey::KEY_LEFT_CONTROL),
+		)];
+		if shift {
+			events.push(Event::KeyboardEvent(
+				uid,
+				KeyboardEvent::KeyDown(KeyboardKey::KEY_LEFT_SHIFT),
+			));
+		}
+		events.push(Event::KeyboardEvent(
+			uid,
+			KeyboardEvent::KeyPressed(KeyboardKey::KEY_TAB),
+		));
+		tabs.pass_events(events.into_iter(), DET, 1.0, &mut Vec::new());
+	}
+
+	#[test]
+	fn test_only_active() {
+		let contents = [content(), content()];
+		let mut tabs = tabs(&contents);
+		tabs.tick();
+		let key = Event::KeyboardEvent(UniqueId::null(), KeyboardEvent::CharPressed('a'));
+		tabs.pass_events(std::iter::once(key), DET, 1.0, &mut Vec::new());
+		assert_eq!((contents[0].ticks.get(), contents[0].events.get()), (1, 1));
+		assert_eq!((contents[1].ticks.get(), contents[1].events.get()), (0, 0));
+
+		tabs.active_store().set(1);
+		tabs.tick();
+		tabs.pass_events(std::iter::once(key), DET, 1.0, &mut Vec::new());
+		assert_eq!((contents[0].ticks.get(), contents[0].events.get()), (1, 1));
+		assert_eq!((contents[1].ticks.get(), contents[1].events.get()), (1, 1));
+	}
+
+	#[test]
+	fn test_switch() {
+		let contents = [content(), content(), content()];
+		let mut tabs = tabs(&contents);
+		let uid = tabs.uid();
+
+		press(&mut tabs, uid, true);
+		assert_eq!(tabs.active(), Some(2));
+		press(&mut tabs, uid, false);
+		assert_eq!(tabs.active(), Some(0));
+		press(&mut tabs, uid, false);
+		assert_eq!(tabs.active(), Some(1));
+
+		// something else is focused
+		press(&mut tabs, UniqueId::new(), false);
+		assert_eq!(tabs.active(), Some(1));
+
+		// the content got focused by a click
+		let click = Event::MouseEvent(MouseEvent::MouseClick { x: 10, y: 150 });
+		tabs.pass_events(std::iter::once(click), DET, 1.0, &mut Vec::new());
+		press(&mut tabs, contents[1].uid, false);
+		assert_eq!(tabs.active(), Some(2));
+	}
+}
@@ -1,15 +1,28 @@
 use std::{
+	collections::BTreeSet,
 	mem::ManuallyDrop,
 	ops::Deref,
-	sync::{Arc, Mutex},
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc, Mutex, OnceLock, RwLock,
+	},
 };
 
+use super::measure::GlyphMetrics;
+
 static DEFAULT_FONT: Mutex<Option<Font>> = Mutex::new(None);
+static NEXT_FONT_ID: AtomicU64 = AtomicU64::new(1);
 
 #[derive(Debug)]
 pub struct FontInner {
 	pub font: ManuallyDrop<raylib::text::Font>,
 	pub needs_drop: bool,
+
+	/// unique for every font created, used as the key of the measurement cache
+	id: u64,
+	metrics: OnceLock<GlyphMetrics>,
+	/// whether the glyphs are signed distance fields, see [Font::from_raylib_sdf]
+	sdf: bool,
 }
 impl Drop for FontInner {
 	fn drop(&mut self) {
@@ -19,6 +32,14 @@ impl Drop for FontInner {
 	}
 }
 
+/// what every clone of a [Font] points to. the font inside can be swapped out with [Font::replace]
+#[derive(Debug)]
+struct FontShared {
+	current: RwLock<Arc<FontInner>>,
+	/// characters that text was measured with but the current font doesn't have a glyph for
+	missing: Mutex<BTreeSet<char>>,
+}
+
 /// it's a font idk what else to tell you \
 ///
 /// ## Send Safety
@@ -30,7 +51,7 @@ impl Drop for FontInner {
 /// to the main thread to render
 #[derive(Debug, Clone)]
 pub struct Font {
-	font: Arc<FontInner>,
+	font: Arc<FontShared>,
 }
 unsafe impl Send for Font {}
 impl Default for Font {
@@ -56,8 +77,58 @@ impl Default for Font {
 }
 impl Font {
 	pub fn from_raylib(font: raylib::text::Font, needs_drop: bool) -> Self {
+		Self::from_parts(font, needs_drop, OnceLock::new(), false)
+	}
+	/// for fonts loaded as signed distance fields (`FONT_SDF`). these get rendered through
+	/// raylib's sdf shader, so they stay sharp at any size. see [super::sdf]
+	pub fn from_raylib_sdf(font: raylib::text::Font, needs_drop: bool) -> Self {
+		Self::from_parts(font, needs_drop, OnceLock::new(), true)
+	}
+	/// same as [Self::from_raylib], but measures with `metrics` instead of reading them from the raylib font
+	pub fn from_raylib_with_metrics(
+		font: raylib::text::Font,
+		needs_drop: bool,
+		metrics: GlyphMetrics,
+	) -> Self {
+		Self::from_parts(font, needs_drop, OnceLock::from(metrics), false)
+	}
+	/// creates a font with no raylib font behind it, so it can be created and measured without
+	/// a raylib context (tests, background threads, etc) \
+	/// rendering a headless font falls back to raylib's default font
+	pub fn headless(metrics: GlyphMetrics) -> Self {
+		// raylib treats a font with no texture as the default font
+		let font = unsafe { raylib::text::Font::from_raw(std::mem::zeroed()) };
+		Self::from_raylib_with_metrics(font, false, metrics)
+	}
+	/// parses a ttf/otf font and creates a [headless](Self::headless) font from it. \
+	/// see [GlyphMetrics::from_ttf]
+	#[cfg(feature = "ttf")]
+	pub fn headless_from_ttf(
+		data: &[u8],
+		base_size: i32,
+		chars: impl IntoIterator<Item = char>,
+	) -> Result<Self, ttf_parser::FaceParsingError> {
+		let metrics = GlyphMetrics::from_ttf(data, base_size, chars)?;
+		Ok(Self::headless(metrics))
+	}
+	fn from_parts(
+		font: raylib::text::Font,
+		needs_drop: bool,
+		metrics: OnceLock<GlyphMetrics>,
+		sdf: bool,
+	) -> Self {
 		let font = ManuallyDrop::new(font);
-		let font = FontInner { font, needs_drop };
+		let font = FontInner {
+			font,
+			needs_drop,
+			id: NEXT_FONT_ID.fetch_add(1, Ordering::Relaxed),
+			metrics,
+			sdf,
+		};
+		let font = FontShared {
+			current: RwLock::new(Arc::new(font)),
+			missing: Default::default(),
+		};
 		Self {
 			font: Arc::new(font),
 		}
@@ -79,8 +150,71 @@ impl Font {
 		*lock = Some(self.clone())
 	}
 
+	fn inner(&self) -> Arc<FontInner> {
+		let lock = match self.font.current.read() {
+			Ok(a) => a,
+			Err(err) => err.into_inner(),
+		};
+		lock.clone()
+	}
+
+	/// swaps the font inside for `with`'s, for this font and every clone of it. \
+	/// the old font gets dropped (unloaded, if it needs to) once nothing's rendering with it,
+	/// so this should be called from the main thread
+	pub fn replace(&self, with: &Font) {
+		let new = with.inner();
+		let mut lock = match self.font.current.write() {
+			Ok(a) => a,
+			Err(err) => err.into_inner(),
+		};
+		*lock = new;
+	}
+
+	/// unique for every font loaded. changes when the font gets [replaced](Self::replace)
+	pub fn id(&self) -> u64 {
+		self.inner().id
+	}
+	pub fn is_sdf(&self) -> bool {
+		self.inner().sdf
+	}
+	/// calls f with the glyph advance table of this font, which gets read from the raylib font the first time it's needed
+	pub fn with_metrics<R>(&self, f: impl FnOnce(&GlyphMetrics) -> R) -> R {
+		let inner = self.inner();
+		let metrics = inner
+			.metrics
+			.get_or_init(|| GlyphMetrics::from_raylib(&inner.font));
+		f(metrics)
+	}
+
+	/// remembers every character in `text` this font doesn't have a glyph for. \
+	/// text measurement calls this, so every character that shows up in a [Text](crate::Text) ends up here
+	pub fn note_missing_glyphs(&self, text: &str) {
+		let missing = self.with_metrics(|metrics| {
+			text.chars()
+				.filter(|c| !c.is_control() && !metrics.has_glyph(*c))
+				.collect::<Vec<_>>()
+		});
+		if missing.is_empty() {
+			return;
+		}
+
+		let mut lock = match self.font.missing.lock() {
+			Ok(a) => a,
+			Err(err) => err.into_inner(),
+		};
+		lock.extend(missing);
+	}
+	/// returns and forgets every character [Self::note_missing_glyphs] found so far
+	pub fn take_missing_glyphs(&self) -> BTreeSet<char> {
+		let mut lock = match self.font.missing.lock() {
+			Ok(a) => a,
+			Err(err) => err.into_inner(),
+		};
+		std::mem::take(&mut *lock)
+	}
+
 	pub fn with_font<R, F: FnOnce(&raylib::text::Font) -> R>(&self, f: F) -> R {
-		let inner = self.font.as_ref();
+		let inner = self.inner();
 		let r = inner.font.deref();
 
 		f(r)
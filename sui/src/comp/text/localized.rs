@@ -0,0 +1,128 @@
+use std::borrow::Cow;
+
+use super::{FontKey, Text, TextLayout};
+use crate::{l10n, Color, Layable};
+
+/// [Text] that displays the string for a [l10n] key in the active locale. \
+/// re-translates itself on tick whenever [l10n::generation] changes, so switching locales
+/// doesn't require rebuilding the ui
+#[derive(Debug, Clone)]
+pub struct LocalizedText {
+	key: Cow<'static, str>,
+	count: Option<i64>,
+	args: Vec<(Cow<'static, str>, String)>,
+
+	generation: u64,
+	text: Text<'static>,
+}
+impl LocalizedText {
+	pub fn new(key: impl Into<Cow<'static, str>>, size: i32) -> Self {
+		Self::from_text(key, Text::new("", size))
+	}
+	pub fn new_colored(key: impl Into<Cow<'static, str>>, size: i32, color: Color) -> Self {
+		Self::from_text(key, Text::new_colored("", size, color))
+	}
+	/// uses `text`'s size, font and color, its text gets replaced by the translation
+	pub fn from_text(key: impl Into<Cow<'static, str>>, text: Text<'static>) -> Self {
+		let mut localized = Self {
+			key: key.into(),
+			count: None,
+			args: Vec::new(),
+			generation: 0,
+			text,
+		};
+		localized.retranslate();
+		localized
+	}
+
+	/// adds an arg for the `{name}` placeholder, see [l10n::interpolate]
+	pub fn with_arg(mut self, name: impl Into<Cow<'static, str>>, value: impl ToString) -> Self {
+		self.set_arg(name, value);
+		self
+	}
+	/// uses the plural variant for `count` items, see [l10n::tr_count]
+	pub fn with_count(mut self, count: i64) -> Self {
+		self.set_count(count);
+		self
+	}
+	pub fn with_font(self, font: super::Font) -> Self {
+		Self {
+			text: self.text.with_font(font),
+			..self
+		}
+	}
+	/// see [Text::with_family]
+	pub fn with_family(self, key: &FontKey) -> Self {
+		Self {
+			text: self.text.with_family(key),
+			..self
+		}
+	}
+
+	pub fn set_arg(&mut self, name: impl Into<Cow<'static, str>>, value: impl ToString) {
+		let name = name.into();
+		let value = value.to_string();
+		match self.args.iter_mut().find(|(existing, _)| *existing == name) {
+			Some((_, existing)) => *existing = value,
+			None => self.args.push((name, value)),
+		}
+		self.retranslate();
+	}
+	pub fn set_count(&mut self, count: i64) {
+		self.count = Some(count);
+		self.retranslate();
+	}
+
+	pub fn key(&self) -> &str {
+		&self.key
+	}
+	/// the translated text, as it's displayed right now
+	pub fn text(&self) -> &str {
+		&self.text.text
+	}
+
+	fn retranslate(&mut self) {
+		self.generation = l10n::generation();
+
+		let args = self
+			.args
+			.iter()
+			.map(|(name, value)| (name.as_ref(), value.as_str()))
+			.collect::<Vec<_>>();
+		let translated = match self.count {
+			Some(count) => l10n::tr_count(&self.key, count, &args),
+			None => l10n::tr_args(&self.key, &args),
+		};
+		self.text.text = Cow::Owned(translated);
+	}
+}
+
+impl Layable for LocalizedText {
+	fn size(&self) -> (i32, i32) {
+		self.text.size()
+	}
+	fn render(&self, d: &mut crate::Handle, det: crate::Details, scale: f32) {
+		self.text.render(d, det, scale)
+	}
+
+	fn tick(&mut self) {
+		if self.generation != l10n::generation() {
+			self.retranslate();
+		}
+		self.text.tick();
+	}
+}
+impl TextLayout for LocalizedText {
+	fn text(&self) -> &str {
+		self.text.text()
+	}
+	fn font(&self) -> &super::Font {
+		self.text.font()
+	}
+	fn text_size(&self) -> i32 {
+		self.text.text_size()
+	}
+	fn lines(&self, det: crate::Details, scale: f32) -> Vec<super::selectable::LaidOutLine> {
+		self.text.lines(det, scale)
+	}
+}
@@ -0,0 +1,245 @@
+use std::{
+	cell::RefCell,
+	collections::HashMap,
+	hash::{DefaultHasher, Hash, Hasher},
+};
+
+use super::Font;
+
+/// the measurement cache gets cleared when it reaches this many entries
+pub const MEASURE_CACHE_CAPACITY: usize = 4096;
+
+/// (font id, size, hash of the text)
+type MeasureKey = (u64, i32, u64);
+
+thread_local! {
+	static MEASURE_CACHE: RefCell<HashMap<MeasureKey, (i32, i32)>> = RefCell::new(HashMap::new());
+}
+
+/// measures `text` with [GlyphMetrics] and caches the result, so the same line with
+/// the same font and size only gets measured once
+pub fn measure_line_cached(text: &str, size: i32, font: &Font) -> (i32, i32) {
+	let mut hasher = DefaultHasher::new();
+	text.hash(&mut hasher);
+	let key = (font.id(), size, hasher.finish());
+
+	if let Some(cached) = MEASURE_CACHE.with_borrow(|cache| cache.get(&key).copied()) {
+		return cached;
+	}
+
+	font.note_missing_glyphs(text);
+	let (w, h) =
+		font.with_metrics(|metrics| metrics.measure_line(text, size as f32, super::SPACING));
+	let measured = (w.ceil() as i32, h.ceil() as i32);
+
+	MEASURE_CACHE.with_borrow_mut(|cache| {
+		if cache.len() >= MEASURE_CACHE_CAPACITY {
+			cache.clear();
+		}
+		cache.insert(key, measured);
+	});
+	measured
+}
+
+/// drops every cached measurement on this thread
+pub fn clear_measure_cache() {
+	MEASURE_CACHE.with_borrow_mut(|cache| cache.clear());
+}
+
+/// per-glyph advance table of a font, read out of the raylib font once so measuring text
+/// doesn't have to go through ffi
+#[derive(Clone, Debug, Default)]
+pub struct GlyphMetrics {
+	base_size: i32,
+	/// unscaled advance of every glyph in the font
+	advances: HashMap<char, f32>,
+	/// the advance raylib falls back to for glyphs the font doesn't have (the advance of '?',
+	/// or of the first glyph if there's no '?')
+	fallback_advance: f32,
+}
+impl GlyphMetrics {
+	pub fn new(base_size: i32, advances: HashMap<char, f32>) -> Self {
+		let fallback_advance = advances.get(&'?').copied().unwrap_or_default();
+		Self {
+			base_size,
+			advances,
+			fallback_advance,
+		}
+	}
+	/// reads the advances out of `font.glyphs`, the same way `MeasureTextEx` would
+	pub fn from_raylib(font: &raylib::ffi::Font) -> Self {
+		if font.texture.id == 0 || font.glyphs.is_null() || font.recs.is_null() {
+			return Self::default();
+		}
+
+		let count = font.glyphCount.max(0) as usize;
+		let glyphs = unsafe { std::slice::from_raw_parts(font.glyphs, count) };
+		let recs = unsafe { std::slice::from_raw_parts(font.recs, count) };
+
+		let mut advances = HashMap::with_capacity(count);
+		for (glyph, rec) in glyphs.iter().zip(recs) {
+			let advance = if glyph.advanceX != 0 {
+				glyph.advanceX as f32
+			} else {
+				rec.width + glyph.offsetX as f32
+			};
+			if let Some(c) = char::from_u32(glyph.value as u32) {
+				// GetGlyphIndex returns the first glyph with a matching codepoint
+				advances.entry(c).or_insert(advance);
+			}
+		}
+
+		let mut metrics = Self::new(font.baseSize, advances);
+		if !metrics.advances.contains_key(&'?') {
+			// no '?' means raylib falls back to the first glyph
+			if let (Some(glyph), Some(rec)) = (glyphs.first(), recs.first()) {
+				metrics.fallback_advance = if glyph.advanceX != 0 {
+					glyph.advanceX as f32
+				} else {
+					rec.width + glyph.offsetX as f32
+				};
+			}
+		}
+		metrics
+	}
+	/// computes the advances from the font file itself, no raylib context required. \
+	/// the math is the same as raylib's `LoadFontData` (stb_truetype) loading the font at `base_size`,
+	/// with `chars` as the codepoints, so fonts measure identically both ways
+	///
+	/// codepoints the font doesn't have get the advance of .notdef, and if '?' isn't in `chars`, glyphs
+	/// that weren't loaded fall back to the first one in `chars`, both like raylib. \
+	/// glyphs with an advance of zero aren't rasterized here, so they measure as zero instead of
+	/// their bitmap's width like they would with [Self::from_raylib]
+	#[cfg(feature = "ttf")]
+	pub fn from_ttf(
+		data: &[u8],
+		base_size: i32,
+		chars: impl IntoIterator<Item = char>,
+	) -> Result<Self, ttf_parser::FaceParsingError> {
+		let face = ttf_parser::Face::parse(data, 0)?;
+
+		// stbtt_ScaleForPixelHeight
+		let hhea = face.tables().hhea;
+		let scale_factor = base_size as f32 / (hhea.ascender as f32 - hhea.descender as f32);
+
+		let mut advances = HashMap::new();
+		let mut first = None;
+		for c in chars {
+			first.get_or_insert(c);
+			// stb_truetype uses .notdef for codepoints the font doesn't have, and so does raylib
+			let glyph = face.glyph_index(c).unwrap_or(ttf_parser::GlyphId(0));
+			let advance = face.glyph_hor_advance(glyph).unwrap_or(0);
+
+			// raylib truncates the scaled advance into an int
+			let advance = (advance as f32 * scale_factor) as i32;
+			advances.entry(c).or_insert(advance as f32);
+		}
+
+		let mut metrics = Self::new(base_size, advances);
+		if let (false, Some(first)) = (metrics.has_glyph('?'), first) {
+			// no '?' means raylib falls back to the first glyph, same as in from_raylib
+			metrics.fallback_advance = metrics.advance(first);
+		}
+		Ok(metrics)
+	}
+
+	/// sets the base size the advances are relative to, without rescaling the advances. \
+	/// this is how raylib fonts can be made to render smaller or larger than they were rasterized at
+	pub fn with_base_size(self, base_size: i32) -> Self {
+		Self { base_size, ..self }
+	}
+
+	pub fn base_size(&self) -> i32 {
+		self.base_size
+	}
+	pub fn has_glyph(&self, c: char) -> bool {
+		self.advances.contains_key(&c)
+	}
+	/// unscaled advance of `c`
+	pub fn advance(&self, c: char) -> f32 {
+		self.advances
+			.get(&c)
+			.copied()
+			.unwrap_or(self.fallback_advance)
+	}
+
+	/// same math as raylib's `MeasureTextEx`. `text` is expected to be a single line. \
+	/// empty text measures as `(0, 0)`, like it does in raylib
+	pub fn measure_line(&self, text: &str, size: f32, spacing: f32) -> (f32, f32) {
+		if self.base_size == 0 || text.is_empty() {
+			return (0.0, 0.0);
+		}
+
+		let (mut width, mut count) = (0.0, 0);
+		for c in text.chars() {
+			width += self.advance(c);
+			count += 1;
+		}
+
+		let scale_factor = size / self.base_size as f32;
+		(width * scale_factor + (count - 1) as f32 * spacing, size)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_measure_line() {
+		let metrics = GlyphMetrics::new(10, [('a', 5.0), ('b', 7.0), ('?', 4.0)].into());
+
+		assert_eq!(metrics.measure_line("ab", 10.0, 1.0), (13.0, 10.0));
+		assert_eq!(metrics.measure_line("ab", 20.0, 1.0), (25.0, 20.0));
+		// unknown glyphs measure as '?'
+		assert_eq!(metrics.measure_line("ax", 10.0, 0.0), (9.0, 10.0));
+		assert_eq!(metrics.measure_line("", 10.0, 1.0), (0.0, 0.0));
+		// empty lines don't get the spacing taken off either
+		let lines = "a\n\nb"
+			.split('\n')
+			.map(|line| metrics.measure_line(line, 10.0, 1.0))
+			.collect::<Vec<_>>();
+		assert_eq!(lines, [(5.0, 10.0), (0.0, 0.0), (7.0, 10.0)]);
+		assert_eq!(
+			GlyphMetrics::default().measure_line("ab", 10.0, 1.0),
+			(0.0, 0.0)
+		);
+	}
+
+	#[test]
+	fn test_headless_font() {
+		let font = Font::headless(GlyphMetrics::new(10, [('a', 5.0), ('?', 4.0)].into()));
+
+		assert_eq!(measure_line_cached("aa", 10, &font), (11, 10));
+		assert!(font.take_missing_glyphs().is_empty());
+
+		assert_eq!(measure_line_cached("ab", 10, &font), (10, 10));
+		assert_eq!(font.take_missing_glyphs(), ['b'].into());
+		assert!(font.take_missing_glyphs().is_empty());
+	}
+
+	#[cfg(feature = "ttf")]
+	#[test]
+	fn test_ttf() {
+		use crate::Layable;
+
+		// 1000 units per em, .notdef is 500 wide, ' ' 250, 'a' 600 and 'b' 400. there's no '?'
+		const TINY: &[u8] =
+			include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fonts/tiny.ttf"));
+
+		let metrics = GlyphMetrics::from_ttf(TINY, 10, "ab ".chars()).unwrap();
+		assert_eq!(metrics.advance('a'), 6.0);
+		// 2.5, truncated
+		assert_eq!(metrics.advance(' '), 2.0);
+		// glyphs that weren't loaded fall back to the first one, since there's no '?' either
+		assert_eq!(metrics.advance('x'), 6.0);
+		// '?' is loaded, but it's not in the font, so it's .notdef
+		let metrics = GlyphMetrics::from_ttf(TINY, 10, "?ab".chars()).unwrap();
+		assert_eq!(metrics.advance('x'), 5.0);
+
+		let font = Font::headless_from_ttf(TINY, 10, "ab ".chars()).unwrap();
+		let text = crate::Text::new("ab a\nb", 20).with_font(font);
+		// (6 + 4 + 2 + 6) * 2 + 3 spacing wide, and 2 lines of 20 - 1
+		assert_eq!(text.size(), (39, 38));
+	}
+}
@@ -3,12 +3,26 @@ use raylib::{color::Color, math::Vector2, prelude::RaylibDraw};
 use std::borrow::Cow;
 
 pub mod wrapped_text;
-pub use wrapped_text::{CenteredWrappedText, WrappedText};
+pub use wrapped_text::{CenteredWrappedText, Run, WrappedText};
 
 pub mod font;
 pub(self) mod word_wrap;
 pub use font::Font;
 
+pub mod measure;
+pub use measure::{clear_measure_cache, GlyphMetrics};
+
+pub mod registry;
+pub use registry::{FontKey, FontStyle, FontWeight};
+
+pub mod sdf;
+
+pub mod localized;
+pub use localized::LocalizedText;
+
+pub mod selectable;
+pub use selectable::{Selectable, TextLayout};
+
 // --
 
 pub const BOUNDS_DEBUG: bool = false;
@@ -45,6 +59,14 @@ impl<'a> Text<'a> {
 			color,
 		}
 	}
+
+	pub fn with_font(self, font: Font) -> Self {
+		Self { font, ..self }
+	}
+	/// uses the registered font closest to `key`, see [registry::get]
+	pub fn with_family(self, key: &FontKey) -> Self {
+		self.with_font(registry::get(key))
+	}
 }
 impl<'a, I: Into<Cow<'a, str>>> Into<Text<'a>> for (I, i32) {
 	fn into(self) -> Text<'a> {
@@ -56,7 +78,7 @@ impl<'a> Layable for Text<'a> {
 	fn size(&self) -> (i32, i32) {
 		self.text
 			.split('\n')
-			.map(|line| measure_line(line, self.size))
+			.map(|line| measure_line_font(line, self.size, &self.font))
 			.fold((0, 0), |acc, (x, y)| (acc.0.max(x), acc.1 + y - 1)) // i don't know why we need to remove 1 pixel from height per line
 	}
 	fn render(&self, d: &mut crate::Handle, det: crate::Details, scale: f32) {
@@ -65,24 +87,55 @@ impl<'a> Layable for Text<'a> {
 			d.draw_rectangle_lines(det.x, det.y, s.0, s.1, Color::WHITE);
 		}
 
-		self.font.with_font(|font| {
-			d.draw_text_ex(
-				font,
-				&self.text,
-				Vector2::new(det.x as f32, det.y as f32),
-				self.size as f32 * scale,
-				SPACING,
-				self.color,
-			);
+		sdf::with_font_shader(&self.font, || {
+			self.font.with_font(|font| {
+				d.draw_text_ex(
+					font,
+					&self.text,
+					Vector2::new(det.x as f32, det.y as f32),
+					self.size as f32 * scale,
+					SPACING,
+					self.color,
+				);
+			})
 		})
 	}
 }
 
+impl<'a> TextLayout for Text<'a> {
+	fn text(&self) -> &str {
+		&self.text
+	}
+	fn font(&self) -> &Font {
+		&self.font
+	}
+	fn text_size(&self) -> i32 {
+		self.size
+	}
+	fn lines(&self, _det: crate::Details, scale: f32) -> Vec<selectable::LaidOutLine> {
+		let h = (self.size as f32 * scale) as i32;
+		word_wrap::text_splitter(&self.text, &[b'\n'])
+			.into_iter()
+			.enumerate()
+			.map(|(i, range)| selectable::LaidOutLine {
+				range,
+				y: i as i32 * (h + selectable::RAYLIB_LINE_SPACING),
+				h,
+			})
+			.collect()
+	}
+}
+
 pub fn measure_line(text: &str, size: i32) -> (i32, i32) {
 	let font = Font::default();
 	measure_line_font(text, size, &font)
 }
+/// measures a single line of text. results are cached per (font, size, text), see [measure]
 pub fn measure_line_font(text: &str, size: i32, font: &Font) -> (i32, i32) {
+	measure::measure_line_cached(text, size, font)
+}
+/// measures through raylib's `MeasureTextEx` directly, skipping the cache and the glyph advance table
+pub fn measure_line_ffi(text: &str, size: i32, font: &Font) -> (i32, i32) {
 	font.with_font(|font| {
 		let cstring =
 			std::ffi::CString::new(text).expect("CString::new failed while measuring text size:(");
@@ -0,0 +1,233 @@
+//! global font registry, keyed by family, weight and style. \
+//! fonts can be registered already loaded ([register]), or as an asset key that only gets loaded
+//! once something asks for it ([register_asset]). loading is up to whoever calls [take_requested],
+//! see `asset_provider_font::load_requested_fonts`
+
+use std::{
+	collections::BTreeMap,
+	sync::{Mutex, MutexGuard},
+};
+
+use super::Font;
+
+static REGISTRY: Mutex<BTreeMap<FontKey, Entry>> = Mutex::new(BTreeMap::new());
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+/// css-style font weight, 100 to 900
+pub struct FontWeight(pub u16);
+impl FontWeight {
+	pub const THIN: Self = Self(100);
+	pub const LIGHT: Self = Self(300);
+	pub const REGULAR: Self = Self(400);
+	pub const MEDIUM: Self = Self(500);
+	pub const SEMIBOLD: Self = Self(600);
+	pub const BOLD: Self = Self(700);
+	pub const BLACK: Self = Self(900);
+}
+impl Default for FontWeight {
+	fn default() -> Self {
+		Self::REGULAR
+	}
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum FontStyle {
+	#[default]
+	Normal,
+	Italic,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FontKey {
+	pub family: String,
+	pub weight: FontWeight,
+	pub style: FontStyle,
+}
+impl FontKey {
+	pub fn new(family: impl Into<String>) -> Self {
+		Self {
+			family: family.into(),
+			weight: FontWeight::REGULAR,
+			style: FontStyle::Normal,
+		}
+	}
+
+	pub fn weight(self, weight: FontWeight) -> Self {
+		Self { weight, ..self }
+	}
+	pub fn bold(self) -> Self {
+		self.weight(FontWeight::BOLD)
+	}
+	pub fn style(self, style: FontStyle) -> Self {
+		Self { style, ..self }
+	}
+	pub fn italic(self) -> Self {
+		self.style(FontStyle::Italic)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Source {
+	Loaded,
+	/// registered with an asset key, nothing has asked for it yet
+	Asset(String),
+	/// something asked for it, waiting for [take_requested]
+	Requested(String),
+	Loading,
+}
+
+#[derive(Clone, Debug)]
+struct Entry {
+	font: Font,
+	source: Source,
+	/// whether `font` is a real font yet, or at least renders like the default font. \
+	/// placeholders only get pointed at the default font once there's a window, see [get]
+	resolved: bool,
+}
+
+fn lock() -> MutexGuard<'static, BTreeMap<FontKey, Entry>> {
+	match REGISTRY.lock() {
+		Ok(a) => a,
+		Err(err) => err.into_inner(),
+	}
+}
+
+/// gets pointed at the default font by [get] once there's a window, and at the real font once it's loaded. \
+/// [Font::default] can't be used here, before `InitWindow` it'd cache an empty font as the default
+fn placeholder() -> Font {
+	Font::headless(Default::default())
+}
+fn window_ready() -> bool {
+	unsafe { raylib::ffi::IsWindowReady() }
+}
+
+/// registers an already loaded font. \
+/// if `key` was already registered, its font gets [replaced](Font::replace) in place, so text
+/// already using it switches over too
+pub fn register(key: FontKey, font: Font) {
+	let mut lock = lock();
+	match lock.get_mut(&key) {
+		Some(entry) => {
+			entry.font.replace(&font);
+			entry.source = Source::Loaded;
+			entry.resolved = true;
+		}
+		None => {
+			lock.insert(
+				key,
+				Entry {
+					font,
+					source: Source::Loaded,
+					resolved: true,
+				},
+			);
+		}
+	}
+}
+/// registers a font that gets loaded from `asset_key` the first time it's asked for. \
+/// until it's loaded, [get] returns a font that renders like the default font (once there's a window)
+pub fn register_asset(key: FontKey, asset_key: impl Into<String>) {
+	let placeholder = placeholder();
+	let source = Source::Asset(asset_key.into());
+
+	let mut lock = lock();
+	match lock.get_mut(&key) {
+		Some(entry) => entry.source = source,
+		None => {
+			lock.insert(
+				key,
+				Entry {
+					font: placeholder,
+					source,
+					resolved: false,
+				},
+			);
+		}
+	}
+}
+
+/// returns the registered font closest to `key`: \
+/// same family, same style if there's one, and the closest weight (heavier wins ties). \
+/// falls back to [Font::default] if the family isn't registered
+pub fn get(key: &FontKey) -> Font {
+	let font = {
+		let mut lock = lock();
+		match best_match(&lock, key) {
+			Some(found) => {
+				let entry = lock
+					.get_mut(&found)
+					.expect("best_match returned a key that isn't in the registry");
+				if let Source::Asset(asset_key) = &entry.source {
+					entry.source = Source::Requested(asset_key.clone());
+				}
+				// still under the registry lock, so a font registered meanwhile can't get overwritten.
+				// nothing locks the registry while holding DEFAULT_FONT
+				if !entry.resolved && window_ready() {
+					entry.font.replace(&Font::default());
+					entry.resolved = true;
+				}
+				Some(entry.font.clone())
+			}
+			None => None,
+		}
+	};
+	// Font::default locks DEFAULT_FONT, so the registry is unlocked by now
+	font.unwrap_or_default()
+}
+fn best_match(registry: &BTreeMap<FontKey, Entry>, key: &FontKey) -> Option<FontKey> {
+	let family = registry.keys().filter(|k| k.family == key.family);
+	let closest = |keys: &mut dyn Iterator<Item = &FontKey>| {
+		keys.min_by_key(|k| {
+			let distance = (k.weight.0 as i32 - key.weight.0 as i32).abs();
+			(distance, std::cmp::Reverse(k.weight))
+		})
+		.cloned()
+	};
+
+	closest(&mut family.clone().filter(|k| k.style == key.style))
+		.or_else(|| closest(&mut family.clone()))
+}
+
+/// every font that's been asked for but isn't loaded yet, as (key, asset key). \
+/// the caller is expected to load them and [register] them under the same key
+pub fn take_requested() -> Vec<(FontKey, String)> {
+	let mut lock = lock();
+	let mut requested = Vec::new();
+	for (key, entry) in lock.iter_mut() {
+		if let Source::Requested(asset_key) = &entry.source {
+			requested.push((key.clone(), asset_key.clone()));
+			entry.source = Source::Loading;
+		}
+	}
+	requested
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::comp::text::GlyphMetrics;
+
+	#[test]
+	fn test_best_match() {
+		let regular = Font::headless(GlyphMetrics::default());
+		let bold = Font::headless(GlyphMetrics::default());
+		let italic = Font::headless(GlyphMetrics::default());
+
+		let family = "test_best_match";
+		register(FontKey::new(family), regular.clone());
+		register(FontKey::new(family).bold(), bold.clone());
+		register(
+			FontKey::new(family).weight(FontWeight::LIGHT).italic(),
+			italic.clone(),
+		);
+
+		assert_eq!(get(&FontKey::new(family)).id(), regular.id());
+		assert_eq!(get(&FontKey::new(family).bold()).id(), bold.id());
+		assert_eq!(
+			get(&FontKey::new(family).weight(FontWeight::BLACK)).id(),
+			bold.id()
+		);
+		// same style wins over closer weight
+		assert_eq!(get(&FontKey::new(family).bold().italic()).id(), italic.id());
+	}
+}
@@ -0,0 +1,50 @@
+use std::{cell::OnceCell, ffi::CString};
+
+use super::Font;
+
+/// raylib's sdf fragment shader (examples/text/resources/shaders/glsl330/sdf.fs)
+const SDF_FRAGMENT_SHADER: &str = r#"#version 330
+
+in vec2 fragTexCoord;
+in vec4 fragColor;
+
+uniform sampler2D texture0;
+uniform vec4 colDiffuse;
+
+out vec4 finalColor;
+
+void main()
+{
+	// calculate alpha using the signed distance field
+	float distanceFromOutline = texture(texture0, fragTexCoord).a - 0.5;
+	float distanceChangePerFragment = length(vec2(dFdx(distanceFromOutline), dFdy(distanceFromOutline)));
+	float alpha = smoothstep(-distanceChangePerFragment, distanceChangePerFragment, distanceFromOutline);
+
+	finalColor = vec4(fragColor.rgb, fragColor.a*alpha);
+}
+"#;
+
+thread_local! {
+	/// loaded the first time an sdf font gets rendered, never unloaded
+	static SDF_SHADER: OnceCell<raylib::ffi::Shader> = const { OnceCell::new() };
+}
+
+/// runs `f` (which should draw text with `font`) inside the sdf shader if `font` is an sdf font,
+/// or just runs it if it isn't
+pub fn with_font_shader<R>(font: &Font, f: impl FnOnce() -> R) -> R {
+	if !font.is_sdf() {
+		return f();
+	}
+
+	SDF_SHADER.with(|shader| {
+		let shader = shader.get_or_init(|| {
+			let fs =
+				CString::new(SDF_FRAGMENT_SHADER).expect("sdf shader source contains a nul byte");
+			unsafe { raylib::ffi::LoadShaderFromMemory(std::ptr::null(), fs.as_ptr()) }
+		});
+		unsafe { raylib::ffi::BeginShaderMode(*shader) };
+	});
+	let r = f();
+	unsafe { raylib::ffi::EndShaderMode() };
+	r
+}
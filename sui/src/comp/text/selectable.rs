@@ -0,0 +1,221 @@
+use std::{
+	ops::Range,
+	time::{Duration, Instant},
+};
+
+use raylib::{ffi::KeyboardKey, prelude::RaylibDraw};
+
+use super::{measure_line_font, Font, SPACING};
+use crate::{
+	core::{Event, KeyboardEvent, MouseEvent, ReturnEvent},
+	form::{selection::Selection, typable::TypeEvent, FocusCommand, UniqueId},
+	Color, Details, Layable,
+};
+
+/// raylib's default `textLineSpacing`, the gap `DrawTextEx` leaves between lines
+pub const RAYLIB_LINE_SPACING: i32 = 2;
+/// two clicks on the same word closer than this select the word
+pub const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+pub const DEFAULT_HIGHLIGHT: Color = crate::color(60, 110, 200, 160);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// a rendered line of a [TextLayout]. `y` and `h` are in screen pixels, relative to det.y
+pub struct LaidOutLine {
+	/// byte range into [TextLayout::text]
+	pub range: Range<usize>,
+	pub y: i32,
+	pub h: i32,
+}
+
+/// text components that can tell where each of their lines got rendered, so positions can be
+/// mapped to characters. see [Selectable]
+pub trait TextLayout: Layable {
+	fn text(&self) -> &str;
+	fn font(&self) -> &Font;
+	/// unscaled font size
+	fn text_size(&self) -> i32;
+	fn lines(&self, det: Details, scale: f32) -> Vec<LaidOutLine>;
+}
+
+#[derive(Clone, Debug)]
+/// makes [Text](super::Text) or [WrappedText](super::WrappedText) selectable, without making it editable. \
+/// drag to select, double-click to select a word, ctrl+c to copy, ctrl+a to select everything. \
+/// clicking requests focus, keyboard shortcuts only work while focused
+pub struct Selectable<L: TextLayout> {
+	layable: L,
+	uid: UniqueId,
+	selection: Selection,
+	highlight: Color,
+
+	dragging: bool,
+	/// when and where the last click landed, for double clicks
+	last_click: Option<(Instant, usize)>,
+}
+impl<L: TextLayout> Selectable<L> {
+	pub fn new(layable: L) -> Self {
+		Self {
+			layable,
+			uid: UniqueId::new(),
+			selection: Selection::default(),
+			highlight: DEFAULT_HIGHLIGHT,
+			dragging: false,
+			last_click: None,
+		}
+	}
+	pub fn with_highlight(self, highlight: Color) -> Self {
+		Self { highlight, ..self }
+	}
+
+	pub fn uid(&self) -> UniqueId {
+		self.uid
+	}
+	pub fn selection(&self) -> Selection {
+		self.selection
+	}
+	pub fn selected_text(&self) -> &str {
+		self.selection.slice(self.layable.text())
+	}
+	pub fn take(self) -> L {
+		self.layable
+	}
+
+	/// x of the byte index `i` in `line`, in screen pixels relative to det.x
+	fn x_at(&self, line: &LaidOutLine, i: usize, scale: f32) -> i32 {
+		if i <= line.range.start {
+			return 0;
+		}
+		let text = &self.layable.text()[line.range.start..i.min(line.range.end)];
+		let size = (self.layable.text_size() as f32 * scale) as i32;
+		let (w, _) = measure_line_font(text, size, self.layable.font());
+		w + SPACING as i32
+	}
+	/// the byte index closest to (x, y)
+	fn hit(&self, det: Details, scale: f32, x: i32, y: i32) -> usize {
+		let lines = self.layable.lines(det, scale);
+		let line = lines
+			.iter()
+			.find(|line| y < det.y + line.y + line.h)
+			.or(lines.last());
+		let Some(line) = line else {
+			return 0;
+		};
+
+		let text = self.layable.text();
+		let boundaries = text[line.range.clone()]
+			.char_indices()
+			.map(|(i, _)| line.range.start + i)
+			.chain(std::iter::once(line.range.end));
+
+		let mut prev: Option<(usize, i32)> = None;
+		for i in boundaries {
+			let i_x = det.x + self.x_at(line, i, scale);
+			if i_x >= x {
+				// whichever side of the character is closer
+				return match prev {
+					Some((prev_i, prev_x)) if x - prev_x < i_x - x => prev_i,
+					_ => i,
+				};
+			}
+			prev = Some((i, i_x));
+		}
+		line.range.end
+	}
+
+	fn copy(&self) {
+		let Ok(text) = std::ffi::CString::new(self.selected_text()) else {
+			return;
+		};
+		unsafe { raylib::ffi::SetClipboardText(text.as_ptr()) };
+	}
+}
+
+impl<L: TextLayout> Layable for Selectable<L> {
+	fn size(&self) -> (i32, i32) {
+		self.layable.size()
+	}
+	fn render(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		if !self.selection.is_empty() && d.focus() == self.uid {
+			let selected = self.selection.range();
+			for line in self.layable.lines(det, scale) {
+				let start = selected.start.max(line.range.start);
+				let end = selected.end.min(line.range.end);
+				if start >= end {
+					continue;
+				}
+
+				let (x0, x1) = (self.x_at(&line, start, scale), self.x_at(&line, end, scale));
+				d.draw_rectangle(det.x + x0, det.y + line.y, x1 - x0, line.h, self.highlight);
+			}
+		}
+
+		self.layable.render(d, det, scale);
+	}
+
+	fn tick(&mut self) {
+		self.layable.tick();
+	}
+	fn pass_events(
+		&mut self,
+		events: impl Iterator<Item = Event>,
+		det: Details,
+		scale: f32,
+		ret_events: &mut Vec<ReturnEvent>,
+	) {
+		let events = events.collect::<Vec<_>>();
+		let ctrl = events.iter().any(|event| match event {
+			Event::KeyboardEvent(
+				uid,
+				KeyboardEvent::KeyDown(
+					KeyboardKey::KEY_LEFT_CONTROL | KeyboardKey::KEY_RIGHT_CONTROL,
+				),
+			) => *uid == self.uid,
+			_ => false,
+		});
+
+		for event in events {
+			match event {
+				Event::MouseEvent(MouseEvent::MouseClick { x, y }) if det.is_inside(x, y) => {
+					let at = self.hit(det, scale, x, y);
+					let now = Instant::now();
+
+					let text = self.layable.text();
+					let double_click = match self.last_click {
+						Some((when, prev)) => {
+							now - when < DOUBLE_CLICK
+								&& Selection::word(text, prev) == Selection::word(text, at)
+						}
+						None => false,
+					};
+
+					if double_click {
+						self.selection = Selection::word(text, at);
+						self.dragging = false;
+						self.last_click = None;
+					} else {
+						self.selection = Selection::caret(at);
+						self.dragging = true;
+						self.last_click = Some((now, at));
+					}
+					ret_events.push(Event::ret(FocusCommand::Request(self.uid)));
+				}
+				Event::MouseEvent(MouseEvent::MouseHeld { x, y }) if self.dragging => {
+					let at = self.hit(det, scale, x, y);
+					self.selection = self.selection.extend_to(at);
+				}
+				Event::MouseEvent(MouseEvent::MouseRelease { .. }) => self.dragging = false,
+
+				Event::KeyboardEvent(uid, KeyboardEvent::KeyPressed(key))
+					if uid == self.uid && ctrl =>
+				{
+					match key {
+						KeyboardKey::KEY_C => self.copy(),
+						KeyboardKey::KEY_A => self.selection = Selection::all(self.layable.text()),
+						_ => continue,
+					}
+					ret_events.push(ReturnEvent::new(TypeEvent::Handled));
+				}
+				_ => {}
+			}
+		}
+	}
+}
@@ -2,7 +2,10 @@
 
 use std::{cmp::Ordering, ops::Range};
 
-use crate::{comp::text::measure_line, Details};
+use crate::{
+	comp::text::{measure_line, measure_line_font, Font},
+	Details,
+};
 
 pub fn word_is_line_startegy(
 	text: &str,
@@ -179,3 +182,145 @@ pub fn precise_wrapping_strategy(
 		}
 	}
 }
+
+/// part of a run that ended up on a single line, see [run_wrapping_strategy]. \
+/// coordinates are in screen pixels, relative to where the text starts
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Piece {
+	pub run: usize,
+	/// byte range into the run's text
+	pub range: Range<usize>,
+	pub x: i32,
+	pub y: i32,
+	pub w: i32,
+	pub h: i32,
+}
+
+/// splits text into words, each keeping the whitespace after it. line breaks are their own words
+pub(crate) fn word_splitter(text: &str) -> Vec<Range<usize>> {
+	let mut words = Vec::new();
+	let mut from = 0;
+	let mut in_space = false;
+	for (i, c) in text.char_indices() {
+		if c == '\n' {
+			if from < i {
+				words.push(from..i);
+			}
+			words.push(i..i + 1);
+			from = i + 1;
+			in_space = false;
+		} else if c.is_whitespace() {
+			in_space = true;
+		} else if in_space {
+			words.push(from..i);
+			from = i;
+			in_space = false;
+		}
+	}
+	if from < text.len() {
+		words.push(from..text.len());
+	}
+	words
+}
+
+/// word wraps runs of text with their own size and font as if they were one text. \
+/// `runs` are (text, unscaled size, font). returns the pieces and their scaled width and height. \
+/// words of the same run on the same line get merged into a single piece
+pub fn run_wrapping_strategy(
+	runs: &[(&str, i32, &Font)],
+	aw: i32,
+	scale: f32,
+) -> (Vec<Piece>, i32, i32) {
+	let mut pieces: Vec<Piece> = Vec::new();
+	let (mut x, mut y, mut line_h, mut width) = (0, 0, 0, 0);
+
+	for (i, (text, size, font)) in runs.iter().copied().enumerate() {
+		let size = (size as f32 * scale) as i32;
+		for word in word_splitter(text) {
+			let word_text = &text[word.clone()];
+			if word_text == "\n" {
+				y += line_h.max(size);
+				(x, line_h) = (0, 0);
+				continue;
+			}
+
+			// trailing whitespace is allowed to hang over the edge
+			let (fit_w, h) = measure_line_font(word_text.trim_end(), size, font);
+			if x > 0 && x + fit_w > aw {
+				y += line_h;
+				(x, line_h) = (0, 0);
+			}
+
+			match pieces.last_mut() {
+				Some(last) if last.run == i && last.y == y && last.range.end == word.start => {
+					last.range.end = word.end;
+					last.w = measure_line_font(&text[last.range.clone()], size, font).0;
+					x = last.x + last.w;
+				}
+				_ => {
+					let (w, _) = measure_line_font(word_text, size, font);
+					pieces.push(Piece {
+						run: i,
+						range: word,
+						x,
+						y,
+						w,
+						h,
+					});
+					x += w;
+				}
+			}
+			line_h = line_h.max(h);
+			width = width.max(x);
+		}
+	}
+
+	(pieces, width, y + line_h)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_word_splitter() {
+		let text = "hi  there\nfriend ";
+		let words = word_splitter(text)
+			.into_iter()
+			.map(|rng| &text[rng])
+			.collect::<Vec<_>>();
+		assert_eq!(words, ["hi  ", "there", "\n", "friend "]);
+	}
+
+	#[test]
+	fn test_run_wrapping_strategy() {
+		use crate::comp::text::GlyphMetrics;
+		let font = Font::headless(GlyphMetrics::new(10, [('a', 5.0), (' ', 3.0)].into()));
+
+		let runs = [("aa aa ", 10, &font), ("aa", 20, &font)];
+		let (pieces, w, h) = run_wrapping_strategy(&runs, 30, 1.0);
+		// the second word's trailing space hangs over the edge, the bigger run doesn't fit next to it
+		assert_eq!(
+			pieces,
+			[
+				Piece {
+					run: 0,
+					range: 0..6,
+					x: 0,
+					y: 0,
+					w: 31,
+					h: 10
+				},
+				Piece {
+					run: 1,
+					range: 0..2,
+					x: 0,
+					y: 10,
+					w: 21,
+					h: 20
+				},
+			]
+		);
+		assert_eq!((w, h), (31, 30));
+	}
+}
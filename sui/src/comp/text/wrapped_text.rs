@@ -19,6 +19,8 @@ pub struct WrapData {
 	height: i32,
 
 	lines: Vec<Range<usize>>,
+	/// only for [styled](WrappedText::styled) text, where `lines` is empty
+	pieces: Vec<word_wrap::Piece>,
 }
 impl WrapData {
 	fn hash(det: Details, scale: f32) -> u64 {
@@ -34,6 +36,21 @@ impl WrapData {
 			self.force_recalculate(text, size, det, scale);
 		}
 	}
+	fn recalculate_runs(&mut self, runs: &[Run], det: Details, scale: f32) {
+		let hash = WrapData::hash(det, scale);
+		if self.hash == hash {
+			return;
+		}
+		let runs = (runs.iter())
+			.map(|run| (run.text.as_str(), run.size, &run.font))
+			.collect::<Vec<_>>();
+		let (pieces, width, height) = word_wrap::run_wrapping_strategy(&runs, det.aw, scale);
+
+		self.pieces = pieces;
+		self.width = (width as f32 / scale) as i32;
+		self.height = (height as f32 / scale) as i32;
+		self.hash = hash;
+	}
 	fn force_recalculate(&mut self, text: &str, size: i32, det: Details, scale: f32) {
 		{
 			{
@@ -93,16 +110,42 @@ impl WrapData {
 	}
 }
 
+#[derive(Clone, Debug)]
+/// a part of a [WrappedText] with a style of its own, see [WrappedText::styled]
+pub struct Run {
+	pub text: String,
+	pub size: i32,
+	pub font: Font,
+	pub color: Color,
+	pub background: Option<Color>,
+	pub underline: bool,
+}
+impl Run {
+	pub fn new(text: impl Into<String>, size: i32, font: Font, color: Color) -> Self {
+		Self {
+			text: text.into(),
+			size,
+			font,
+			color,
+			background: None,
+			underline: false,
+		}
+	}
+}
+
 /// the sibling of [`Text`](crate::comp::Text), with text wrapping enabled, meaning the text
 /// will always (at least try to) fit into the space provided. \
 ///
-/// wrapping can be achieved by several strategies, all varying by usecase and performance.
+/// wrapping can be achieved by several strategies, all varying by usecase and performance. \
+/// text with more than one font, size or color can be made with [WrappedText::styled]
 #[derive(Debug, Clone)]
 pub struct WrappedText<'a> {
 	pub text: Cow<'a, str>,
 	pub size: i32,
 	font: Font,
 	color: Color,
+	/// empty unless [styled](WrappedText::styled)
+	runs: Vec<Run>,
 
 	wrap_data: Rc<RefCell<WrapData>>,
 }
@@ -129,13 +172,98 @@ impl<'a> WrappedText<'a> {
 			size,
 			font,
 			color,
+			runs: Vec::new(),
 			wrap_data,
 		}
 	}
+	/// text made of runs with their own style, word wrapped as a whole. \
+	/// `text` is every run's text after each other. [Selectable](super::Selectable) measures it
+	/// all with the first run's font and size
+	pub fn styled(runs: Vec<Run>) -> WrappedText<'static> {
+		let text = runs.iter().map(|run| run.text.as_str()).collect::<String>();
+		let (size, font, color) = match runs.first() {
+			Some(run) => (run.size, run.font.clone(), run.color),
+			None => (0, Font::default(), DEFAULT_COLOR),
+		};
+		let text = WrappedText {
+			runs,
+			..WrappedText::new_explicit(text, size, font, color)
+		};
+		// so size is something sensible before the first render
+		text.recalculate(Details::new(0, 0, i32::MAX, i32::MAX), 1.0);
+		text
+	}
+
+	pub fn with_font(self, font: Font) -> Self {
+		Self { font, ..self }
+	}
+	/// uses the registered font closest to `key`, see [super::registry::get]
+	pub fn with_family(self, key: &super::FontKey) -> Self {
+		self.with_font(super::registry::get(key))
+	}
+
+	pub fn runs(&self) -> &[Run] {
+		&self.runs
+	}
+	/// the index of the [run](WrappedText::styled) at (x, y), if there's one
+	pub fn run_at(&self, det: Details, scale: f32, x: i32, y: i32) -> Option<usize> {
+		self.recalculate(det, scale);
+		let wrap_data = self.wrap_data.borrow();
+		let piece = wrap_data.pieces.iter().find(|piece| {
+			Details::new(det.x + piece.x, det.y + piece.y, piece.w, piece.h).is_inside(x, y)
+		})?;
+		Some(piece.run)
+	}
 
 	fn recalculate(&self, det: Details, scale: f32) {
 		let mut wrap_data = self.wrap_data.borrow_mut();
-		wrap_data.recalculate(&self.text, self.size, det, scale)
+		if self.runs.is_empty() {
+			wrap_data.recalculate(&self.text, self.size, det, scale)
+		} else {
+			wrap_data.recalculate_runs(&self.runs, det, scale)
+		}
+	}
+	fn render_runs(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		for piece in self.wrap_data.borrow().pieces.iter() {
+			let run = &self.runs[piece.run];
+			let (x, y) = (det.x + piece.x, det.y + piece.y);
+
+			if let Some(background) = run.background {
+				d.draw_rectangle(x, y, piece.w, piece.h, background);
+			}
+			let text = &run.text[piece.range.clone()];
+			let text = crate::Text::new_explicit(text, run.size, run.font.clone(), run.color);
+			text.render(d, Details::new(x, y, piece.w, piece.h), scale);
+			if run.underline {
+				d.draw_line(x, y + piece.h, x + piece.w, y + piece.h, run.color);
+			}
+		}
+	}
+	/// the pieces on the same line put together, with ranges into `text` instead of the runs' text
+	fn styled_lines(&self) -> Vec<super::selectable::LaidOutLine> {
+		let mut starts = Vec::with_capacity(self.runs.len());
+		let mut start = 0;
+		for run in self.runs.iter() {
+			starts.push(start);
+			start += run.text.len();
+		}
+
+		let mut lines: Vec<super::selectable::LaidOutLine> = Vec::new();
+		for piece in self.wrap_data.borrow().pieces.iter() {
+			let range = starts[piece.run] + piece.range.start..starts[piece.run] + piece.range.end;
+			match lines.last_mut() {
+				Some(line) if line.y == piece.y => {
+					line.range.end = range.end;
+					line.h = line.h.max(piece.h);
+				}
+				_ => lines.push(super::selectable::LaidOutLine {
+					range,
+					y: piece.y,
+					h: piece.h,
+				}),
+			}
+		}
+		lines
 	}
 }
 
@@ -147,25 +275,63 @@ impl<'a> Layable for WrappedText<'a> {
 
 	fn render(&self, d: &mut crate::Handle, det: crate::Details, scale: f32) {
 		self.recalculate(det, scale);
+		if !self.runs.is_empty() {
+			return self.render_runs(d, det, scale);
+		}
 
-		self.font.with_font(|font| {
-			let mut y = det.y;
-			for line in self.wrap_data.borrow().lines.iter().cloned() {
-				let text = &self.text[line];
-				d.draw_text_ex(
-					&font,
-					text,
-					Vector2::new(det.x as f32, y as f32),
-					self.size as f32 * scale,
-					SPACING,
-					self.color,
-				);
-				y += self.size;
-			}
+		super::sdf::with_font_shader(&self.font, || {
+			self.font.with_font(|font| {
+				let mut y = det.y;
+				for line in self.wrap_data.borrow().lines.iter().cloned() {
+					let text = &self.text[line];
+					d.draw_text_ex(
+						&font,
+						text,
+						Vector2::new(det.x as f32, y as f32),
+						self.size as f32 * scale,
+						SPACING,
+						self.color,
+					);
+					y += self.size;
+				}
+			})
 		});
 	}
 }
 
+impl<'a> super::TextLayout for WrappedText<'a> {
+	fn text(&self) -> &str {
+		&self.text
+	}
+	fn font(&self) -> &Font {
+		&self.font
+	}
+	fn text_size(&self) -> i32 {
+		self.size
+	}
+	fn lines(&self, det: Details, scale: f32) -> Vec<super::selectable::LaidOutLine> {
+		self.recalculate(det, scale);
+		if !self.runs.is_empty() {
+			return self.styled_lines();
+		}
+
+		let h = (self.size as f32 * scale) as i32;
+		self.wrap_data
+			.borrow()
+			.lines
+			.iter()
+			.cloned()
+			.enumerate()
+			// same spacing as render
+			.map(|(i, range)| super::selectable::LaidOutLine {
+				range,
+				y: i as i32 * self.size,
+				h,
+			})
+			.collect()
+	}
+}
+
 #[derive(Debug, Clone)]
 pub struct CenteredWrappedText<'a> {
 	pub text: Cow<'a, str>,
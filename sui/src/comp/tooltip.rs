@@ -0,0 +1,296 @@
+use std::time::{Duration, Instant};
+
+use raylib::prelude::RaylibDraw;
+
+use crate::{
+	core::{Event, MouseEvent, ReturnEvent},
+	form::{control, UniqueId},
+	Color, Details, Layable,
+};
+
+/// how long the pointer has to rest before the tooltip shows up, by default
+pub const TOOLTIP_DELAY: Duration = Duration::from_millis(500);
+/// space between the tooltip's edge and its content, unscaled
+const PADDING: i32 = 4;
+/// how far from the cursor (or the component) the tooltip goes, unscaled
+const GAP: i32 = 4;
+/// how far right and down from the cursor the tooltip goes, so the cursor doesn't cover it
+const CURSOR_OFFSET: (i32, i32) = (12, 18);
+const BACKGROUND: Color = crate::color(25, 25, 25, 240);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub enum TooltipPlacement {
+	/// next to where the pointer came to rest
+	#[default]
+	Cursor,
+	/// under the component, left-aligned with it
+	Anchored,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Hover {
+	/// where the pointer last moved to, and when
+	at: (i32, i32),
+	since: Instant,
+	shown: bool,
+}
+
+#[derive(Clone, Debug)]
+/// shows `content` in a [layer](crate::layer), so on top of everything and outside of the parent's
+/// [Details], once the pointer's been resting on `layable` for a while (see [Tooltip::with_delay]). \
+/// it stays up until the pointer leaves or clicks, and gets flipped to the other side of the cursor
+/// (or the component) if it'd go off screen
+pub struct Tooltip<L: Layable, T: Layable + Clone + 'static> {
+	uid: UniqueId,
+	layable: L,
+	content: T,
+	delay: Duration,
+	placement: TooltipPlacement,
+	hover: Option<Hover>,
+	/// clicked while hovering, stays hidden until the pointer leaves
+	dismissed: bool,
+}
+impl<L: Layable, T: Layable + Clone + 'static> Tooltip<L, T> {
+	pub fn new(layable: L, content: T) -> Self {
+		Self {
+			uid: UniqueId::new(),
+			layable,
+			content,
+			delay: TOOLTIP_DELAY,
+			placement: TooltipPlacement::default(),
+			hover: None,
+			dismissed: false,
+		}
+	}
+	pub fn with_delay(self, delay: Duration) -> Self {
+		Self { delay, ..self }
+	}
+	pub fn with_placement(self, placement: TooltipPlacement) -> Self {
+		Self { placement, ..self }
+	}
+	/// shorthand for [TooltipPlacement::Anchored]
+	pub fn anchored(self) -> Self {
+		self.with_placement(TooltipPlacement::Anchored)
+	}
+
+	pub fn take(self) -> L {
+		self.layable
+	}
+	pub fn is_shown(&self) -> bool {
+		self.hover.is_some_and(|hover| hover.shown)
+	}
+
+	/// where the tooltip goes, in window coords
+	fn tooltip_det(&self, at: (i32, i32), det: Details, scale: f32) -> Details {
+		let s = |v: i32| (v as f32 * scale) as i32;
+		let (content_w, content_h) = self.content.size();
+		let (w, h) = (s(content_w + PADDING * 2), s(content_h + PADDING * 2));
+		let (screen_w, screen_h) = unsafe {
+			(
+				raylib::ffi::GetScreenWidth(),
+				raylib::ffi::GetScreenHeight(),
+			)
+		};
+
+		let (x, y) = match self.placement {
+			TooltipPlacement::Cursor => {
+				let (x, y) = at;
+				(
+					flip(x + s(CURSOR_OFFSET.0), x - s(GAP), w, screen_w),
+					flip(y + s(CURSOR_OFFSET.1), y - s(GAP), h, screen_h),
+				)
+			}
+			TooltipPlacement::Anchored => (
+				flip(det.x, det.x + det.aw, w, screen_w),
+				flip(det.y + det.ah + s(GAP), det.y - s(GAP), h, screen_h),
+			),
+		};
+		Details { x, y, aw: w, ah: h }
+	}
+}
+
+/// where something `len` long starts along one axis of the screen. \
+/// it'd rather start at `after`, but if that goes off the end it ends at `before` instead,
+/// if that fits. if neither does it just gets pushed back on screen
+pub fn flip(after: i32, before: i32, len: i32, screen: i32) -> i32 {
+	if after + len <= screen {
+		after
+	} else if before - len >= 0 {
+		before - len
+	} else {
+		(screen - len).max(0)
+	}
+}
+
+impl<L: Layable, T: Layable + Clone + 'static> Layable for Tooltip<L, T> {
+	fn size(&self) -> (i32, i32) {
+		self.layable.size()
+	}
+	fn render(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		self.layable.render(d, det, scale);
+
+		let Some(hover) = self.hover.filter(|hover| hover.shown) else {
+			return;
+		};
+		// the layer gets rendered later, so it needs its own copy
+		let tooltip = self.tooltip_det(hover.at, det, scale);
+		let content = self.content.clone();
+		let padding = (PADDING as f32 * scale) as i32;
+		d.layer(self.uid, tooltip, move |d| {
+			let t = tooltip;
+			d.draw_rectangle(t.x, t.y, t.aw, t.ah, BACKGROUND);
+			d.draw_rectangle_lines(t.x, t.y, t.aw, t.ah, control::INACTIVE);
+			let content_det = Details {
+				x: t.x + padding,
+				y: t.y + padding,
+				aw: t.aw - padding * 2,
+				ah: t.ah - padding * 2,
+			};
+			content.render(d, content_det, scale);
+		});
+	}
+
+	fn tick(&mut self) {
+		self.layable.tick();
+	}
+	fn pass_events(
+		&mut self,
+		events: impl Iterator<Item = Event>,
+		det: Details,
+		scale: f32,
+		ret_events: &mut Vec<ReturnEvent>,
+	) {
+		let events = events.collect::<Vec<_>>();
+
+		// MouseMove comes every frame the pointer's over us, so no MouseMove means it left
+		// (or it's over the tooltip itself, which counts as leaving too)
+		let moved_to = events.iter().rev().find_map(|event| match event {
+			Event::MouseEvent(MouseEvent::MouseMove { x, y }) if det.is_inside(*x, *y) => {
+				Some((*x, *y))
+			}
+			_ => None,
+		});
+		let clicked = (events.iter())
+			.any(|event| matches!(event, Event::MouseEvent(MouseEvent::MouseClick { .. })));
+
+		self.hover = match (moved_to, self.hover) {
+			(None, _) => {
+				self.dismissed = false;
+				None
+			}
+			// once it's up it stays where it is
+			(Some(_), Some(hover)) if hover.shown => Some(hover),
+			(Some(at), Some(hover)) if at == hover.at => Some(Hover {
+				shown: hover.since.elapsed() >= self.delay,
+				..hover
+			}),
+			(Some(at), _) => Some(Hover {
+				at,
+				since: Instant::now(),
+				shown: false,
+			}),
+		};
+		if clicked {
+			self.dismissed = true;
+		}
+		if self.dismissed {
+			self.hover = self.hover.map(|hover| Hover {
+				since: Instant::now(),
+				shown: false,
+				..hover
+			});
+		}
+
+		self.layable
+			.pass_events(events.into_iter(), det, scale, ret_events);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::comp::Space;
+
+	const DET: Details = Details {
+		x: 0,
+		y: 0,
+		aw: 100,
+		ah: 100,
+	};
+
+	fn tooltip(delay: Duration) -> Tooltip<Space, Space> {
+		Tooltip::new(Space::new(100, 100), Space::new(10, 10)).with_delay(delay)
+	}
+	/// one frame: the pointer's at `at` (or not over anything if none), and clicks if `click`
+	fn frame(tooltip: &mut Tooltip<Space, Space>, at: Option<(i32, i32)>, click: bool) {
+		let events = at.into_iter().flat_map(|(x, y)| {
+			let click = click.then_some(Event::MouseEvent(MouseEvent::MouseClick { x, y }));
+			[Event::MouseEvent(MouseEvent::MouseMove { x, y })]
+				.into_iter()
+				.chain(click)
+		});
+		tooltip.pass_events(events, DET, 1.0, &mut Vec::new());
+	}
+
+	#[test]
+	fn test_delay() {
+		let delay = Duration::from_millis(50);
+		let mut tooltip = tooltip(delay);
+
+		frame(&mut tooltip, Some((5, 5)), false);
+		frame(&mut tooltip, Some((5, 5)), false);
+		assert!(!tooltip.is_shown());
+
+		// moving starts the wait over
+		std::thread::sleep(delay);
+		frame(&mut tooltip, Some((6, 5)), false);
+		assert!(!tooltip.is_shown());
+
+		std::thread::sleep(delay);
+		frame(&mut tooltip, Some((6, 5)), false);
+		assert!(tooltip.is_shown());
+		// once it's up, moving around the component doesn't hide it
+		frame(&mut tooltip, Some((50, 50)), false);
+		assert!(tooltip.is_shown());
+
+		// no MouseMove means the pointer left
+		frame(&mut tooltip, None, false);
+		assert!(!tooltip.is_shown());
+		frame(&mut tooltip, Some((200, 200)), false);
+		assert!(!tooltip.is_shown());
+	}
+
+	#[test]
+	fn test_dismiss() {
+		let mut tooltip = tooltip(Duration::ZERO);
+
+		frame(&mut tooltip, Some((5, 5)), false);
+		frame(&mut tooltip, Some((5, 5)), false);
+		assert!(tooltip.is_shown());
+
+		frame(&mut tooltip, Some((5, 5)), true);
+		assert!(!tooltip.is_shown());
+		// resting doesn't bring it back
+		frame(&mut tooltip, Some((5, 5)), false);
+		frame(&mut tooltip, Some((5, 5)), false);
+		assert!(!tooltip.is_shown());
+
+		// leaving does
+		frame(&mut tooltip, None, false);
+		frame(&mut tooltip, Some((5, 5)), false);
+		frame(&mut tooltip, Some((5, 5)), false);
+		assert!(tooltip.is_shown());
+	}
+
+	#[test]
+	fn test_flip() {
+		// fits where it wants to be
+		assert_eq!(flip(10, 5, 20, 100), 10);
+		// would go off the end, goes before instead
+		assert_eq!(flip(90, 85, 20, 100), 65);
+		// fits neither way, pushed back on
+		assert_eq!(flip(90, 10, 20, 100), 80);
+		// bigger than the screen
+		assert_eq!(flip(0, 0, 200, 100), 0);
+	}
+}
@@ -0,0 +1,645 @@
+use std::{
+	any::Any,
+	collections::{HashMap, HashSet},
+	hash::Hash,
+	rc::Rc,
+};
+
+use raylib::{ffi::KeyboardKey, math::Vector2, prelude::RaylibDraw};
+
+use crate::{
+	core::{Event, MouseEvent, ReturnEvent, Store},
+	form::{control, FocusCommand, UniqueId},
+	Color, Details, Layable,
+};
+
+const PADDING: i32 = 2;
+/// size of the expand/collapse arrow, unscaled
+const ARROW_SIZE: i32 = 12;
+const SELECTED_BACKGROUND: Color = crate::color(60, 80, 110, 255);
+
+/// the children of lazy nodes, by the node's key. each one is a `Vec<TreeNode<K, L>>`
+type Loaded<K> = HashMap<K, Rc<dyn Any>>;
+
+#[derive(Clone, Debug)]
+/// everything a [Tree] remembers, kept in a [Store] so it survives the tree being rebuilt. \
+/// that includes what [lazy](TreeNode::lazy) nodes loaded, so rebuilding the tree doesn't load them again
+pub struct TreeState<K: Eq + Hash> {
+	uid: UniqueId,
+	pub expanded: HashSet<K>,
+	pub selected: Option<K>,
+	loaded: Loaded<K>,
+}
+impl<K: Eq + Hash> Default for TreeState<K> {
+	fn default() -> Self {
+		Self {
+			uid: UniqueId::new(),
+			expanded: HashSet::new(),
+			selected: None,
+			loaded: HashMap::new(),
+		}
+	}
+}
+/// the loaded children aren't compared
+impl<K: Eq + Hash> PartialEq for TreeState<K> {
+	fn eq(&self, other: &Self) -> bool {
+		self.uid == other.uid && self.expanded == other.expanded && self.selected == other.selected
+	}
+}
+impl<K: Eq + Hash> Eq for TreeState<K> {}
+impl<K: Eq + Hash> TreeState<K> {
+	pub fn new() -> Self {
+		Self::default()
+	}
+	/// the uid keyboard events have to be sent to
+	pub fn uid(&self) -> UniqueId {
+		self.uid
+	}
+	pub fn is_expanded(&self, key: &K) -> bool {
+		self.expanded.contains(key)
+	}
+	/// forgets what the lazy node `key` loaded, so it gets loaded again the next time it's built expanded
+	pub fn unload(&mut self, key: &K) {
+		self.loaded.remove(key);
+	}
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+/// returned by a [Tree] when a node gets selected
+pub struct NodeSelected<K> {
+	pub uid: UniqueId,
+	pub key: K,
+}
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+/// returned by a [Tree] when a node gets expanded or collapsed
+pub struct NodeExpanded<K> {
+	pub uid: UniqueId,
+	pub key: K,
+	pub expanded: bool,
+}
+
+#[derive(Clone)]
+enum Children<K, L: Layable> {
+	Loaded(Vec<TreeNode<K, L>>),
+	/// called the first time the node gets expanded, unless the children are in the [Loaded] already
+	Lazy(Rc<dyn Fn(&mut Loaded<K>) -> Vec<TreeNode<K, L>>>),
+}
+impl<K: std::fmt::Debug, L: Layable + std::fmt::Debug> std::fmt::Debug for Children<K, L> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Loaded(children) => f.debug_tuple("Loaded").field(children).finish(),
+			Self::Lazy(_) => f.write_str("Lazy"),
+		}
+	}
+}
+
+#[derive(Clone, Debug)]
+/// a node of a [Tree]. `key` has to be unique in the whole tree, it's what the [TreeState] remembers
+/// the node by
+pub struct TreeNode<K, L: Layable> {
+	key: K,
+	label: L,
+	children: Children<K, L>,
+}
+impl<K, L: Layable> TreeNode<K, L> {
+	pub fn new(key: K, label: L) -> Self {
+		Self {
+			key,
+			label,
+			children: Children::Loaded(Vec::new()),
+		}
+	}
+	/// a node whose children are only built once it's expanded. \
+	/// shows an arrow even if `load` ends up not returning anything. \
+	/// what `load` returns is kept in the [TreeState], so it only runs again after [TreeState::unload]
+	pub fn lazy(key: K, label: L, load: impl Fn() -> Vec<TreeNode<K, L>> + 'static) -> Self
+	where
+		K: Clone + Eq + Hash + 'static,
+		L: Clone + 'static,
+	{
+		let cache_key = key.clone();
+		let load = move |loaded: &mut Loaded<K>| {
+			let cached = (loaded.get(&cache_key))
+				.and_then(|children| children.downcast_ref::<Vec<TreeNode<K, L>>>());
+			if let Some(children) = cached {
+				return children.clone();
+			}
+			let children = load();
+			loaded.insert(cache_key.clone(), Rc::new(children.clone()));
+			children
+		};
+		Self {
+			key,
+			label,
+			children: Children::Lazy(Rc::new(load)),
+		}
+	}
+	pub fn child(mut self, child: TreeNode<K, L>) -> Self {
+		self.children_mut().push(child);
+		self
+	}
+	pub fn with_children(mut self, children: impl IntoIterator<Item = TreeNode<K, L>>) -> Self {
+		self.children_mut().extend(children);
+		self
+	}
+
+	pub fn key(&self) -> &K {
+		&self.key
+	}
+
+	fn has_children(&self) -> bool {
+		match &self.children {
+			Children::Loaded(children) => !children.is_empty(),
+			Children::Lazy(_) => true,
+		}
+	}
+	/// loads the children if they haven't been yet, out of `loaded` if they're there
+	fn load(&mut self, loaded: &mut Loaded<K>) -> &mut Vec<TreeNode<K, L>> {
+		if let Children::Lazy(load) = &self.children {
+			self.children = Children::Loaded(load(loaded));
+		}
+		match &mut self.children {
+			Children::Loaded(children) => children,
+			Children::Lazy(_) => unreachable!(),
+		}
+	}
+	/// the children, loading them without a [TreeState] if they haven't been yet
+	fn children_mut(&mut self) -> &mut Vec<TreeNode<K, L>> {
+		self.load(&mut HashMap::new())
+	}
+	/// the children, if they've been loaded
+	fn children(&self) -> &[TreeNode<K, L>] {
+		match &self.children {
+			Children::Loaded(children) => children,
+			Children::Lazy(_) => &[],
+		}
+	}
+}
+
+/// a node that's on screen
+#[derive(Clone, Debug)]
+struct Row {
+	/// child indices from the roots down to the node
+	path: Vec<usize>,
+	depth: usize,
+	/// the row of the parent node
+	parent: Option<usize>,
+}
+
+#[derive(Clone, Debug)]
+/// a tree of [TreeNode]s, with an arrow to expand or collapse every node that has children. \
+/// clicking a node selects it, clicking its arrow expands or collapses it. while focused, up/down move the
+/// selection, right expands the selected node (or goes to its first child), left collapses it (or goes to its
+/// parent) and enter or space toggle it. \
+/// which nodes are expanded and which one's selected live in a `Store<TreeState<K>>`, so the tree can be
+/// rebuilt every frame. returns [NodeSelected] and [NodeExpanded]. \
+/// events are passed on to the labels too, so they can be buttons and such
+pub struct Tree<K: Clone + Eq + Hash + 'static, L: Layable> {
+	state: Store<TreeState<K>>,
+	roots: Vec<TreeNode<K, L>>,
+	/// unscaled
+	indent: i32,
+}
+impl<K: Clone + Eq + Hash + 'static, L: Layable> Tree<K, L> {
+	pub fn new(state: Store<TreeState<K>>, roots: Vec<TreeNode<K, L>>) -> Self {
+		let mut tree = Self {
+			state,
+			roots,
+			indent: ARROW_SIZE + PADDING * 2,
+		};
+		tree.load_expanded();
+		tree
+	}
+	/// how far each level is indented, unscaled
+	pub fn with_indent(self, indent: i32) -> Self {
+		Self { indent, ..self }
+	}
+
+	pub fn state(&self) -> &Store<TreeState<K>> {
+		&self.state
+	}
+
+	/// loads the children of every expanded node
+	fn load_expanded(&mut self) {
+		fn load<K: Eq + Hash, L: Layable>(
+			nodes: &mut [TreeNode<K, L>],
+			expanded: &HashSet<K>,
+			loaded: &mut Loaded<K>,
+		) {
+			for node in nodes {
+				if expanded.contains(&node.key) {
+					load(node.load(loaded), expanded, loaded);
+				}
+			}
+		}
+		// taken out of the store, so loaders can look at it
+		let (expanded, mut loaded) = self
+			.state
+			.with_mut_borrow(|state| (state.expanded.clone(), std::mem::take(&mut state.loaded)));
+		load(&mut self.roots, &expanded, &mut loaded);
+		self.state.with_mut_borrow(|state| state.loaded = loaded);
+	}
+
+	/// every node that's on screen, in order
+	fn rows(&self) -> Vec<Row> {
+		fn walk<K: Eq + Hash, L: Layable>(
+			nodes: &[TreeNode<K, L>],
+			expanded: &HashSet<K>,
+			path: &mut Vec<usize>,
+			parent: Option<usize>,
+			rows: &mut Vec<Row>,
+		) {
+			for (i, node) in nodes.iter().enumerate() {
+				path.push(i);
+				rows.push(Row {
+					path: path.clone(),
+					depth: path.len() - 1,
+					parent,
+				});
+				if expanded.contains(&node.key) {
+					let row = rows.len() - 1;
+					walk(node.children(), expanded, path, Some(row), rows);
+				}
+				path.pop();
+			}
+		}
+		let mut rows = Vec::new();
+		self.state.with_borrow(|state| {
+			walk(
+				&self.roots,
+				&state.expanded,
+				&mut Vec::new(),
+				None,
+				&mut rows,
+			)
+		});
+		rows
+	}
+	fn node(&self, path: &[usize]) -> &TreeNode<K, L> {
+		let mut node = &self.roots[path[0]];
+		for i in &path[1..] {
+			node = &node.children()[*i];
+		}
+		node
+	}
+	fn node_mut(&mut self, path: &[usize]) -> &mut TreeNode<K, L> {
+		let mut node = &mut self.roots[path[0]];
+		for i in &path[1..] {
+			node = &mut node.children_mut()[*i];
+		}
+		node
+	}
+
+	/// unscaled
+	fn row_size(&self, row: &Row) -> (i32, i32) {
+		let (label_w, label_h) = self.node(&row.path).label.size();
+		(
+			row.depth as i32 * self.indent + ARROW_SIZE + PADDING * 2 + label_w,
+			label_h.max(ARROW_SIZE) + PADDING * 2,
+		)
+	}
+	/// every row's det, scaled
+	fn layout(&self, rows: &[Row], det: Details, scale: f32) -> Vec<Details> {
+		let mut y = det.y;
+		rows.iter()
+			.map(|row| {
+				let h = (self.row_size(row).1 as f32 * scale) as i32;
+				y += h;
+				Details {
+					x: det.x,
+					y: y - h,
+					aw: det.aw,
+					ah: h,
+				}
+			})
+			.collect()
+	}
+	/// where the row's arrow and label go
+	fn split_row(&self, row: &Row, row_det: Details, scale: f32) -> (Details, Details) {
+		let s = |v: i32| (v as f32 * scale) as i32;
+		let indent = s(row.depth as i32 * self.indent);
+		let arrow = Details {
+			x: row_det.x + indent + s(PADDING),
+			y: row_det.y + (row_det.ah - s(ARROW_SIZE)) / 2,
+			aw: s(ARROW_SIZE),
+			ah: s(ARROW_SIZE),
+		};
+		let label_x = arrow.x + arrow.aw + s(PADDING);
+		let label = Details {
+			x: label_x,
+			y: row_det.y + s(PADDING),
+			aw: (row_det.aw - (label_x - row_det.x)).max(0),
+			ah: row_det.ah - s(PADDING) * 2,
+		};
+		(arrow, label)
+	}
+
+	fn select(&mut self, key: K, ret_events: &mut Vec<ReturnEvent>) {
+		let changed = self.state.with_mut_borrow(|state| {
+			let changed = state.selected.as_ref() != Some(&key);
+			state.selected = Some(key.clone());
+			changed
+		});
+		if changed {
+			ret_events.push(Event::ret(NodeSelected {
+				uid: self.state.with_borrow(|state| state.uid),
+				key,
+			}));
+		}
+	}
+	fn set_expanded(&mut self, path: &[usize], expanded: bool, ret_events: &mut Vec<ReturnEvent>) {
+		let node = self.node_mut(path);
+		if !node.has_children() {
+			return;
+		}
+		let key = node.key.clone();
+		let (uid, changed) = self.state.with_mut_borrow(|state| {
+			let changed = if expanded {
+				state.expanded.insert(key.clone())
+			} else {
+				state.expanded.remove(&key)
+			};
+			(state.uid, changed)
+		});
+		if changed {
+			ret_events.push(Event::ret(NodeExpanded { uid, key, expanded }));
+		}
+		if expanded {
+			// that's when lazy children get loaded
+			self.load_expanded();
+		}
+	}
+}
+
+impl<K: Clone + Eq + Hash + 'static, L: Layable> Layable for Tree<K, L> {
+	fn size(&self) -> (i32, i32) {
+		(self.rows().iter())
+			.map(|row| self.row_size(row))
+			.fold((0, 0), |(w, h), (row_w, row_h)| (w.max(row_w), h + row_h))
+	}
+	fn render(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		let rows = self.rows();
+		let (uid, selected) = self
+			.state
+			.with_borrow(|state| (state.uid, state.selected.clone()));
+		let focused = d.focus() == uid;
+
+		for (row, row_det) in rows.iter().zip(self.layout(&rows, det, scale)) {
+			let node = self.node(&row.path);
+			if selected.as_ref() == Some(&node.key) {
+				d.draw_rectangle(
+					row_det.x,
+					row_det.y,
+					row_det.aw,
+					row_det.ah,
+					SELECTED_BACKGROUND,
+				);
+				if focused {
+					d.draw_rectangle_lines(
+						row_det.x,
+						row_det.y,
+						row_det.aw,
+						row_det.ah,
+						control::ACCENT,
+					);
+				}
+			}
+
+			let (arrow, label) = self.split_row(row, row_det, scale);
+			if node.has_children() {
+				let expanded = self
+					.state
+					.with_borrow(|state| state.expanded.contains(&node.key));
+				draw_arrow(d, arrow, expanded);
+			}
+			node.label.render(d, label, scale);
+		}
+	}
+
+	fn tick(&mut self) {
+		fn tick<K, L: Layable>(nodes: &mut [TreeNode<K, L>]) {
+			for node in nodes {
+				node.label.tick();
+				if let Children::Loaded(children) = &mut node.children {
+					tick(children);
+				}
+			}
+		}
+		tick(&mut self.roots);
+	}
+	fn pass_events(
+		&mut self,
+		events: impl Iterator<Item = Event>,
+		det: Details,
+		scale: f32,
+		ret_events: &mut Vec<ReturnEvent>,
+	) {
+		let uid = self.state.with_borrow(|state| state.uid);
+		for event in events {
+			// the layout changes as nodes get expanded and collapsed
+			let rows = self.rows();
+			let dets = self.layout(&rows, det, scale);
+			let selected = self.state.with_borrow(|state| {
+				let selected = state.selected.as_ref()?;
+				rows.iter()
+					.position(|row| self.node(&row.path).key == *selected)
+			});
+
+			for (row, row_det) in rows.iter().zip(dets.iter()) {
+				let (_, label) = self.split_row(row, *row_det, scale);
+				if let Event::MouseEvent(m_event) = event {
+					if !label.is_inside_tuple(m_event.at()) {
+						continue;
+					}
+				}
+				(self.node_mut(&row.path).label).pass_events(
+					std::iter::once(event),
+					label,
+					scale,
+					ret_events,
+				);
+			}
+
+			match event {
+				Event::MouseEvent(MouseEvent::MouseClick { x, y }) => {
+					let Some(i) = dets.iter().position(|row_det| row_det.is_inside(x, y)) else {
+						continue;
+					};
+					ret_events.push(Event::ret(FocusCommand::Request(uid)));
+					let (arrow, _) = self.split_row(&rows[i], dets[i], scale);
+					let node = self.node(&rows[i].path);
+					if node.has_children()
+						&& x < arrow.x + arrow.aw + (PADDING as f32 * scale) as i32
+					{
+						let expanded = self
+							.state
+							.with_borrow(|state| state.expanded.contains(&node.key));
+						self.set_expanded(&rows[i].path, !expanded, ret_events);
+					} else {
+						self.select(node.key.clone(), ret_events);
+					}
+				}
+				event => {
+					let Some(key) = control::pressed_key(&event, uid) else {
+						continue;
+					};
+					let Some(i) = selected else {
+						if let (KeyboardKey::KEY_UP | KeyboardKey::KEY_DOWN, Some(first)) =
+							(key, rows.first())
+						{
+							self.select(self.node(&first.path).key.clone(), ret_events);
+						}
+						continue;
+					};
+					let node = self.node(&rows[i].path);
+					let expanded = self
+						.state
+						.with_borrow(|state| state.expanded.contains(&node.key));
+					match key {
+						KeyboardKey::KEY_UP if i > 0 => {
+							self.select(self.node(&rows[i - 1].path).key.clone(), ret_events)
+						}
+						KeyboardKey::KEY_DOWN if i + 1 < rows.len() => {
+							self.select(self.node(&rows[i + 1].path).key.clone(), ret_events)
+						}
+						KeyboardKey::KEY_RIGHT if node.has_children() && !expanded => {
+							self.set_expanded(&rows[i].path, true, ret_events)
+						}
+						KeyboardKey::KEY_RIGHT if expanded => {
+							if let Some(child) = node.children().first() {
+								self.select(child.key.clone(), ret_events);
+							}
+						}
+						KeyboardKey::KEY_LEFT if expanded => {
+							self.set_expanded(&rows[i].path, false, ret_events)
+						}
+						KeyboardKey::KEY_LEFT => {
+							if let Some(parent) = rows[i].parent {
+								self.select(self.node(&rows[parent].path).key.clone(), ret_events);
+							}
+						}
+						_ if control::is_activate_key(&event, uid) => {
+							self.set_expanded(&rows[i].path, !expanded, ret_events)
+						}
+						_ => {}
+					}
+				}
+			}
+		}
+	}
+}
+
+/// pointing right if collapsed, down if expanded
+pub(crate) fn draw_arrow(d: &mut crate::Handle, det: Details, expanded: bool) {
+	let (x, y, w, h) = (det.x as f32, det.y as f32, det.aw as f32, det.ah as f32);
+	let quarter = w / 4.0;
+	// raylib wants the points counter-clockwise
+	if expanded {
+		d.draw_triangle(
+			Vector2::new(x + w / 2.0, y + h - quarter),
+			Vector2::new(x + w - quarter / 2.0, y + quarter),
+			Vector2::new(x + quarter / 2.0, y + quarter),
+			control::OUTLINE,
+		);
+	} else {
+		d.draw_triangle(
+			Vector2::new(x + quarter, y + quarter / 2.0),
+			Vector2::new(x + quarter, y + h - quarter / 2.0),
+			Vector2::new(x + w - quarter, y + h / 2.0),
+			control::OUTLINE,
+		);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::cell::Cell;
+
+	use super::*;
+	use crate::{comp::Space, core::KeyboardEvent};
+
+	fn node(key: &'static str) -> TreeNode<&'static str, Space> {
+		TreeNode::new(key, Space::new(10, 10))
+	}
+	fn press(tree: &mut Tree<&'static str, Space>, key: KeyboardKey) {
+		let uid = tree.state.with_borrow(|state| state.uid);
+		let event = Event::KeyboardEvent(uid, KeyboardEvent::KeyPressed(key));
+		let det = Details {
+			x: 0,
+			y: 0,
+			aw: 100,
+			ah: 100,
+		};
+		tree.pass_events(std::iter::once(event), det, 1.0, &mut Vec::new());
+	}
+	fn selected(tree: &Tree<&'static str, Space>) -> Option<&'static str> {
+		tree.state.with_borrow(|state| state.selected)
+	}
+
+	#[test]
+	fn test_keyboard() {
+		let roots = vec![node("a").child(node("a1")).child(node("a2")), node("b")];
+		let mut tree = Tree::new(Store::new(TreeState::new()), roots);
+
+		// nothing selected, down starts at the top
+		press(&mut tree, KeyboardKey::KEY_DOWN);
+		assert_eq!(selected(&tree), Some("a"));
+		// right expands first, then goes to the first child
+		press(&mut tree, KeyboardKey::KEY_RIGHT);
+		assert!(tree.state.with_borrow(|state| state.is_expanded(&"a")));
+		assert_eq!(selected(&tree), Some("a"));
+		press(&mut tree, KeyboardKey::KEY_RIGHT);
+		assert_eq!(selected(&tree), Some("a1"));
+		press(&mut tree, KeyboardKey::KEY_DOWN);
+		assert_eq!(selected(&tree), Some("a2"));
+		// right on a leaf doesn't do anything
+		press(&mut tree, KeyboardKey::KEY_RIGHT);
+		assert_eq!(selected(&tree), Some("a2"));
+		// left goes to the parent, then collapses it
+		press(&mut tree, KeyboardKey::KEY_LEFT);
+		assert_eq!(selected(&tree), Some("a"));
+		press(&mut tree, KeyboardKey::KEY_LEFT);
+		assert!(!tree.state.with_borrow(|state| state.is_expanded(&"a")));
+		// collapsed, so down skips the children
+		press(&mut tree, KeyboardKey::KEY_DOWN);
+		assert_eq!(selected(&tree), Some("b"));
+		press(&mut tree, KeyboardKey::KEY_DOWN);
+		assert_eq!(selected(&tree), Some("b"));
+		// left on a root doesn't do anything either
+		press(&mut tree, KeyboardKey::KEY_LEFT);
+		assert_eq!(selected(&tree), Some("b"));
+		press(&mut tree, KeyboardKey::KEY_UP);
+		assert_eq!(selected(&tree), Some("a"));
+	}
+
+	#[test]
+	fn test_lazy_cache() {
+		let loads = Rc::new(Cell::new(0));
+		let state = Store::new(TreeState::new());
+		let build = |state: &Store<TreeState<&'static str>>| {
+			let loads = loads.clone();
+			let root = TreeNode::lazy("root", Space::new(10, 10), move || {
+				loads.set(loads.get() + 1);
+				vec![node("child")]
+			});
+			Tree::new(state.clone(), vec![root])
+		};
+
+		// not loaded until it's expanded
+		let mut tree = build(&state);
+		assert_eq!(loads.get(), 0);
+		press(&mut tree, KeyboardKey::KEY_DOWN);
+		press(&mut tree, KeyboardKey::KEY_RIGHT);
+		assert_eq!(loads.get(), 1);
+		assert_eq!(tree.rows().len(), 2);
+
+		// rebuilding gets the children from the state
+		let tree = build(&state);
+		assert_eq!(loads.get(), 1);
+		assert_eq!(tree.rows().len(), 2);
+
+		state.with_mut_borrow(|state| state.unload(&"root"));
+		build(&state);
+		assert_eq!(loads.get(), 2);
+	}
+}
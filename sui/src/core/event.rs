@@ -2,6 +2,8 @@
 pub enum Event {
 	MouseEvent(MouseEvent),
 	KeyboardEvent(crate::form::UniqueId, KeyboardEvent),
+	/// sent to the component owning a [layer](crate::layer) instead of mouse events that happened on it
+	LayerEvent(crate::form::UniqueId, LayerEvent),
 }
 impl Event {
 	pub fn ret<T: 'static>(ret: T) -> ReturnEvent {
@@ -12,18 +14,43 @@ impl Event {
 /// mouseevent can figure out which component to go to from the coords and the det passed to `pass_event`
 pub enum MouseEvent {
 	// these all use window coords
-	MouseClick { x: i32, y: i32 },
-	MouseHeld { x: i32, y: i32 },
-	MouseRelease { x: i32, y: i32 },
+	MouseClick {
+		x: i32,
+		y: i32,
+	},
+	/// the right mouse button got pressed
+	RightClick {
+		x: i32,
+		y: i32,
+	},
+	MouseHeld {
+		x: i32,
+		y: i32,
+	},
+	MouseRelease {
+		x: i32,
+		y: i32,
+	},
+	/// sent every frame, wherever the pointer is. useful for hover effects
+	MouseMove {
+		x: i32,
+		y: i32,
+	},
 
-	Scroll { x: i32, y: i32, amount: f32 },
+	Scroll {
+		x: i32,
+		y: i32,
+		amount: f32,
+	},
 }
 impl MouseEvent {
 	pub fn at(&self) -> (i32, i32) {
 		match self {
 			&Self::MouseClick { x, y } => (x, y),
+			&Self::RightClick { x, y } => (x, y),
 			&Self::MouseHeld { x, y } => (x, y),
 			&Self::MouseRelease { x, y } => (x, y),
+			&Self::MouseMove { x, y } => (x, y),
 			&Self::Scroll { x, y, amount: _ } => (x, y),
 		}
 	}
@@ -34,6 +61,10 @@ impl MouseEvent {
 				let (x, y) = f((x, y));
 				Self::MouseClick { x, y }
 			}
+			Self::RightClick { x, y } => {
+				let (x, y) = f((x, y));
+				Self::RightClick { x, y }
+			}
 			Self::MouseHeld { x, y } => {
 				let (x, y) = f((x, y));
 				Self::MouseHeld { x, y }
@@ -42,6 +73,10 @@ impl MouseEvent {
 				let (x, y) = f((x, y));
 				Self::MouseRelease { x, y }
 			}
+			Self::MouseMove { x, y } => {
+				let (x, y) = f((x, y));
+				Self::MouseMove { x, y }
+			}
 			Self::Scroll { x, y, amount } => {
 				let (x, y) = f((x, y));
 				Self::Scroll { x, y, amount }
@@ -50,10 +85,22 @@ impl MouseEvent {
 	}
 }
 
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LayerEvent {
+	/// a mouse event on the layer, in window coords
+	Mouse(MouseEvent),
+	/// a click (left or right) anywhere outside of the owner's layers. \
+	/// the click itself gets passed on as usual
+	ClickedOutside { x: i32, y: i32 },
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum KeyboardEvent {
 	CharPressed(char),
+	/// sent every frame the key is held down
 	KeyDown(raylib::ffi::KeyboardKey),
+	/// sent only on the frame the key got pressed
+	KeyPressed(raylib::ffi::KeyboardKey),
 }
 
 // -
@@ -115,4 +162,8 @@ impl ReturnEvent {
 	pub fn take<T: 'static>(self) -> Option<T> {
 		self.boxed.downcast::<T>().ok().map(|a| *a)
 	}
+	/// looks at the event without taking it
+	pub fn peek<T: 'static>(&self) -> Option<&T> {
+		self.boxed.downcast_ref::<T>()
+	}
 }
@@ -2,26 +2,35 @@ use std::ops::{Deref, DerefMut};
 
 use raylib::{prelude::RaylibDrawHandle, RaylibThread};
 
-use crate::form::{FocusHandler, UniqueId};
+use crate::{
+	form::{FocusHandler, UniqueId},
+	layer::Layer,
+	Details,
+};
 
 pub struct Handle<'a> {
 	d: RaylibDrawHandle<'a>,
 	thread: &'a RaylibThread,
 	focus: UniqueId,
+	layers: Vec<Layer>,
 }
 impl<'a> Handle<'a> {
 	pub fn new(d: RaylibDrawHandle<'a>, thread: &'a RaylibThread, fh: &FocusHandler) -> Self {
+		crate::layer::clear();
 		Self {
 			d,
 			thread,
 			focus: fh.with_borrow(|a| *a),
+			layers: Vec::new(),
 		}
 	}
 	pub fn new_unfocused(d: RaylibDrawHandle<'a>, thread: &'a RaylibThread) -> Self {
+		crate::layer::clear();
 		Self {
 			d,
 			thread,
 			focus: UniqueId::null(),
+			layers: Vec::new(),
 		}
 	}
 
@@ -39,6 +48,28 @@ impl<'a> Handle<'a> {
 	pub fn focus(&self) -> UniqueId {
 		self.focus
 	}
+	/// opens a layer owned by `owner` at `det` (window coords), see [crate::layer]. \
+	/// `render` gets called once the [RootContext](crate::RootContext) is done rendering
+	pub fn layer(
+		&mut self,
+		owner: UniqueId,
+		det: Details,
+		render: impl FnOnce(&mut Handle) + 'static,
+	) {
+		self.layers.push(Layer {
+			owner,
+			det,
+			render: Box::new(render),
+		});
+	}
+	pub(crate) fn take_layers(&mut self) -> Option<Vec<Layer>> {
+		if self.layers.is_empty() {
+			None
+		} else {
+			Some(std::mem::take(&mut self.layers))
+		}
+	}
+
 	/// self.thread is just a borrow so there's no need to return that
 	pub fn take(self) -> RaylibDrawHandle<'a> {
 		self.d
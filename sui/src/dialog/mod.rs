@@ -1,12 +1,20 @@
-use crate::{core::ImmutableWrap, Comp, Layable, LayableExt, RootContext};
+use raylib::prelude::RaylibDraw;
 
-const DEFAULT_COMP: Comp = Comp::Space(crate::comp::Space::new(0, 0));
+use crate::{
+	core::{Event, LayerEvent, ReturnEvent},
+	Color, Comp, Details, Layable,
+};
+
+/// the backdrop [Instance::dimmed] uses
+pub const DIM: Color = crate::color(0, 0, 0, 128);
 
 #[derive(Clone, Debug)]
-/// DialogHandler is the struct that handles everything about having a dialog
+/// DialogHandler is the struct that handles everything about having dialogs. \
+/// dialogs are kept in a stack, opening one puts it on top and closing one takes the top one off. \
+/// to show them, wrap the rest of the ui with [Handler::wrap]
 pub struct Handler {
 	frame_f: fn(Comp<'static>) -> Comp<'static>,
-	inst: Option<Instance>,
+	stack: Vec<Instance>,
 }
 impl Default for Handler {
 	fn default() -> Self {
@@ -20,25 +28,36 @@ impl Handler {
 	pub fn new(frame_f: fn(Comp<'static>) -> Comp<'static>) -> Self {
 		Self {
 			frame_f,
-			inst: None,
+			stack: Vec::new(),
 		}
 	}
 
 	pub fn run(&mut self, command: Command) {
 		match command {
-			Command::Open(inst) => self.inst = Some(inst.with_framer(self.frame_f)),
-			Command::Close => self.inst = None,
+			Command::Open(inst) => self.stack.push(inst.with_framer(self.frame_f)),
+			Command::Close => {
+				self.stack.pop();
+			}
+			Command::CloseAll => self.stack.clear(),
 		}
 	}
-	pub fn root_context(&self) -> RootContext<ImmutableWrap<Comp<'static>>> {
-		match &self.inst {
-			Some(Instance { comp, at, scale }) => {
-				let (x, y) = *at;
-				let (aw, ah) = comp.size();
-				let det = crate::Details { x, y, aw, ah };
-				RootContext::new(comp.immutable_wrap(), det, *scale)
-			}
-			None => RootContext::new(DEFAULT_COMP.immutable_wrap(), Default::default(), 1.0),
+	/// the open dialogs, topmost last
+	pub fn stack(&self) -> &[Instance] {
+		&self.stack
+	}
+	pub fn is_empty(&self) -> bool {
+		self.stack.is_empty()
+	}
+
+	/// `layable` with the open dialogs on top of it. \
+	/// the top dialog gets events first. mouse events on a dialog don't get to anything under it,
+	/// and a modal dialog doesn't let any events through, except for clicks outside of the
+	/// [layers](crate::layer) under it so popups there close. \
+	/// layers are drawn on top of whatever opened them, but under the dialogs above that
+	pub fn wrap<L: Layable>(&mut self, layable: L) -> Dialogs<'_, L> {
+		Dialogs {
+			layable,
+			stack: &mut self.stack,
 		}
 	}
 }
@@ -48,21 +67,193 @@ pub struct Instance {
 	pub comp: Comp<'static>,
 	pub at: (i32, i32),
 	pub scale: f32,
+	/// a modal dialog keeps everything under it from getting any events, see [Handler::wrap]
+	pub modal: bool,
+	/// drawn over everything under the dialog
+	pub backdrop: Option<Color>,
 }
 impl Instance {
-	pub fn with_framer(self, f: fn(Comp<'static>) -> Comp<'static>) -> Self {
-		let Instance {
-			mut comp,
+	pub fn new(comp: Comp<'static>, at: (i32, i32), scale: f32) -> Self {
+		Self {
+			comp,
 			at,
 			scale,
-		} = self;
-		comp = f(comp);
-		return Instance { comp, at, scale };
+			modal: false,
+			backdrop: None,
+		}
+	}
+	pub fn modal(self) -> Self {
+		Self {
+			modal: true,
+			..self
+		}
+	}
+	pub fn with_backdrop(self, backdrop: Color) -> Self {
+		Self {
+			backdrop: Some(backdrop),
+			..self
+		}
+	}
+	/// modal, with everything under it dimmed
+	pub fn dimmed(self) -> Self {
+		self.modal().with_backdrop(DIM)
+	}
+
+	pub fn with_framer(self, f: fn(Comp<'static>) -> Comp<'static>) -> Self {
+		let Instance { comp, .. } = self;
+		Instance {
+			comp: f(comp),
+			..self
+		}
+	}
+
+	fn det(&self) -> Details {
+		let (x, y) = self.at;
+		let (aw, ah) = self.comp.size();
+		Details { x, y, aw, ah }
+	}
+}
+
+#[derive(Debug)]
+/// see [Handler::wrap]
+pub struct Dialogs<'a, L: Layable> {
+	layable: L,
+	stack: &'a mut Vec<Instance>,
+}
+impl<'a, L: Layable> Layable for Dialogs<'a, L> {
+	fn size(&self) -> (i32, i32) {
+		self.layable.size()
+	}
+	fn render(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		self.layable.render(d, det, scale);
+		for inst in self.stack.iter() {
+			// the layers opened so far go under the dialog
+			crate::layer::render_layers(d);
+			if let Some(backdrop) = inst.backdrop {
+				d.draw_rectangle(det.x, det.y, det.aw, det.ah, backdrop);
+			}
+			// a modal dialog takes the mouse everywhere
+			crate::layer::cover(if inst.modal { det } else { inst.det() });
+			inst.comp.render(d, inst.det(), inst.scale);
+		}
+	}
+
+	fn tick(&mut self) {
+		self.layable.tick();
+		for inst in self.stack.iter_mut() {
+			inst.comp.tick();
+		}
+	}
+	fn pass_events(
+		&mut self,
+		events: impl Iterator<Item = Event>,
+		det: Details,
+		scale: f32,
+		ret_events: &mut Vec<ReturnEvent>,
+	) {
+		let mut events = events.collect::<Vec<_>>();
+
+		for inst in self.stack.iter_mut().rev() {
+			let inst_det = inst.det();
+			// keyboard and layer events go to every dialog, they say who they're for themselves
+			let on_dialog = |event: &Event| match event {
+				Event::MouseEvent(m_event) => inst_det.is_inside_tuple(m_event.at()),
+				_ => true,
+			};
+			let mine = events.iter().copied().filter(on_dialog);
+			inst.comp
+				.pass_events(mine, inst_det, inst.scale, ret_events);
+			events.retain(|event| match event {
+				// the layers under it still have to close
+				Event::LayerEvent(_, LayerEvent::ClickedOutside { .. }) => true,
+				_ if inst.modal => false,
+				Event::MouseEvent(m_event) => !inst_det.is_inside_tuple(m_event.at()),
+				_ => true,
+			});
+		}
+		self.layable
+			.pass_events(events.into_iter(), det, scale, ret_events);
 	}
 }
 
 #[derive(Clone, Debug)]
 pub enum Command {
+	/// puts the dialog on top of the stack
 	Open(Instance),
+	/// closes the top dialog
 	Close,
+	CloseAll,
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{cell::RefCell, rc::Rc};
+
+	use super::*;
+	use crate::{
+		comp::Space,
+		core::{KeyboardEvent, MouseEvent},
+		form::UniqueId,
+	};
+
+	#[derive(Clone, Debug, Default)]
+	/// writes down every event it gets
+	struct Recorder(Rc<RefCell<Vec<Event>>>);
+	impl Layable for Recorder {
+		fn size(&self) -> (i32, i32) {
+			(200, 200)
+		}
+		fn render(&self, _: &mut crate::Handle, _: Details, _: f32) {}
+		fn pass_events(
+			&mut self,
+			events: impl Iterator<Item = Event>,
+			_: Details,
+			_: f32,
+			_: &mut Vec<ReturnEvent>,
+		) {
+			self.0.borrow_mut().extend(events);
+		}
+	}
+
+	#[test]
+	fn test_modal() {
+		let popup = UniqueId::new();
+		let dialog = Instance::new(Comp::Space(Space::new(50, 50)), (100, 100), 1.0);
+		let mut handler = Handler::default();
+		handler.run(Command::Open(dialog.clone().modal()));
+
+		// a popup under the dialog, partly covered by it. a modal dialog covers everything
+		let det = Details::new(0, 0, 200, 200);
+		let open = [
+			(popup, Details::new(0, 0, 120, 120)),
+			(UniqueId::null(), det),
+		];
+		let click = |x, y| Event::MouseEvent(MouseEvent::MouseClick { x, y });
+		let key = Event::KeyboardEvent(popup, KeyboardEvent::CharPressed('a'));
+		let events =
+			crate::layer::route_with(&open, [click(10, 10), click(110, 110), key].into_iter());
+
+		let base = Recorder::default();
+		(handler.wrap(base.clone())).pass_events(events.into_iter(), det, 1.0, &mut Vec::new());
+		let outside = |x, y| Event::LayerEvent(popup, LayerEvent::ClickedOutside { x, y });
+		assert_eq!(*base.0.borrow(), vec![outside(10, 10), outside(110, 110)]);
+
+		// a dialog that isn't modal only takes what's on it
+		handler.run(Command::Close);
+		handler.run(Command::Open(dialog));
+		let open = [
+			(popup, Details::new(0, 0, 120, 120)),
+			(UniqueId::null(), Details::new(100, 100, 50, 50)),
+		];
+		let events =
+			crate::layer::route_with(&open, [click(10, 10), click(110, 110), key].into_iter());
+
+		let base = Recorder::default();
+		(handler.wrap(base.clone())).pass_events(events.into_iter(), det, 1.0, &mut Vec::new());
+		let on_popup = Event::LayerEvent(
+			popup,
+			LayerEvent::Mouse(MouseEvent::MouseClick { x: 10, y: 10 }),
+		);
+		assert_eq!(*base.0.borrow(), vec![on_popup, outside(110, 110), key]);
+	}
 }
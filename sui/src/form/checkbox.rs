@@ -0,0 +1,181 @@
+use raylib::{math::Rectangle, prelude::RaylibDraw};
+
+use super::{
+	control::{self, Changed},
+	UniqueId,
+};
+use crate::{
+	core::{Event, ReturnEvent, Store},
+	Details, Layable, Text,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub enum CheckboxLook {
+	#[default]
+	Box,
+	/// a toggle switch
+	Switch,
+}
+
+#[derive(Clone, Debug)]
+/// a checkbox or a toggle switch, bound to a `Store<bool>`. \
+/// clicking it (or pressing enter or space while it's focused) flips the value and returns a [Changed<bool>]
+pub struct Checkbox {
+	store: Store<bool>,
+	uid: UniqueId,
+	look: CheckboxLook,
+	/// height of the box, unscaled. switches are twice as wide
+	size: i32,
+	label: Option<Text<'static>>,
+}
+impl Checkbox {
+	pub fn new(store: Store<bool>, size: i32) -> Self {
+		Self {
+			store,
+			uid: UniqueId::new(),
+			look: CheckboxLook::Box,
+			size,
+			label: None,
+		}
+	}
+	/// a toggle switch instead of a box
+	pub fn toggle(store: Store<bool>, size: i32) -> Self {
+		Self {
+			look: CheckboxLook::Switch,
+			..Self::new(store, size)
+		}
+	}
+	pub fn with_label(self, label: Text<'static>) -> Self {
+		Self {
+			label: Some(label),
+			..self
+		}
+	}
+
+	pub fn uid(&self) -> UniqueId {
+		self.uid
+	}
+	pub fn store(&self) -> &Store<bool> {
+		&self.store
+	}
+
+	/// unscaled size of the box or the switch
+	fn control_size(&self) -> (i32, i32) {
+		match self.look {
+			CheckboxLook::Box => (self.size, self.size),
+			CheckboxLook::Switch => (self.size * 2, self.size),
+		}
+	}
+	fn bounds(&self, det: Details, scale: f32) -> Details {
+		let (w, h) = self.size();
+		Details {
+			aw: (w as f32 * scale) as i32,
+			ah: (h as f32 * scale) as i32,
+			..det
+		}
+	}
+}
+
+impl Layable for Checkbox {
+	fn size(&self) -> (i32, i32) {
+		let (w, h) = self.control_size();
+		match &self.label {
+			Some(label) => {
+				let (l_w, l_h) = label.size();
+				(w + self.size / 2 + l_w, h.max(l_h))
+			}
+			None => (w, h),
+		}
+	}
+	fn render(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		let checked = self.store.get();
+		let focused = d.focus() == self.uid;
+
+		let (w, h) = self.control_size();
+		let (w, h) = ((w as f32 * scale) as i32, (h as f32 * scale) as i32);
+		let outline = if focused {
+			control::ACCENT
+		} else {
+			control::OUTLINE
+		};
+
+		match self.look {
+			CheckboxLook::Box => {
+				d.draw_rectangle_lines(det.x, det.y, w, h, outline);
+				if checked {
+					let inset = (3.0 * scale) as i32;
+					d.draw_rectangle(
+						det.x + inset,
+						det.y + inset,
+						w - inset * 2,
+						h - inset * 2,
+						control::ACCENT,
+					);
+				}
+			}
+			CheckboxLook::Switch => {
+				let track = Rectangle::new(det.x as f32, det.y as f32, w as f32, h as f32);
+				let track_color = if checked {
+					control::ACCENT
+				} else {
+					control::INACTIVE
+				};
+				d.draw_rectangle_rounded(track, 1.0, 12, track_color);
+				if focused {
+					let pad = (2.0 * scale) as i32;
+					d.draw_rectangle_lines(
+						det.x - pad,
+						det.y - pad,
+						w + pad * 2,
+						h + pad * 2,
+						outline,
+					);
+				}
+
+				let radius = h as f32 / 2.0;
+				let knob_x = if checked {
+					det.x as f32 + w as f32 - radius
+				} else {
+					det.x as f32 + radius
+				};
+				d.draw_circle(
+					knob_x as i32,
+					(det.y as f32 + radius) as i32,
+					radius - 2.0 * scale,
+					crate::color(240, 240, 240, 255),
+				);
+			}
+		}
+
+		if let Some(label) = &self.label {
+			let offset = ((self.control_size().0 + self.size / 2) as f32 * scale) as i32;
+			let label_det = Details {
+				x: det.x + offset,
+				aw: det.aw - offset,
+				..det
+			};
+			label.render(d, label_det, scale);
+		}
+	}
+
+	fn pass_events(
+		&mut self,
+		events: impl Iterator<Item = Event>,
+		det: Details,
+		scale: f32,
+		ret_events: &mut Vec<ReturnEvent>,
+	) {
+		let bounds = self.bounds(det, scale);
+		for event in events {
+			let clicked = control::click_to_focus(&event, bounds, self.uid, ret_events).is_some();
+			if clicked || control::is_activate_key(&event, self.uid) {
+				let value = !self.store.get();
+				self.store.set(value);
+				ret_events.push(Event::ret(Changed {
+					uid: self.uid,
+					value,
+				}));
+			}
+		}
+	}
+}
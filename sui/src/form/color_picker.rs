@@ -0,0 +1,541 @@
+use std::cell::Cell;
+
+use raylib::{ffi::KeyboardKey, prelude::RaylibDraw};
+
+use super::{
+	control::{self, Changed},
+	typable::{TypableData, BACKSPACE},
+	FieldValue, FocusCommand, FormField, Typable, UniqueId,
+};
+use crate::{
+	comp::{self, Margin, Overlay},
+	core::{Event, KeyboardEvent, MouseEvent, ReturnEvent, Store},
+	Color, Details, Layable, LayableExt,
+};
+
+/// space between the parts of the picker, unscaled
+const GAP: i32 = 6;
+/// thickness of the hue and alpha bars, unscaled
+const BAR: i32 = 16;
+const TEXT_SIZE: i32 = 16;
+const PADDING: i32 = 4;
+const ROW_H: i32 = TEXT_SIZE + PADDING * 2;
+const BACKGROUND: Color = crate::color(40, 40, 40, 255);
+const INVALID: Color = crate::color(230, 80, 80, 255);
+const CHECKER_LIGHT: Color = crate::color(200, 200, 200, 255);
+const CHECKER_DARK: Color = crate::color(130, 130, 130, 255);
+/// side of a checkerboard cell, unscaled
+const CHECKER_CELL: i32 = 8;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+enum PickerPart {
+	/// the saturation/value area
+	Area,
+	Hue,
+	Alpha,
+}
+
+#[derive(Clone, Debug)]
+/// a color picker writing to a `Store<Color>`: a saturation/value area, a hue bar, an alpha bar,
+/// and a hex input (`#rrggbb` or `#rrggbbaa`, parsed when enter or tab is pressed or it loses focus). \
+/// every change returns a [Changed<Color>]. \
+/// wrap it in [crate::custom] to put it in a [Comp](crate::Comp), like the ones [crate::dialog] uses. \
+/// in a [Form](super::Form) its value is the color as [to_hex] text
+pub struct ColorPicker {
+	store: Store<Color>,
+	hex: Store<TypableData>,
+	typable: Typable,
+	/// side of the saturation/value area, unscaled
+	size: i32,
+
+	/// kept separately from the store so the hue doesn't get lost when the color is black, white or gray
+	hsv: (f32, f32, f32),
+	/// the color the picker was last synced with, to notice if the store gets changed from the outside
+	shown: Color,
+	drag: Option<PickerPart>,
+
+	hex_dirty: bool,
+	hex_invalid: bool,
+	/// whether the hex input was focused the last time it was rendered
+	focused: Cell<bool>,
+	was_focused: bool,
+}
+impl ColorPicker {
+	pub fn new(store: Store<Color>, size: i32) -> Self {
+		let color = store.get();
+		let hex = Store::new(TypableData::with_default(to_hex(color)));
+		Self {
+			typable: Typable::new(hex.clone(), TEXT_SIZE),
+			store,
+			hex,
+			size,
+			hsv: rgb_to_hsv(color.r, color.g, color.b),
+			shown: color,
+			drag: None,
+			hex_dirty: false,
+			hex_invalid: false,
+			focused: Cell::new(false),
+			was_focused: false,
+		}
+	}
+
+	/// the uid of the hex input
+	pub fn uid(&self) -> UniqueId {
+		self.hex.with_borrow(|data| data.uid)
+	}
+	pub fn store(&self) -> &Store<Color> {
+		&self.store
+	}
+
+	fn color(&self) -> Color {
+		let (h, s, v) = self.hsv;
+		let (r, g, b) = hsv_to_rgb(h, s, v);
+		crate::color(r, g, b, self.shown.a)
+	}
+	/// makes the picker show `color`, without touching the store
+	fn sync(&mut self, color: Color) {
+		let (h, s, v) = rgb_to_hsv(color.r, color.g, color.b);
+		// gray has no hue and black has no saturation either, keep what we had
+		let h = if s == 0.0 || v == 0.0 { self.hsv.0 } else { h };
+		let s = if v == 0.0 { self.hsv.1 } else { s };
+		self.hsv = (h, s, v);
+		self.shown = color;
+		self.reset_hex();
+	}
+	fn reset_hex(&mut self) {
+		let hex = to_hex(self.shown);
+		self.hex.with_mut_borrow(|data| data.set_text(hex));
+		self.hex_dirty = false;
+		self.hex_invalid = false;
+	}
+	fn set(&mut self, color: Color, ret_events: &mut Vec<ReturnEvent>) {
+		self.shown = color;
+		self.reset_hex();
+		if self.store.get() == color {
+			return;
+		}
+		self.store.set(color);
+		ret_events.push(Event::ret(Changed {
+			uid: self.uid(),
+			value: color,
+		}));
+	}
+	fn commit_hex(&mut self, ret_events: &mut Vec<ReturnEvent>) {
+		if !self.hex_dirty {
+			return;
+		}
+		match self.hex.with_borrow(|data| parse_hex(&data.text)) {
+			Some(color) => {
+				self.sync(color);
+				self.set(color, ret_events);
+			}
+			None => self.hex_invalid = true,
+		}
+	}
+
+	/// scaled det of a part of the picker
+	fn part(&self, part: PickerPart, det: Details, scale: f32) -> Details {
+		let s = |v: i32| (v as f32 * scale) as i32;
+		match part {
+			PickerPart::Area => Details {
+				x: det.x,
+				y: det.y,
+				aw: s(self.size),
+				ah: s(self.size),
+			},
+			PickerPart::Hue => Details {
+				x: det.x + s(self.size + GAP),
+				y: det.y,
+				aw: s(BAR),
+				ah: s(self.size),
+			},
+			PickerPart::Alpha => Details {
+				x: det.x,
+				y: det.y + s(self.size + GAP),
+				aw: s(self.size + GAP + BAR),
+				ah: s(BAR),
+			},
+		}
+	}
+	/// scaled (swatch, hex input) dets
+	fn bottom_row(&self, det: Details, scale: f32) -> (Details, Details) {
+		let s = |v: i32| (v as f32 * scale) as i32;
+		let y = det.y + s(self.size + GAP + BAR + GAP);
+		let swatch = Details {
+			x: det.x,
+			y,
+			aw: s(ROW_H * 2),
+			ah: s(ROW_H),
+		};
+		let hex = Details {
+			x: det.x + s(ROW_H * 2 + GAP),
+			y,
+			aw: s(self.size + BAR - ROW_H * 2),
+			ah: s(ROW_H),
+		};
+		(swatch, hex)
+	}
+	/// where the hex text goes, scaled
+	fn hex_text_det(&self, det: Details, scale: f32) -> Details {
+		let (_, hex) = self.bottom_row(det, scale);
+		// the outline and the padding
+		let inset = ((1 + PADDING) as f32 * scale) as i32;
+		Details {
+			x: hex.x + inset,
+			y: hex.y + inset,
+			aw: hex.aw - inset * 2,
+			ah: hex.ah - inset * 2,
+		}
+	}
+	/// the color on a checkerboard, so transparency shows
+	fn swatch(color: Color) -> impl Layable {
+		outlined(
+			Overlay::new(Checkerboard, comp::Color::new(color)),
+			control::OUTLINE,
+		)
+	}
+	fn hex_field(&self, focused: bool) -> impl Layable + '_ {
+		let outline = if self.hex_invalid {
+			INVALID
+		} else if focused {
+			control::ACCENT
+		} else {
+			control::OUTLINE
+		};
+		let input = self.typable.immutable_wrap().margin(PADDING);
+		outlined(Overlay::new(comp::Color::new(BACKGROUND), input), outline)
+	}
+
+	fn drag_to(&mut self, part: PickerPart, det: Details, scale: f32, (x, y): (i32, i32)) -> Color {
+		let part_det = self.part(part, det, scale);
+		let fx = ((x - part_det.x) as f32 / part_det.aw.max(1) as f32).clamp(0.0, 1.0);
+		let fy = ((y - part_det.y) as f32 / part_det.ah.max(1) as f32).clamp(0.0, 1.0);
+		match part {
+			PickerPart::Area => {
+				self.hsv.1 = fx;
+				self.hsv.2 = 1.0 - fy;
+			}
+			PickerPart::Hue => self.hsv.0 = fy * 360.0,
+			PickerPart::Alpha => self.shown.a = (fx * 255.0).round() as u8,
+		}
+		self.color()
+	}
+}
+
+impl FormField for ColorPicker {
+	fn uid(&self) -> UniqueId {
+		ColorPicker::uid(self)
+	}
+	fn value(&self) -> FieldValue {
+		FieldValue::Text(to_hex(self.store.get()))
+	}
+}
+
+impl Layable for ColorPicker {
+	fn size(&self) -> (i32, i32) {
+		(self.size + GAP + BAR, self.size + GAP + BAR + GAP + ROW_H)
+	}
+	fn render(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		let focused = d.focus() == self.uid();
+		self.focused.set(focused);
+
+		let color = self.color();
+		let (h, s, v) = self.hsv;
+		let (r, g, b) = hsv_to_rgb(h, 1.0, 1.0);
+		let pure_hue = crate::color(r, g, b, 255);
+		let marker_r = 4.0 * scale;
+
+		// saturation goes left to right, value top to bottom
+		let area = self.part(PickerPart::Area, det, scale);
+		d.draw_rectangle_gradient_h(area.x, area.y, area.aw, area.ah, Color::WHITE, pure_hue);
+		d.draw_rectangle_gradient_v(
+			area.x,
+			area.y,
+			area.aw,
+			area.ah,
+			crate::color(0, 0, 0, 0),
+			Color::BLACK,
+		);
+		let (mx, my) = (
+			area.x + (s * area.aw as f32) as i32,
+			area.y + ((1.0 - v) * area.ah as f32) as i32,
+		);
+		d.draw_circle_lines(mx, my, marker_r, Color::WHITE);
+		d.draw_circle_lines(mx, my, marker_r + 1.0, Color::BLACK);
+
+		let hue = self.part(PickerPart::Hue, det, scale);
+		for i in 0..6 {
+			let (y0, y1) = (hue.y + hue.ah * i / 6, hue.y + hue.ah * (i + 1) / 6);
+			let (r0, g0, b0) = hsv_to_rgb(i as f32 * 60.0, 1.0, 1.0);
+			let (r1, g1, b1) = hsv_to_rgb((i + 1) as f32 * 60.0, 1.0, 1.0);
+			d.draw_rectangle_gradient_v(
+				hue.x,
+				y0,
+				hue.aw,
+				y1 - y0,
+				crate::color(r0, g0, b0, 255),
+				crate::color(r1, g1, b1, 255),
+			);
+		}
+		let hy = hue.y + (h / 360.0 * hue.ah as f32) as i32;
+		d.draw_rectangle_lines(hue.x - 1, hy - 2, hue.aw + 2, 4, Color::WHITE);
+
+		let alpha = self.part(PickerPart::Alpha, det, scale);
+		Checkerboard.render(d, alpha, scale);
+		d.draw_rectangle_gradient_h(
+			alpha.x,
+			alpha.y,
+			alpha.aw,
+			alpha.ah,
+			crate::color(color.r, color.g, color.b, 0),
+			crate::color(color.r, color.g, color.b, 255),
+		);
+		let ax = alpha.x + (color.a as f32 / 255.0 * alpha.aw as f32) as i32;
+		d.draw_rectangle_lines(ax - 2, alpha.y - 1, 4, alpha.ah + 2, Color::WHITE);
+
+		let (swatch, hex) = self.bottom_row(det, scale);
+		Self::swatch(color).render(d, swatch, scale);
+		self.hex_field(focused).render(d, hex, scale);
+	}
+
+	fn tick(&mut self) {
+		self.typable.tick();
+		let color = self.store.get();
+		if color != self.shown && !self.hex_dirty {
+			self.sync(color);
+		}
+	}
+	fn pass_events(
+		&mut self,
+		events: impl Iterator<Item = Event>,
+		det: Details,
+		scale: f32,
+		ret_events: &mut Vec<ReturnEvent>,
+	) {
+		let focused = self.focused.get();
+		if self.was_focused && !focused {
+			self.commit_hex(ret_events);
+			if self.hex_invalid {
+				self.reset_hex();
+			}
+		}
+		self.was_focused = focused;
+
+		let uid = self.uid();
+		let (_, hex) = self.bottom_row(det, scale);
+		let text_det = self.hex_text_det(det, scale);
+		let events = events.collect::<Vec<_>>();
+		// the typable gets events one at a time, with the held keys so it knows about shift and ctrl
+		let held = (events.iter().copied())
+			.filter(|event| matches!(event, Event::KeyboardEvent(_, KeyboardEvent::KeyDown(_))))
+			.collect::<Vec<_>>();
+		let to_typable =
+			|typable: &mut Typable, event: Event, ret_events: &mut Vec<ReturnEvent>| {
+				let events = held.iter().copied().chain(std::iter::once(event));
+				typable.pass_events(events, text_det, scale, ret_events);
+			};
+
+		for event in events.iter().copied() {
+			match event {
+				Event::MouseEvent(MouseEvent::MouseClick { x, y }) => {
+					if hex.is_inside(x, y) {
+						ret_events.push(Event::ret(FocusCommand::Request(uid)));
+						to_typable(&mut self.typable, event, ret_events);
+						continue;
+					}
+					let part = [PickerPart::Area, PickerPart::Hue, PickerPart::Alpha]
+						.into_iter()
+						.find(|part| self.part(*part, det, scale).is_inside(x, y));
+					if let Some(part) = part {
+						self.drag = Some(part);
+						let color = self.drag_to(part, det, scale, (x, y));
+						self.set(color, ret_events);
+					}
+				}
+				Event::MouseEvent(MouseEvent::MouseHeld { x, y }) => {
+					if let Some(part) = self.drag {
+						let color = self.drag_to(part, det, scale, (x, y));
+						self.set(color, ret_events);
+					}
+				}
+				Event::MouseEvent(MouseEvent::MouseRelease { .. }) => self.drag = None,
+				Event::KeyboardEvent(this_uid, KeyboardEvent::CharPressed(c))
+					if this_uid == uid =>
+				{
+					if c == '\n' {
+						self.commit_hex(ret_events);
+					} else if c == BACKSPACE
+						|| ((c.is_ascii_hexdigit() || c == '#')
+							&& self.hex.with_borrow(|data| data.text.len() < 9))
+					{
+						self.hex_dirty = true;
+						self.hex_invalid = false;
+						to_typable(&mut self.typable, event, ret_events);
+					}
+				}
+				event => match control::pressed_key(&event, uid) {
+					Some(KeyboardKey::KEY_TAB) => self.commit_hex(ret_events),
+					Some(KeyboardKey::KEY_ESCAPE) => self.reset_hex(),
+					// delete, the arrows, home, end, ctrl+a and ctrl+c are for the typable
+					Some(key) => {
+						if key == KeyboardKey::KEY_DELETE {
+							self.hex_dirty = true;
+							self.hex_invalid = false;
+						}
+						to_typable(&mut self.typable, event, ret_events);
+					}
+					None => {}
+				},
+			}
+		}
+	}
+}
+
+/// `layable` with a 1 pixel `outline` around it
+fn outlined<L: Layable>(layable: L, outline: Color) -> Overlay<Margin<L>, comp::Color> {
+	Overlay::new(comp::Color::new(outline), layable.margin(1))
+}
+
+#[derive(Copy, Clone, Debug)]
+/// light and dark gray squares, for behind things that can be transparent
+struct Checkerboard;
+impl Layable for Checkerboard {
+	fn size(&self) -> (i32, i32) {
+		(0, 0)
+	}
+	fn render(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		draw_checkerboard(d, det, (CHECKER_CELL as f32 * scale) as i32);
+	}
+}
+
+fn draw_checkerboard(d: &mut crate::Handle, det: Details, cell: i32) {
+	let cell = cell.max(1);
+	d.draw_rectangle(det.x, det.y, det.aw, det.ah, CHECKER_LIGHT);
+	for row in 0..(det.ah + cell - 1) / cell {
+		for col in 0..(det.aw + cell - 1) / cell {
+			if (row + col) % 2 == 0 {
+				continue;
+			}
+			let (x, y) = (det.x + col * cell, det.y + row * cell);
+			let w = cell.min(det.x + det.aw - x);
+			let h = cell.min(det.y + det.ah - y);
+			d.draw_rectangle(x, y, w, h, CHECKER_DARK);
+		}
+	}
+}
+
+/// `h` in degrees, `s` and `v` from 0 to 1
+pub fn hsv_to_rgb(h: f32, s: f32, v: f32) -> (u8, u8, u8) {
+	let h = h.rem_euclid(360.0) / 60.0;
+	let c = v * s;
+	let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+	let (r, g, b) = match h as u8 {
+		0 => (c, x, 0.0),
+		1 => (x, c, 0.0),
+		2 => (0.0, c, x),
+		3 => (0.0, x, c),
+		4 => (x, 0.0, c),
+		_ => (c, 0.0, x),
+	};
+	let m = v - c;
+	let to_u8 = |f: f32| ((f + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+	(to_u8(r), to_u8(g), to_u8(b))
+}
+/// (hue in degrees, saturation, value)
+pub fn rgb_to_hsv(r: u8, g: u8, b: u8) -> (f32, f32, f32) {
+	let (r, g, b) = (r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+	let max = r.max(g).max(b);
+	let min = r.min(g).min(b);
+	let delta = max - min;
+
+	let h = if delta == 0.0 {
+		0.0
+	} else if max == r {
+		60.0 * ((g - b) / delta).rem_euclid(6.0)
+	} else if max == g {
+		60.0 * ((b - r) / delta + 2.0)
+	} else {
+		60.0 * ((r - g) / delta + 4.0)
+	};
+	let s = if max == 0.0 { 0.0 } else { delta / max };
+	(h, s, max)
+}
+
+/// `#rrggbb`, or `#rrggbbaa` if it's not opaque
+pub fn to_hex(color: Color) -> String {
+	match color.a {
+		255 => format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b),
+		a => format!("#{:02x}{:02x}{:02x}{a:02x}", color.r, color.g, color.b),
+	}
+}
+/// parses `rgb`, `rrggbb` or `rrggbbaa`, with or without a `#` in front
+pub fn parse_hex(hex: &str) -> Option<Color> {
+	let hex = hex.trim();
+	let hex = hex.strip_prefix('#').unwrap_or(hex);
+	if !hex.is_ascii() {
+		return None;
+	}
+	let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+	match hex.len() {
+		3 => {
+			let nibble = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok().map(|n| n * 17);
+			Some(crate::color(nibble(0)?, nibble(1)?, nibble(2)?, 255))
+		}
+		6 => Some(crate::color(byte(0)?, byte(2)?, byte(4)?, 255)),
+		8 => Some(crate::color(byte(0)?, byte(2)?, byte(4)?, byte(6)?)),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_hex() {
+		assert_eq!(parse_hex("#ff8000"), Some(crate::color(255, 128, 0, 255)));
+		assert_eq!(parse_hex("FF800080"), Some(crate::color(255, 128, 0, 128)));
+		assert_eq!(parse_hex("#f80"), Some(crate::color(255, 136, 0, 255)));
+		assert_eq!(parse_hex("#ff80"), None);
+		assert_eq!(parse_hex("#gg0000"), None);
+
+		assert_eq!(to_hex(crate::color(255, 128, 0, 255)), "#ff8000");
+		assert_eq!(to_hex(crate::color(255, 128, 0, 128)), "#ff800080");
+	}
+
+	#[test]
+	fn test_hex_editing() {
+		let mut picker = ColorPicker::new(Store::new(crate::color(255, 128, 0, 255)), 100);
+		let uid = picker.uid();
+		let key = |key| Event::KeyboardEvent(uid, KeyboardEvent::KeyPressed(key));
+		let ch = |c| Event::KeyboardEvent(uid, KeyboardEvent::CharPressed(c));
+		// "#ff8000" -> "#ff80ff"
+		let events = [
+			key(KeyboardKey::KEY_END),
+			key(KeyboardKey::KEY_LEFT),
+			key(KeyboardKey::KEY_LEFT),
+			key(KeyboardKey::KEY_DELETE),
+			key(KeyboardKey::KEY_DELETE),
+			ch('f'),
+			ch('f'),
+			ch('\n'),
+		];
+		let det = Details::new(0, 0, 200, 200);
+		picker.pass_events(events.into_iter(), det, 1.0, &mut Vec::new());
+		assert_eq!(picker.store().get(), crate::color(255, 128, 255, 255));
+	}
+
+	#[test]
+	fn test_hsv_roundtrip() {
+		assert_eq!(hsv_to_rgb(0.0, 1.0, 1.0), (255, 0, 0));
+		assert_eq!(hsv_to_rgb(120.0, 1.0, 1.0), (0, 255, 0));
+		assert_eq!(hsv_to_rgb(240.0, 1.0, 1.0), (0, 0, 255));
+		assert_eq!(hsv_to_rgb(360.0, 1.0, 1.0), (255, 0, 0));
+		assert_eq!(rgb_to_hsv(0, 0, 255), (240.0, 1.0, 1.0));
+
+		for (r, g, b) in [(255, 128, 0), (12, 200, 99), (80, 80, 80), (1, 2, 3)] {
+			let (h, s, v) = rgb_to_hsv(r, g, b);
+			assert_eq!(hsv_to_rgb(h, s, v), (r, g, b));
+		}
+	}
+}
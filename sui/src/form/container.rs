@@ -0,0 +1,885 @@
+use std::{borrow::Cow, rc::Rc, str::FromStr};
+
+use raylib::{ffi::KeyboardKey, prelude::RaylibDraw};
+
+use super::{
+	control, typable::TypableData, Checkbox, FloatSpinner, FocusCommand, IntSpinner, Select,
+	Slider, Typable, UniqueId,
+};
+use crate::{
+	comp::Button,
+	core::{Event, KeyboardEvent, MouseEvent, ReturnEvent, Store},
+	Color, Details, DynamicLayable, Layable, Text,
+};
+
+const PADDING: i32 = 4;
+/// space between fields, unscaled
+const GAP: i32 = 10;
+const INPUT_BACKGROUND: Color = crate::color(40, 40, 40, 255);
+const ERROR: Color = crate::color(230, 80, 80, 255);
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FieldValue {
+	/// text fields and selects
+	Text(String),
+	Bool(bool),
+	Int(i64),
+	/// float spinners and sliders
+	Float(f64),
+}
+impl FieldValue {
+	pub fn as_str(&self) -> Option<&str> {
+		match self {
+			Self::Text(text) => Some(text),
+			_ => None,
+		}
+	}
+	pub fn as_bool(&self) -> Option<bool> {
+		match self {
+			Self::Bool(b) => Some(*b),
+			_ => None,
+		}
+	}
+	pub fn as_int(&self) -> Option<i64> {
+		match self {
+			Self::Int(i) => Some(*i),
+			_ => None,
+		}
+	}
+	/// ints work too
+	pub fn as_float(&self) -> Option<f64> {
+		match self {
+			Self::Float(f) => Some(*f),
+			Self::Int(i) => Some(*i as f64),
+			_ => None,
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+/// the value of every field in a [Form], in the order they were added
+pub struct FormValues(Vec<(Cow<'static, str>, FieldValue)>);
+impl FormValues {
+	pub fn get(&self, name: &str) -> Option<&FieldValue> {
+		self.0
+			.iter()
+			.find(|(field, _)| field == name)
+			.map(|(_, value)| value)
+	}
+	pub fn text(&self, name: &str) -> Option<&str> {
+		self.get(name)?.as_str()
+	}
+	pub fn bool(&self, name: &str) -> Option<bool> {
+		self.get(name)?.as_bool()
+	}
+	pub fn int(&self, name: &str) -> Option<i64> {
+		self.get(name)?.as_int()
+	}
+	pub fn float(&self, name: &str) -> Option<f64> {
+		self.get(name)?.as_float()
+	}
+	/// parses a text field
+	pub fn parse<T: FromStr>(&self, name: &str) -> Option<T> {
+		self.text(name)?.trim().parse().ok()
+	}
+	pub fn iter(&self) -> impl Iterator<Item = (&str, &FieldValue)> {
+		self.0.iter().map(|(name, value)| (name.as_ref(), value))
+	}
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// returned by a [Form] when it's submitted and every validator passed
+pub struct Submitted {
+	/// the form that got submitted
+	pub uid: UniqueId,
+	pub values: FormValues,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// an error from a cross-field validator. \
+/// gets shown next to `field`, or under the form if there's no field called that
+pub struct FieldError {
+	pub field: Cow<'static, str>,
+	pub message: String,
+}
+impl FieldError {
+	pub fn new(field: impl Into<Cow<'static, str>>, message: impl Into<String>) -> Self {
+		Self {
+			field: field.into(),
+			message: message.into(),
+		}
+	}
+}
+
+#[derive(Clone)]
+struct Validator(Rc<dyn Fn(&FieldValue) -> Result<(), String>>);
+impl std::fmt::Debug for Validator {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str("Validator")
+	}
+}
+#[derive(Clone)]
+struct FormValidator(Rc<dyn Fn(&FormValues) -> Result<(), FieldError>>);
+impl std::fmt::Debug for FormValidator {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str("FormValidator")
+	}
+}
+
+/// a control that can be put in a [Form] with [Form::custom_field], like a [ColorPicker](super::ColorPicker)
+pub trait FormField: Layable + std::fmt::Debug + Clone + 'static {
+	/// the uid that has focus while the control is being edited, it gets focused if the field is invalid
+	fn uid(&self) -> UniqueId;
+	fn value(&self) -> FieldValue;
+}
+
+#[derive(Clone, Debug)]
+enum FieldInput {
+	Text {
+		data: Store<TypableData>,
+		typable: Typable,
+	},
+	Check(Checkbox),
+	Int(IntSpinner),
+	Float(FloatSpinner),
+	Slider(Slider),
+	Select(Select<String>),
+	/// a [FormField], `uid` and `value` know which type is inside
+	Custom {
+		layable: DynamicLayable<'static>,
+		uid: fn(&DynamicLayable<'static>) -> UniqueId,
+		value: fn(&DynamicLayable<'static>) -> FieldValue,
+	},
+}
+impl FieldInput {
+	fn custom<F: FormField>(field: F) -> Self {
+		fn uid<F: FormField>(layable: &DynamicLayable<'static>) -> UniqueId {
+			let field = layable.borrow::<F>();
+			field.expect("custom fields don't change type").uid()
+		}
+		fn value<F: FormField>(layable: &DynamicLayable<'static>) -> FieldValue {
+			let field = layable.borrow::<F>();
+			field.expect("custom fields don't change type").value()
+		}
+		Self::Custom {
+			layable: DynamicLayable::new(field),
+			uid: uid::<F>,
+			value: value::<F>,
+		}
+	}
+}
+
+#[derive(Clone, Debug)]
+struct Field {
+	name: Cow<'static, str>,
+	/// checkboxes show their label themselves
+	label: Option<Text<'static>>,
+	input: FieldInput,
+	validators: Vec<Validator>,
+	error: Option<Text<'static>>,
+}
+impl Field {
+	fn uid(&self) -> UniqueId {
+		match &self.input {
+			FieldInput::Text { data, .. } => data.with_borrow(|data| data.uid),
+			FieldInput::Check(checkbox) => checkbox.uid(),
+			FieldInput::Int(spinner) => spinner.uid(),
+			FieldInput::Float(spinner) => spinner.uid(),
+			FieldInput::Slider(slider) => slider.uid(),
+			FieldInput::Select(select) => select.uid(),
+			FieldInput::Custom { layable, uid, .. } => uid(layable),
+		}
+	}
+	fn value(&self) -> FieldValue {
+		match &self.input {
+			FieldInput::Text { data, .. } => {
+				FieldValue::Text(data.with_borrow(|data| data.text.clone()))
+			}
+			FieldInput::Check(checkbox) => FieldValue::Bool(checkbox.store().get()),
+			FieldInput::Int(spinner) => FieldValue::Int(spinner.store().get()),
+			FieldInput::Float(spinner) => FieldValue::Float(spinner.store().get()),
+			FieldInput::Slider(slider) => FieldValue::Float(slider.store().get() as f64),
+			FieldInput::Select(select) => {
+				FieldValue::Text(select.store().with_borrow(|value| value.clone()))
+			}
+			FieldInput::Custom { layable, value, .. } => value(layable),
+		}
+	}
+}
+
+/// what the submit button returns, so the form can tell it apart from everything else
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct SubmitPressed;
+
+#[derive(Clone, Debug)]
+/// a form: a column of registered fields with a submit button under them. \
+/// pressing enter in a text field or clicking the button runs every field's validators and then the
+/// cross-field ones. if any of them fail, their errors are shown next to the fields and the first invalid field
+/// gets focused, otherwise the form returns a [Submitted] with every field's value. \
+/// tab moves focus to the next field
+pub struct Form {
+	uid: UniqueId,
+	fields: Vec<Field>,
+	validators: Vec<FormValidator>,
+	submit: Button<Text<'static>, fn() -> SubmitPressed, SubmitPressed>,
+	text_size: i32,
+	/// width of text inputs, unscaled
+	field_width: i32,
+
+	/// errors from cross-field validators that don't belong to any field
+	form_error: Option<Text<'static>>,
+	/// once a submit has been attempted, the values the form was last validated with.
+	/// fields get revalidated as they're edited, whenever these change
+	validated: Option<FormValues>,
+}
+impl Form {
+	pub fn new(text_size: i32) -> Self {
+		let submit: fn() -> SubmitPressed = || SubmitPressed;
+		Self {
+			uid: UniqueId::new(),
+			fields: Vec::new(),
+			validators: Vec::new(),
+			submit: Button::new(Text::new("Submit", text_size), submit),
+			text_size,
+			field_width: text_size * 12,
+			form_error: None,
+			validated: None,
+		}
+	}
+	pub fn with_submit_label(mut self, label: impl Into<Cow<'static, str>>) -> Self {
+		let submit: fn() -> SubmitPressed = || SubmitPressed;
+		self.submit = Button::new(Text::new(label.into(), self.text_size), submit);
+		self
+	}
+	/// width of text inputs, unscaled
+	pub fn with_field_width(self, field_width: i32) -> Self {
+		Self {
+			field_width,
+			..self
+		}
+	}
+
+	/// registers a text field called `name`, reading from and typing into `data`
+	pub fn text_field(
+		mut self,
+		name: impl Into<Cow<'static, str>>,
+		label: impl Into<Cow<'static, str>>,
+		data: Store<TypableData>,
+	) -> Self {
+		let input = FieldInput::Text {
+			typable: Typable::new(data.clone(), self.text_size),
+			data,
+		};
+		self.push(name, label, input);
+		self
+	}
+	/// registers a checkbox field called `name`
+	pub fn check_field(
+		mut self,
+		name: impl Into<Cow<'static, str>>,
+		label: impl Into<Cow<'static, str>>,
+		store: Store<bool>,
+	) -> Self {
+		let label = Text::new(label.into(), self.text_size);
+		self.fields.push(Field {
+			name: name.into(),
+			label: None,
+			input: FieldInput::Check(Checkbox::new(store, self.text_size).with_label(label)),
+			validators: Vec::new(),
+			error: None,
+		});
+		self
+	}
+	/// registers an integer field called `name`, shown as a [Spinner](super::Spinner)
+	pub fn int_field(
+		mut self,
+		name: impl Into<Cow<'static, str>>,
+		label: impl Into<Cow<'static, str>>,
+		store: Store<i64>,
+		min: i64,
+		max: i64,
+		step: i64,
+	) -> Self {
+		let spinner = IntSpinner::new(store, min, max, step, self.text_size);
+		self.push(name, label, FieldInput::Int(spinner));
+		self
+	}
+	/// registers a float field called `name`, shown as a [Spinner](super::Spinner)
+	pub fn float_field(
+		mut self,
+		name: impl Into<Cow<'static, str>>,
+		label: impl Into<Cow<'static, str>>,
+		store: Store<f64>,
+		min: f64,
+		max: f64,
+		step: f64,
+	) -> Self {
+		let spinner = FloatSpinner::new(store, min, max, step, self.text_size);
+		self.push(name, label, FieldInput::Float(spinner));
+		self
+	}
+	/// registers a float field called `name`, shown as a [Slider] as wide as text inputs. \
+	/// a `step` of 0 is continuous
+	pub fn slider_field(
+		mut self,
+		name: impl Into<Cow<'static, str>>,
+		label: impl Into<Cow<'static, str>>,
+		store: Store<f32>,
+		min: f32,
+		max: f32,
+		step: f32,
+	) -> Self {
+		let slider = Slider::new(store, min, max, self.field_width)
+			.with_step(step)
+			.with_label(self.text_size);
+		self.push(name, label, FieldInput::Slider(slider));
+		self
+	}
+	/// registers a field called `name` where one of `options` can be picked from a [Select]. \
+	/// its value is the text of the picked option
+	pub fn select_field(
+		mut self,
+		name: impl Into<Cow<'static, str>>,
+		label: impl Into<Cow<'static, str>>,
+		store: Store<String>,
+		options: impl IntoIterator<Item = impl Into<Cow<'static, str>>>,
+	) -> Self {
+		let select = options
+			.into_iter()
+			.fold(Select::new(store), |select, option| {
+				let option = option.into();
+				select.option(option.to_string(), Text::new(option, self.text_size))
+			});
+		self.push(name, label, FieldInput::Select(select));
+		self
+	}
+	/// registers a field called `name` shown as `field`, for controls the form doesn't know about. \
+	/// it gets every mouse event, and every keyboard event except tab
+	pub fn custom_field(
+		mut self,
+		name: impl Into<Cow<'static, str>>,
+		label: impl Into<Cow<'static, str>>,
+		field: impl FormField,
+	) -> Self {
+		self.push(name, label, FieldInput::custom(field));
+		self
+	}
+	fn push(
+		&mut self,
+		name: impl Into<Cow<'static, str>>,
+		label: impl Into<Cow<'static, str>>,
+		input: FieldInput,
+	) {
+		self.fields.push(Field {
+			name: name.into(),
+			label: Some(Text::new(label.into(), self.text_size)),
+			input,
+			validators: Vec::new(),
+			error: None,
+		});
+	}
+	/// adds a validator to the field called `name`, see [super::validators]. \
+	/// validators run in the order they were added, only the first error gets shown. \
+	/// panics if there's no field called `name`
+	pub fn validate(
+		mut self,
+		name: &str,
+		validator: impl Fn(&FieldValue) -> Result<(), String> + 'static,
+	) -> Self {
+		match self.fields.iter_mut().find(|field| field.name == name) {
+			Some(field) => field.validators.push(Validator(Rc::new(validator))),
+			None => panic!("Form::validate: no field called {name:?}, add the field first"),
+		}
+		self
+	}
+	/// adds a validator that sees every field's value, for things like checking that two passwords match. \
+	/// these only run if every field's own validators passed
+	pub fn validate_form(
+		mut self,
+		validator: impl Fn(&FormValues) -> Result<(), FieldError> + 'static,
+	) -> Self {
+		self.validators.push(FormValidator(Rc::new(validator)));
+		self
+	}
+
+	pub fn uid(&self) -> UniqueId {
+		self.uid
+	}
+	pub fn values(&self) -> FormValues {
+		FormValues(
+			self.fields
+				.iter()
+				.map(|field| (field.name.clone(), field.value()))
+				.collect(),
+		)
+	}
+	/// the error shown next to the field called `name`, if any
+	pub fn error(&self, name: &str) -> Option<&str> {
+		let field = self.fields.iter().find(|field| field.name == name)?;
+		field.error.as_ref().map(|error| error.text.as_ref())
+	}
+
+	/// runs every validator and shows the errors. returns whether everything passed
+	pub fn validate_all(&mut self) -> bool {
+		let text_size = self.text_size;
+		let error_text = move |message: String| Text::new_colored(message, text_size, ERROR);
+
+		let mut valid = true;
+		for field in self.fields.iter_mut() {
+			let value = field.value();
+			let error = field
+				.validators
+				.iter()
+				.find_map(|Validator(validator)| validator(&value).err());
+			valid &= error.is_none();
+			field.error = error.map(error_text);
+		}
+
+		self.form_error = None;
+		if !valid {
+			return false;
+		}
+		let values = self.values();
+		for FormValidator(validator) in self.validators.iter() {
+			let Err(FieldError { field, message }) = validator(&values) else {
+				continue;
+			};
+			valid = false;
+			match self.fields.iter_mut().find(|f| f.name == field) {
+				Some(field) if field.error.is_none() => field.error = Some(error_text(message)),
+				Some(_) => {}
+				None if self.form_error.is_none() => self.form_error = Some(error_text(message)),
+				None => {}
+			}
+		}
+		valid
+	}
+	fn submit(&mut self, ret_events: &mut Vec<ReturnEvent>) {
+		self.validated = Some(self.values());
+		if self.validate_all() {
+			ret_events.push(Event::ret(Submitted {
+				uid: self.uid,
+				values: self.values(),
+			}));
+			return;
+		}
+		if let Some(field) = self.fields.iter().find(|field| field.error.is_some()) {
+			ret_events.push(Event::ret(FocusCommand::Request(field.uid())));
+		}
+	}
+
+	/// unscaled (label, input) sizes of a field
+	fn field_sizes(&self, field: &Field) -> ((i32, i32), (i32, i32)) {
+		let label = field
+			.label
+			.as_ref()
+			.map(|label| label.size())
+			.unwrap_or_default();
+		let input = match &field.input {
+			FieldInput::Text { .. } => (self.field_width, self.text_size + PADDING * 2),
+			FieldInput::Check(checkbox) => checkbox.size(),
+			FieldInput::Int(spinner) => spinner.size(),
+			FieldInput::Float(spinner) => spinner.size(),
+			FieldInput::Slider(slider) => slider.size(),
+			FieldInput::Select(select) => select.size(),
+			FieldInput::Custom { layable, .. } => layable.size(),
+		};
+		(label, input)
+	}
+	/// scaled (label, input, error) dets of every field, and the submit button's det
+	fn layout(&self, det: Details, scale: f32) -> (Vec<(Details, Details, Details)>, Details) {
+		let s = |v: i32| (v as f32 * scale) as i32;
+		let mut y = det.y;
+		let mut fields = Vec::with_capacity(self.fields.len());
+		for field in self.fields.iter() {
+			let ((l_w, l_h), (i_w, i_h)) = self.field_sizes(field);
+			let label = Details {
+				x: det.x,
+				y,
+				aw: s(l_w),
+				ah: s(l_h),
+			};
+			y += s(l_h);
+			let input = Details {
+				x: det.x,
+				y,
+				aw: s(i_w),
+				ah: s(i_h),
+			};
+			let (e_w, e_h) = field.error.as_ref().map(|e| e.size()).unwrap_or_default();
+			let error = Details {
+				x: input.x + input.aw + s(PADDING * 2),
+				y: y + (input.ah - s(e_h)) / 2,
+				aw: s(e_w),
+				ah: s(e_h),
+			};
+			y += s(i_h + GAP);
+			fields.push((label, input, error));
+		}
+		if let Some(form_error) = &self.form_error {
+			y += s(form_error.size().1 + GAP);
+		}
+		let (b_w, b_h) = self.submit.size();
+		let button = Details {
+			x: det.x,
+			y,
+			aw: s(b_w),
+			ah: s(b_h),
+		};
+		(fields, button)
+	}
+}
+
+impl Layable for Form {
+	fn size(&self) -> (i32, i32) {
+		let (mut w, mut h) = (0, 0);
+		for field in self.fields.iter() {
+			let ((l_w, l_h), (i_w, i_h)) = self.field_sizes(field);
+			let e_w = field
+				.error
+				.as_ref()
+				.map(|e| e.size().0 + PADDING * 2)
+				.unwrap_or(0);
+			w = w.max(l_w).max(i_w + e_w);
+			h += l_h + i_h + GAP;
+		}
+		if let Some(form_error) = &self.form_error {
+			let (e_w, e_h) = form_error.size();
+			w = w.max(e_w);
+			h += e_h + GAP;
+		}
+		let (b_w, b_h) = self.submit.size();
+		(w.max(b_w), h + b_h)
+	}
+	fn render(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		let focus = d.focus();
+		let (layout, button) = self.layout(det, scale);
+		let padding = (PADDING as f32 * scale) as i32;
+
+		for (field, (label_det, input_det, error_det)) in self.fields.iter().zip(layout) {
+			if let Some(label) = &field.label {
+				label.render(d, label_det, scale);
+			}
+			match &field.input {
+				FieldInput::Text { typable, .. } => {
+					let i = input_det;
+					d.draw_rectangle(i.x, i.y, i.aw, i.ah, INPUT_BACKGROUND);
+					let outline = if field.error.is_some() {
+						ERROR
+					} else if focus == field.uid() {
+						control::ACCENT
+					} else {
+						control::OUTLINE
+					};
+					d.draw_rectangle_lines(i.x, i.y, i.aw, i.ah, outline);
+					let text_det = Details {
+						x: i.x + padding,
+						y: i.y + padding,
+						aw: i.aw - padding * 2,
+						ah: i.ah - padding * 2,
+					};
+					typable.render(d, text_det, scale);
+				}
+				FieldInput::Check(checkbox) => checkbox.render(d, input_det, scale),
+				FieldInput::Int(spinner) => spinner.render(d, input_det, scale),
+				FieldInput::Float(spinner) => spinner.render(d, input_det, scale),
+				FieldInput::Slider(slider) => slider.render(d, input_det, scale),
+				FieldInput::Select(select) => select.render(d, input_det, scale),
+				FieldInput::Custom { layable, .. } => layable.render(d, input_det, scale),
+			}
+			if let Some(error) = &field.error {
+				error.render(d, error_det, scale);
+			}
+		}
+
+		if let Some(form_error) = &self.form_error {
+			let (_, e_h) = form_error.size();
+			let e_h = ((e_h + GAP) as f32 * scale) as i32;
+			let error_det = Details {
+				y: button.y - e_h,
+				..button
+			};
+			form_error.render(d, error_det, scale);
+		}
+		self.submit.render(d, button, scale);
+	}
+
+	fn tick(&mut self) {
+		for field in self.fields.iter_mut() {
+			match &mut field.input {
+				FieldInput::Text { typable, .. } => typable.tick(),
+				FieldInput::Check(checkbox) => checkbox.tick(),
+				FieldInput::Int(spinner) => spinner.tick(),
+				FieldInput::Float(spinner) => spinner.tick(),
+				FieldInput::Slider(slider) => slider.tick(),
+				FieldInput::Select(select) => select.tick(),
+				FieldInput::Custom { layable, .. } => layable.tick(),
+			}
+		}
+		self.submit.tick();
+		if let Some(validated) = &self.validated {
+			let values = self.values();
+			if *validated != values {
+				self.validated = Some(values);
+				self.validate_all();
+			}
+		}
+	}
+	fn pass_events(
+		&mut self,
+		events: impl Iterator<Item = Event>,
+		det: Details,
+		scale: f32,
+		ret_events: &mut Vec<ReturnEvent>,
+	) {
+		let (layout, button) = self.layout(det, scale);
+		let uids = self.fields.iter().map(Field::uid).collect::<Vec<_>>();
+		// only text fields submit on enter, everything else has its own use for it
+		let submits_on_enter = (self.fields.iter())
+			.map(|field| matches!(field.input, FieldInput::Text { .. }))
+			.collect::<Vec<_>>();
+
+		let mut submit = false;
+		// what's left once enter and tab are handled
+		let mut rest = Vec::new();
+		for event in events {
+			let focused = match event {
+				Event::KeyboardEvent(uid, _) => uids.iter().position(|field| *field == uid),
+				_ => None,
+			};
+			match (event, focused) {
+				(
+					Event::KeyboardEvent(
+						_,
+						KeyboardEvent::KeyPressed(
+							KeyboardKey::KEY_ENTER | KeyboardKey::KEY_KP_ENTER,
+						),
+					),
+					Some(i),
+				) if submits_on_enter[i] => {
+					submit = true;
+				}
+				// enter comes as a char too, it shouldn't end up in the text
+				(Event::KeyboardEvent(_, KeyboardEvent::CharPressed('\n')), Some(i))
+					if submits_on_enter[i] => {}
+				(
+					Event::KeyboardEvent(_, KeyboardEvent::KeyPressed(KeyboardKey::KEY_TAB)),
+					Some(i),
+				) => {
+					let next = (i + 1) % uids.len();
+					ret_events.push(Event::ret(FocusCommand::Request(uids[next])));
+				}
+				_ => rest.push(event),
+			}
+		}
+
+		// every field gets all of its events at once, so it can tell which keys are held down during a click
+		for (field, (_, input_det, _)) in self.fields.iter_mut().zip(layout.iter()) {
+			// sliders keep getting the mouse while they're dragged, and custom fields might be dragged too
+			let all_mouse = matches!(
+				field.input,
+				FieldInput::Slider(_) | FieldInput::Custom { .. }
+			);
+			let field_events = rest.iter().copied().filter(|event| match event {
+				Event::MouseEvent(m_event) => all_mouse || input_det.is_inside_tuple(m_event.at()),
+				_ => true,
+			});
+			let uid = field.uid();
+			match &mut field.input {
+				FieldInput::Text { typable, .. } => {
+					let clicked = (field_events.clone()).any(|event| {
+						matches!(event, Event::MouseEvent(MouseEvent::MouseClick { .. }))
+					});
+					if clicked {
+						ret_events.push(Event::ret(FocusCommand::Request(uid)));
+					}
+					typable.pass_events(field_events, *input_det, scale, ret_events);
+				}
+				FieldInput::Check(checkbox) => {
+					checkbox.pass_events(field_events, *input_det, scale, ret_events)
+				}
+				FieldInput::Int(spinner) => {
+					spinner.pass_events(field_events, *input_det, scale, ret_events)
+				}
+				FieldInput::Float(spinner) => {
+					spinner.pass_events(field_events, *input_det, scale, ret_events)
+				}
+				FieldInput::Slider(slider) => {
+					slider.pass_events(field_events, *input_det, scale, ret_events)
+				}
+				FieldInput::Select(select) => {
+					select.pass_events(field_events, *input_det, scale, ret_events)
+				}
+				FieldInput::Custom { layable, .. } => {
+					layable.pass_events(field_events, *input_det, scale, ret_events)
+				}
+			}
+		}
+
+		let mut button_events = Vec::new();
+		self.submit
+			.pass_events(rest.into_iter(), button, scale, &mut button_events);
+		for button_event in button_events {
+			if button_event.can_take::<SubmitPressed>() {
+				submit = true;
+			} else {
+				ret_events.push(button_event);
+			}
+		}
+
+		if submit {
+			self.submit(ret_events);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::form::{validators, ColorPicker};
+
+	fn text(s: &str) -> Store<TypableData> {
+		Store::new(TypableData::with_default(s.to_string()))
+	}
+
+	#[test]
+	fn test_validation() {
+		let (name, password, repeat) = (text(""), text("hunter2"), text("hunter3"));
+		let agree = Store::new(false);
+
+		let mut form = Form::new(16)
+			.text_field("name", "Name", name.clone())
+			.text_field("password", "Password", password.clone())
+			.text_field("repeat", "Repeat password", repeat.clone())
+			.check_field("agree", "I agree", agree.clone())
+			.validate("name", validators::required)
+			.validate("password", validators::min_length(6))
+			.validate("agree", validators::required)
+			.validate_form(|values| {
+				if values.text("password") != values.text("repeat") {
+					return Err(FieldError::new("repeat", "passwords don't match"));
+				}
+				Ok(())
+			});
+
+		assert!(!form.validate_all());
+		assert_eq!(form.error("name"), Some("required"));
+		assert_eq!(form.error("password"), None);
+		assert_eq!(form.error("agree"), Some("required"));
+		// cross-field validators wait until every field is fine on its own
+		assert_eq!(form.error("repeat"), None);
+
+		name.with_mut_borrow(|data| data.text = "manen".into());
+		agree.set(true);
+		assert!(!form.validate_all());
+		assert_eq!(form.error("name"), None);
+		assert_eq!(form.error("repeat"), Some("passwords don't match"));
+
+		repeat.with_mut_borrow(|data| data.text = "hunter2".into());
+		assert!(form.validate_all());
+		assert_eq!(form.values().text("name"), Some("manen"));
+		assert_eq!(form.values().bool("agree"), Some(true));
+	}
+
+	#[test]
+	fn test_revalidation() {
+		let name = text("");
+		let runs = Rc::new(std::cell::Cell::new(0));
+		let counted = runs.clone();
+		let mut form = Form::new(16)
+			.text_field("name", "Name", name.clone())
+			.validate("name", move |value| {
+				counted.set(counted.get() + 1);
+				validators::required(value)
+			});
+
+		// nothing gets validated before the first submit
+		form.tick();
+		assert_eq!(runs.get(), 0);
+
+		form.submit(&mut Vec::new());
+		assert_eq!(runs.get(), 1);
+		assert_eq!(form.error("name"), Some("required"));
+		// and after it, only when something changed
+		form.tick();
+		form.tick();
+		assert_eq!(runs.get(), 1);
+
+		name.with_mut_borrow(|data| data.text = "manen".into());
+		form.tick();
+		assert_eq!(runs.get(), 2);
+		assert_eq!(form.error("name"), None);
+		form.tick();
+		assert_eq!(runs.get(), 2);
+	}
+
+	#[test]
+	fn test_events() {
+		let (name, email) = (text(""), text(""));
+		let mut form = Form::new(16)
+			.text_field("name", "Name", name.clone())
+			.text_field("email", "Email", email.clone())
+			.validate("name", validators::required)
+			.validate("email", validators::required);
+		let (name_uid, email_uid) = (name.with_borrow(|d| d.uid), email.with_borrow(|d| d.uid));
+		let det = Details::new(0, 0, 400, 400);
+		// enter comes as a char and as a key
+		let mut enter = |uid: UniqueId| {
+			let events = [
+				Event::KeyboardEvent(uid, KeyboardEvent::CharPressed('\n')),
+				Event::KeyboardEvent(uid, KeyboardEvent::KeyPressed(KeyboardKey::KEY_ENTER)),
+			];
+			let mut ret_events = Vec::new();
+			form.pass_events(events.into_iter(), det, 1.0, &mut ret_events);
+			ret_events
+		};
+
+		// both are invalid, only the first one gets focused
+		let ret_events = enter(email_uid);
+		assert_eq!(ret_events.len(), 1);
+		assert_eq!(
+			ret_events[0].peek::<FocusCommand>(),
+			Some(&FocusCommand::Request(name_uid))
+		);
+
+		name.with_mut_borrow(|data| data.text = "manen".into());
+		let ret_events = enter(email_uid);
+		assert_eq!(
+			ret_events[0].peek::<FocusCommand>(),
+			Some(&FocusCommand::Request(email_uid))
+		);
+
+		email.with_mut_borrow(|data| data.text = "manen@example.com".into());
+		let ret_events = enter(email_uid);
+		assert_eq!(ret_events.len(), 1);
+		let submitted = ret_events[0]
+			.peek::<Submitted>()
+			.expect("the form got submitted");
+		assert_eq!(submitted.values.text("name"), Some("manen"));
+		// the newline didn't end up in the text
+		assert_eq!(submitted.values.text("email"), Some("manen@example.com"));
+	}
+
+	#[test]
+	fn test_custom_field() {
+		let color = Store::new(crate::color(255, 128, 0, 255));
+		let picker = ColorPicker::new(color.clone(), 100);
+		let uid = picker.uid();
+		let mut form = Form::new(16)
+			.custom_field("color", "Color", picker)
+			.validate("color", |value| match value.as_str() {
+				Some("#000000") => Err("too dark".into()),
+				_ => Ok(()),
+			});
+		assert_eq!(form.values().text("color"), Some("#ff8000"));
+
+		color.set(crate::color(0, 0, 0, 255));
+		let mut ret_events = Vec::new();
+		form.submit(&mut ret_events);
+		assert_eq!(form.error("color"), Some("too dark"));
+		assert_eq!(
+			ret_events[0].peek::<FocusCommand>(),
+			Some(&FocusCommand::Request(uid))
+		);
+	}
+}
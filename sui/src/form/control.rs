@@ -0,0 +1,58 @@
+//! the parts every focusable form control shares
+
+use raylib::ffi::KeyboardKey;
+
+use super::{FocusCommand, UniqueId};
+use crate::{
+	core::{Event, KeyboardEvent, MouseEvent, ReturnEvent},
+	Color, Details,
+};
+
+pub const ACCENT: Color = crate::color(110, 170, 255, 255);
+pub const INACTIVE: Color = crate::color(70, 70, 70, 255);
+pub const OUTLINE: Color = crate::color(200, 200, 200, 255);
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+/// returned by form controls every time the user changes their value
+pub struct Changed<T> {
+	/// the control the value changed in
+	pub uid: UniqueId,
+	pub value: T,
+}
+
+/// whether `event` is enter or space being pressed while `uid` is focused
+pub(crate) fn is_activate_key(event: &Event, uid: UniqueId) -> bool {
+	matches!(
+		event,
+		Event::KeyboardEvent(
+			focused,
+			KeyboardEvent::KeyPressed(
+				KeyboardKey::KEY_ENTER | KeyboardKey::KEY_KP_ENTER | KeyboardKey::KEY_SPACE
+			)
+		) if *focused == uid
+	)
+}
+/// the key pressed while `uid` is focused, if any
+pub(crate) fn pressed_key(event: &Event, uid: UniqueId) -> Option<KeyboardKey> {
+	match event {
+		Event::KeyboardEvent(focused, KeyboardEvent::KeyPressed(key)) if *focused == uid => {
+			Some(*key)
+		}
+		_ => None,
+	}
+}
+/// if `event` is a click inside `bounds`, requests focus for `uid` and returns where the click was
+pub(crate) fn click_to_focus(
+	event: &Event,
+	bounds: Details,
+	uid: UniqueId,
+	ret_events: &mut Vec<ReturnEvent>,
+) -> Option<(i32, i32)> {
+	match event {
+		Event::MouseEvent(MouseEvent::MouseClick { x, y }) if bounds.is_inside(*x, *y) => {
+			ret_events.push(Event::ret(FocusCommand::Request(uid)));
+			Some((*x, *y))
+		}
+		_ => None,
+	}
+}
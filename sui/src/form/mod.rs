@@ -4,6 +4,39 @@ pub use typable::Typable;
 pub mod textbox;
 pub use textbox::textbox;
 
+pub mod selection;
+pub use selection::Selection;
+
+pub mod control;
+pub use control::Changed;
+
+pub mod checkbox;
+pub use checkbox::{Checkbox, CheckboxLook};
+
+pub mod radio;
+pub use radio::RadioGroup;
+
+pub mod slider;
+pub use slider::{Slider, SliderOrientation};
+
+pub mod select;
+pub use select::Select;
+
+pub mod spinner;
+pub use spinner::{FloatSpinner, IntSpinner, Spinner};
+
+pub mod color_picker;
+pub use color_picker::ColorPicker;
+
+pub mod container;
+pub use container::{FieldError, FieldValue, Form, FormField, FormValues, Submitted};
+pub mod validators;
+
+#[cfg(feature = "serde")]
+pub mod settings;
+#[cfg(feature = "serde")]
+pub use settings::{SettingsError, SettingsForm, SettingsFormBuilder};
+
 use crate::core::Store;
 
 // i don't know if this is the appropriate place for the focus implementation
@@ -0,0 +1,174 @@
+use raylib::{ffi::KeyboardKey, prelude::RaylibDraw};
+
+use super::{
+	control::{self, Changed},
+	UniqueId,
+};
+use crate::{
+	core::{Event, ReturnEvent, Store},
+	Details, Layable, Text,
+};
+
+#[derive(Clone, Debug)]
+/// a list of options where exactly one is selected, bound to a `Store<T>` (usually an enum). \
+/// clicking an option selects it, the arrow keys move the selection while the group is focused. \
+/// every change returns a [Changed<T>]
+pub struct RadioGroup<T: Clone + PartialEq + 'static> {
+	store: Store<T>,
+	uid: UniqueId,
+	options: Vec<(T, Text<'static>)>,
+	/// diameter of the circles, unscaled
+	size: i32,
+	/// space between options, unscaled
+	gap: i32,
+}
+impl<T: Clone + PartialEq + 'static> RadioGroup<T> {
+	pub fn new(store: Store<T>, size: i32) -> Self {
+		Self {
+			store,
+			uid: UniqueId::new(),
+			options: Vec::new(),
+			size,
+			gap: size / 2,
+		}
+	}
+	pub fn option(mut self, value: T, label: Text<'static>) -> Self {
+		self.options.push((value, label));
+		self
+	}
+	pub fn with_gap(self, gap: i32) -> Self {
+		Self { gap, ..self }
+	}
+
+	pub fn uid(&self) -> UniqueId {
+		self.uid
+	}
+	pub fn store(&self) -> &Store<T> {
+		&self.store
+	}
+
+	fn selected_index(&self) -> Option<usize> {
+		self.store
+			.with_borrow(|value| self.options.iter().position(|(opt, _)| opt == value))
+	}
+	fn select(&mut self, i: usize, ret_events: &mut Vec<ReturnEvent>) {
+		let Some((value, _)) = self.options.get(i) else {
+			return;
+		};
+		if self.selected_index() == Some(i) {
+			return;
+		}
+		self.store.set(value.clone());
+		ret_events.push(Event::ret(Changed {
+			uid: self.uid,
+			value: value.clone(),
+		}));
+	}
+
+	fn bounds(&self, det: Details, scale: f32) -> Details {
+		let (w, h) = self.size();
+		Details {
+			aw: (w as f32 * scale) as i32,
+			ah: (h as f32 * scale) as i32,
+			..det
+		}
+	}
+	/// unscaled height of a row
+	fn row_h(&self, label: &Text) -> i32 {
+		label.size().1.max(self.size)
+	}
+	/// (index, det) of every option, scaled
+	fn rows(&self, det: Details, scale: f32) -> Vec<(usize, Details)> {
+		let mut y = det.y;
+		let mut rows = Vec::with_capacity(self.options.len());
+		for (i, (_, label)) in self.options.iter().enumerate() {
+			let (w, _) = label.size();
+			let h = self.row_h(label);
+			let row = Details {
+				x: det.x,
+				y,
+				aw: ((self.size + self.size / 2 + w) as f32 * scale) as i32,
+				ah: (h as f32 * scale) as i32,
+			};
+			rows.push((i, row));
+			y += ((h + self.gap) as f32 * scale) as i32;
+		}
+		rows
+	}
+}
+
+impl<T: Clone + PartialEq + 'static> Layable for RadioGroup<T> {
+	fn size(&self) -> (i32, i32) {
+		let (mut w, mut h) = (0, 0);
+		for (_, label) in self.options.iter() {
+			w = w.max(self.size + self.size / 2 + label.size().0);
+			h += self.row_h(label) + self.gap;
+		}
+		(w, (h - self.gap).max(0))
+	}
+	fn render(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		let selected = self.selected_index();
+		let focused = d.focus() == self.uid;
+		let radius = self.size as f32 * scale / 2.0;
+
+		for (i, row) in self.rows(det, scale) {
+			let (cx, cy) = (row.x as f32 + radius, row.y as f32 + row.ah as f32 / 2.0);
+			let outline = if focused && selected == Some(i) {
+				control::ACCENT
+			} else {
+				control::OUTLINE
+			};
+			d.draw_circle_lines(cx as i32, cy as i32, radius, outline);
+			if selected == Some(i) {
+				d.draw_circle(cx as i32, cy as i32, radius * 0.55, control::ACCENT);
+			}
+
+			let label = &self.options[i].1;
+			let offset = ((self.size + self.size / 2) as f32 * scale) as i32;
+			let label_h = (label.size().1 as f32 * scale) as i32;
+			let label_det = Details {
+				x: row.x + offset,
+				y: row.y + (row.ah - label_h) / 2,
+				aw: row.aw - offset,
+				ah: label_h,
+			};
+			label.render(d, label_det, scale);
+		}
+	}
+
+	fn pass_events(
+		&mut self,
+		events: impl Iterator<Item = Event>,
+		det: Details,
+		scale: f32,
+		ret_events: &mut Vec<ReturnEvent>,
+	) {
+		let rows = self.rows(det, scale);
+		for event in events {
+			if let Some((x, y)) =
+				control::click_to_focus(&event, self.bounds(det, scale), self.uid, ret_events)
+			{
+				if let Some((i, _)) = rows.iter().find(|(_, row)| row.is_inside(x, y)) {
+					self.select(*i, ret_events);
+				}
+				continue;
+			}
+
+			let len = self.options.len();
+			if len == 0 {
+				continue;
+			}
+			let current = self.selected_index();
+			let next = match control::pressed_key(&event, self.uid) {
+				Some(KeyboardKey::KEY_DOWN | KeyboardKey::KEY_RIGHT) => {
+					current.map(|i| (i + 1) % len).unwrap_or(0)
+				}
+				Some(KeyboardKey::KEY_UP | KeyboardKey::KEY_LEFT) => {
+					current.map(|i| (i + len - 1) % len).unwrap_or(len - 1)
+				}
+				_ => continue,
+			};
+			self.select(next, ret_events);
+		}
+	}
+}
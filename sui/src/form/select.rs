@@ -0,0 +1,438 @@
+use std::time::{Duration, Instant};
+
+use raylib::{ffi::KeyboardKey, math::Vector2, prelude::RaylibDraw};
+
+use super::{
+	control::{self, Changed},
+	FocusCommand, UniqueId,
+};
+use crate::{
+	comp::fit::scrollable::SCROLLBAR_WIDTH,
+	core::{Event, KeyboardEvent, LayerEvent, MouseEvent, ReturnEvent, Store},
+	Color, Details, Layable, Text,
+};
+
+const PADDING: i32 = 4;
+const BACKGROUND: Color = crate::color(40, 40, 40, 255);
+const POPUP_BACKGROUND: Color = crate::color(30, 30, 30, 255);
+const HIGHLIGHT: Color = crate::color(60, 80, 110, 255);
+/// type-ahead starts over if nothing's been typed for this long
+const TYPEAHEAD_TIMEOUT: Duration = Duration::from_millis(1000);
+
+#[derive(Clone, Debug)]
+/// a dropdown bound to a `Store<T>`. shows the selected option, and clicking it opens a list of every option
+/// in a [layer](crate::layer), scrollable if there's more than `max_visible`. \
+/// while focused, enter, space or the arrow keys open the list, the arrow keys, page up/down and home/end move
+/// around in it, enter or space picks the highlighted option and escape closes it.
+/// typing jumps to the first option starting with what's been typed. \
+/// every change returns a [Changed<T>]
+pub struct Select<T: Clone + PartialEq + 'static> {
+	store: Store<T>,
+	uid: UniqueId,
+	options: Vec<(T, Text<'static>)>,
+	max_visible: usize,
+
+	open: bool,
+	highlighted: usize,
+	/// index of the first option visible in the list
+	scroll: usize,
+	typeahead: String,
+	last_typed: Option<Instant>,
+}
+impl<T: Clone + PartialEq + 'static> Select<T> {
+	pub fn new(store: Store<T>) -> Self {
+		Self {
+			store,
+			uid: UniqueId::new(),
+			options: Vec::new(),
+			max_visible: 8,
+			open: false,
+			highlighted: 0,
+			scroll: 0,
+			typeahead: String::new(),
+			last_typed: None,
+		}
+	}
+	pub fn option(mut self, value: T, label: Text<'static>) -> Self {
+		self.options.push((value, label));
+		self
+	}
+	/// the list gets a scrollbar if there's more options than this
+	pub fn with_max_visible(self, max_visible: usize) -> Self {
+		Self {
+			max_visible: max_visible.max(1),
+			..self
+		}
+	}
+
+	pub fn uid(&self) -> UniqueId {
+		self.uid
+	}
+	pub fn store(&self) -> &Store<T> {
+		&self.store
+	}
+	pub fn is_open(&self) -> bool {
+		self.open
+	}
+
+	fn selected_index(&self) -> Option<usize> {
+		self.store
+			.with_borrow(|value| self.options.iter().position(|(opt, _)| opt == value))
+	}
+	fn open_list(&mut self) {
+		self.open = true;
+		self.highlighted = self.selected_index().unwrap_or(0);
+		self.scroll_to_highlighted();
+	}
+	fn close_list(&mut self) {
+		self.open = false;
+	}
+	fn choose(&mut self, i: usize, ret_events: &mut Vec<ReturnEvent>) {
+		self.close_list();
+		let Some((value, _)) = self.options.get(i) else {
+			return;
+		};
+		self.highlighted = i;
+		if self.selected_index() == Some(i) {
+			return;
+		}
+		self.store.set(value.clone());
+		ret_events.push(Event::ret(Changed {
+			uid: self.uid,
+			value: value.clone(),
+		}));
+	}
+	fn highlight(&mut self, i: usize) {
+		self.highlighted = i.min(self.options.len().saturating_sub(1));
+		self.scroll_to_highlighted();
+	}
+	fn scroll_to_highlighted(&mut self) {
+		if self.highlighted < self.scroll {
+			self.scroll = self.highlighted;
+		} else if self.highlighted >= self.scroll + self.max_visible {
+			self.scroll = self.highlighted + 1 - self.max_visible;
+		}
+	}
+	fn scroll_by(&mut self, rows: isize) {
+		let max = self.options.len().saturating_sub(self.max_visible);
+		self.scroll = self.scroll.saturating_add_signed(rows).min(max);
+	}
+
+	fn typing(&self) -> bool {
+		!self.typeahead.is_empty()
+			&& self
+				.last_typed
+				.is_some_and(|last| last.elapsed() < TYPEAHEAD_TIMEOUT)
+	}
+	fn type_ahead(&mut self, c: char, ret_events: &mut Vec<ReturnEvent>) {
+		if !self.typing() {
+			self.typeahead.clear();
+		}
+		// space is for opening and picking unless we're already typing
+		if c.is_control() || (c == ' ' && self.typeahead.is_empty()) {
+			return;
+		}
+		self.typeahead.push(c);
+		self.last_typed = Some(Instant::now());
+
+		let current = if self.open {
+			self.highlighted
+		} else {
+			self.selected_index().unwrap_or(0)
+		};
+		let labels = self.options.iter().map(|(_, label)| label.text.as_ref());
+		let Some(i) = typeahead_match(labels, &self.typeahead, current) else {
+			return;
+		};
+		if self.open {
+			self.highlight(i);
+		} else {
+			self.choose(i, ret_events);
+		}
+	}
+
+	/// unscaled height of one option
+	fn row_h(&self) -> i32 {
+		let text_h = self
+			.options
+			.iter()
+			.map(|(_, label)| label.size().1)
+			.max()
+			.unwrap_or(0);
+		text_h + PADDING * 2
+	}
+	fn bounds(&self, det: Details, scale: f32) -> Details {
+		let (w, h) = self.size();
+		Details {
+			aw: (w as f32 * scale) as i32,
+			ah: (h as f32 * scale) as i32,
+			..det
+		}
+	}
+	/// where the list is, in window coords. \
+	/// under the select if it fits on the screen, above it if it doesn't
+	fn popup_det(&self, det: Details, scale: f32) -> Details {
+		let bounds = self.bounds(det, scale);
+		let shown = self.options.len().min(self.max_visible) as i32;
+		let h = (self.row_h() as f32 * scale) as i32 * shown;
+
+		let screen_h = unsafe { raylib::ffi::GetScreenHeight() };
+		let y = if bounds.y + bounds.ah + h > screen_h && bounds.y - h >= 0 {
+			bounds.y - h
+		} else {
+			bounds.y + bounds.ah
+		};
+		Details {
+			x: bounds.x,
+			y,
+			aw: bounds.aw,
+			ah: h,
+		}
+	}
+	fn option_at(&self, popup: Details, scale: f32, y: i32) -> Option<usize> {
+		let row_h = ((self.row_h() as f32 * scale) as i32).max(1);
+		let i = self.scroll + ((y - popup.y) / row_h) as usize;
+		(y >= popup.y && i < self.options.len()).then_some(i)
+	}
+}
+
+impl<T: Clone + PartialEq + 'static> Layable for Select<T> {
+	fn size(&self) -> (i32, i32) {
+		let row_h = self.row_h();
+		let text_w = self
+			.options
+			.iter()
+			.map(|(_, label)| label.size().0)
+			.max()
+			.unwrap_or(0);
+		// the arrow is row_h / 2 wide
+		(text_w + PADDING * 3 + row_h / 2, row_h)
+	}
+	fn render(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		let focused = d.focus() == self.uid;
+		let bounds = self.bounds(det, scale);
+		let padding = (PADDING as f32 * scale) as i32;
+
+		d.draw_rectangle(bounds.x, bounds.y, bounds.aw, bounds.ah, BACKGROUND);
+		let outline = if focused || self.open {
+			control::ACCENT
+		} else {
+			control::OUTLINE
+		};
+		d.draw_rectangle_lines(bounds.x, bounds.y, bounds.aw, bounds.ah, outline);
+
+		if let Some(i) = self.selected_index() {
+			let label = &self.options[i].1;
+			let label_det = Details {
+				x: bounds.x + padding,
+				y: bounds.y + padding,
+				aw: bounds.aw - padding * 2,
+				ah: bounds.ah - padding * 2,
+			};
+			label.render(d, label_det, scale);
+		}
+
+		let arrow_w = bounds.ah as f32 / 2.0;
+		let arrow_x = (bounds.x + bounds.aw - padding) as f32 - arrow_w;
+		let arrow_y = bounds.y as f32 + bounds.ah as f32 / 2.0;
+		d.draw_triangle(
+			Vector2::new(arrow_x + arrow_w / 2.0, arrow_y + arrow_w / 4.0),
+			Vector2::new(arrow_x + arrow_w, arrow_y - arrow_w / 4.0),
+			Vector2::new(arrow_x, arrow_y - arrow_w / 4.0),
+			outline,
+		);
+
+		if !self.open {
+			return;
+		}
+
+		// the layer gets rendered later, so it needs its own copy of everything
+		let popup = self.popup_det(det, scale);
+		let row_h = (self.row_h() as f32 * scale) as i32;
+		let shown = self.options.len().min(self.max_visible);
+		let rows = self
+			.options
+			.iter()
+			.enumerate()
+			.skip(self.scroll)
+			.take(shown)
+			.map(|(i, (_, label))| (i, label.clone()))
+			.collect::<Vec<_>>();
+		let (len, scroll) = (self.options.len(), self.scroll);
+		let (selected, highlighted) = (self.selected_index(), self.highlighted);
+
+		d.layer(self.uid, popup, move |d| {
+			d.draw_rectangle(popup.x, popup.y, popup.aw, popup.ah, POPUP_BACKGROUND);
+
+			for (row, (i, label)) in rows.iter().enumerate() {
+				let y = popup.y + row as i32 * row_h;
+				if *i == highlighted {
+					d.draw_rectangle(popup.x, y, popup.aw, row_h, HIGHLIGHT);
+				}
+				if Some(*i) == selected {
+					d.draw_rectangle(popup.x, y, padding / 2, row_h, control::ACCENT);
+				}
+				let label_det = Details {
+					x: popup.x + padding,
+					y: y + padding,
+					aw: popup.aw - padding * 2,
+					ah: row_h - padding * 2,
+				};
+				label.render(d, label_det, scale);
+			}
+
+			if len > shown {
+				let bar_w = (SCROLLBAR_WIDTH * scale / 2.0) as i32;
+				let handle_h = popup.ah * shown as i32 / len as i32;
+				let handle_y = popup.y + popup.ah * scroll as i32 / len as i32;
+				d.draw_rectangle(
+					popup.x + popup.aw - bar_w,
+					handle_y,
+					bar_w,
+					handle_h,
+					control::OUTLINE,
+				);
+			}
+
+			d.draw_rectangle_lines(popup.x, popup.y, popup.aw, popup.ah, control::OUTLINE);
+		});
+	}
+
+	fn pass_events(
+		&mut self,
+		events: impl Iterator<Item = Event>,
+		det: Details,
+		scale: f32,
+		ret_events: &mut Vec<ReturnEvent>,
+	) {
+		let bounds = self.bounds(det, scale);
+		for event in events {
+			match event {
+				Event::MouseEvent(MouseEvent::MouseClick { x, y }) if bounds.is_inside(x, y) => {
+					ret_events.push(Event::ret(FocusCommand::Request(self.uid)));
+					if self.open {
+						self.close_list();
+					} else {
+						self.open_list();
+					}
+				}
+				Event::LayerEvent(uid, LayerEvent::ClickedOutside { x, y }) if uid == self.uid => {
+					if !bounds.is_inside(x, y) {
+						self.close_list();
+					}
+				}
+				Event::LayerEvent(uid, LayerEvent::Mouse(m_event)) if uid == self.uid => {
+					let popup = self.popup_det(det, scale);
+					match m_event {
+						MouseEvent::MouseMove { y, .. } => {
+							if let Some(i) = self.option_at(popup, scale, y) {
+								self.highlighted = i;
+							}
+						}
+						MouseEvent::MouseClick { y, .. } => {
+							if let Some(i) = self.option_at(popup, scale, y) {
+								self.choose(i, ret_events);
+							}
+						}
+						MouseEvent::Scroll { amount, .. } => {
+							self.scroll_by(-amount.signum() as isize)
+						}
+						_ => {}
+					}
+				}
+				Event::KeyboardEvent(uid, KeyboardEvent::CharPressed(c)) if uid == self.uid => {
+					self.type_ahead(c, ret_events)
+				}
+				event => {
+					let Some(key) = control::pressed_key(&event, self.uid) else {
+						continue;
+					};
+					if key == KeyboardKey::KEY_SPACE && self.typing() {
+						continue;
+					}
+					if !self.open {
+						if let KeyboardKey::KEY_ENTER
+						| KeyboardKey::KEY_KP_ENTER
+						| KeyboardKey::KEY_SPACE
+						| KeyboardKey::KEY_DOWN
+						| KeyboardKey::KEY_UP = key
+						{
+							self.open_list();
+						}
+						continue;
+					}
+
+					let page = self.max_visible;
+					match key {
+						KeyboardKey::KEY_DOWN => self.highlight(self.highlighted + 1),
+						KeyboardKey::KEY_UP => self.highlight(self.highlighted.saturating_sub(1)),
+						KeyboardKey::KEY_PAGE_DOWN => self.highlight(self.highlighted + page),
+						KeyboardKey::KEY_PAGE_UP => {
+							self.highlight(self.highlighted.saturating_sub(page))
+						}
+						KeyboardKey::KEY_HOME => self.highlight(0),
+						KeyboardKey::KEY_END => self.highlight(usize::MAX),
+						KeyboardKey::KEY_ENTER
+						| KeyboardKey::KEY_KP_ENTER
+						| KeyboardKey::KEY_SPACE => self.choose(self.highlighted, ret_events),
+						KeyboardKey::KEY_ESCAPE | KeyboardKey::KEY_TAB => self.close_list(),
+						_ => {}
+					}
+				}
+			}
+		}
+	}
+}
+
+/// the option `query` should jump to, given the one at `current`. case-insensitive. \
+/// typing the same letter over and over cycles through the options starting with it
+pub fn typeahead_match<'a>(
+	labels: impl Iterator<Item = &'a str>,
+	query: &str,
+	current: usize,
+) -> Option<usize> {
+	let query = query.to_lowercase();
+	let labels = labels.map(str::to_lowercase).collect::<Vec<_>>();
+	let len = labels.len();
+	if len == 0 || query.is_empty() {
+		return None;
+	}
+
+	let mut chars = query.chars();
+	let first = chars.next()?;
+	let (query, start) = if chars.all(|c| c == first) {
+		// cycling, so skip the current one
+		(first.to_string(), current + 1)
+	} else {
+		(query, current)
+	};
+
+	(0..len)
+		.map(|offset| (start + offset) % len)
+		.find(|&i| labels[i].starts_with(&query))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_typeahead_match() {
+		let labels = ["Apple", "Banana", "Blueberry", "Cherry", "blackberry"];
+		let labels = || labels.iter().copied();
+
+		assert_eq!(typeahead_match(labels(), "c", 0), Some(3));
+		assert_eq!(typeahead_match(labels(), "bl", 0), Some(2));
+		assert_eq!(typeahead_match(labels(), "bla", 0), Some(4));
+		assert_eq!(typeahead_match(labels(), "x", 0), None);
+
+		// cycling through the b's
+		assert_eq!(typeahead_match(labels(), "b", 0), Some(1));
+		assert_eq!(typeahead_match(labels(), "bb", 1), Some(2));
+		assert_eq!(typeahead_match(labels(), "bbb", 2), Some(4));
+		assert_eq!(typeahead_match(labels(), "bbbb", 4), Some(1));
+
+		// a longer query can stay on the current one
+		assert_eq!(typeahead_match(labels(), "blu", 2), Some(2));
+	}
+}
@@ -0,0 +1,111 @@
+use std::ops::Range;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+/// a range of text selected with a cursor, as byte indices. \
+/// `anchor` is where the selection started, `cursor` is where it's being extended to, so
+/// `cursor` can be before `anchor`. \
+/// used by both [Typable](super::Typable) and [Selectable](crate::comp::text::Selectable)
+pub struct Selection {
+	pub anchor: usize,
+	pub cursor: usize,
+}
+impl Selection {
+	pub fn new(anchor: usize, cursor: usize) -> Self {
+		Self { anchor, cursor }
+	}
+	/// nothing selected, cursor at `at`
+	pub fn caret(at: usize) -> Self {
+		Self::new(at, at)
+	}
+	pub fn all(text: &str) -> Self {
+		Self::new(0, text.len())
+	}
+	/// the word `at` is in, see [word_at]
+	pub fn word(text: &str, at: usize) -> Self {
+		let word = word_at(text, at);
+		Self::new(word.start, word.end)
+	}
+
+	/// moves the cursor, keeping the anchor where it is
+	pub fn extend_to(self, cursor: usize) -> Self {
+		Self { cursor, ..self }
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.anchor == self.cursor
+	}
+	/// the selected range, start always before end
+	pub fn range(&self) -> Range<usize> {
+		self.anchor.min(self.cursor)..self.anchor.max(self.cursor)
+	}
+	/// both ends clamped to `text`, and to char boundaries
+	pub fn clamp(self, text: &str) -> Self {
+		Self::new(
+			floor_char_boundary(text, self.anchor),
+			floor_char_boundary(text, self.cursor),
+		)
+	}
+	/// the selected part of `text`. clamped to `text`, and to char boundaries
+	pub fn slice<'a>(&self, text: &'a str) -> &'a str {
+		&text[self.clamp(text).range()]
+	}
+}
+
+fn floor_char_boundary(text: &str, i: usize) -> usize {
+	let mut i = i.min(text.len());
+	while !text.is_char_boundary(i) {
+		i -= 1;
+	}
+	i
+}
+
+/// the range of the word at byte index `at`. \
+/// a word is a run of alphanumeric characters (and `_`), any other character is a word of its own
+pub fn word_at(text: &str, at: usize) -> Range<usize> {
+	let at = floor_char_boundary(text, at);
+	let is_word = |c: char| c.is_alphanumeric() || c == '_';
+
+	let Some(c) = text[at..].chars().next() else {
+		return at..at;
+	};
+	if !is_word(c) {
+		return at..at + c.len_utf8();
+	}
+
+	let start = text[..at]
+		.char_indices()
+		.rev()
+		.take_while(|(_, c)| is_word(*c))
+		.last()
+		.map(|(i, _)| i)
+		.unwrap_or(at);
+	let end = text[at..]
+		.char_indices()
+		.find(|(_, c)| !is_word(*c))
+		.map(|(i, _)| at + i)
+		.unwrap_or(text.len());
+	start..end
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_word_at() {
+		let text = "error: id_4f2 not found";
+		assert_eq!(&text[word_at(text, 2)], "error");
+		assert_eq!(&text[word_at(text, 5)], ":");
+		assert_eq!(&text[word_at(text, 9)], "id_4f2");
+		assert_eq!(word_at(text, text.len()), text.len()..text.len());
+	}
+
+	#[test]
+	fn test_slice() {
+		let text = "árvíztűrő";
+		// backwards selections and indices inside a char both work
+		assert_eq!(Selection::new(4, 0).slice(text), "árv");
+		assert_eq!(Selection::new(0, 1).slice(text), "");
+		assert_eq!(Selection::new(0, 100).slice(text), text);
+	}
+}
@@ -0,0 +1,364 @@
+//! forms built straight from a settings struct, see [SettingsForm]. \
+//! requires the `serde` feature
+
+use std::borrow::Cow;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use reflect::Kind;
+
+use super::{typable::TypableData, Changed, FieldError, FieldValue, Form, Submitted};
+use crate::{
+	core::{Event, ReturnEvent, Store},
+	Details, Layable,
+};
+
+pub mod reflect;
+pub use reflect::SettingsError;
+
+#[derive(Clone, Debug, Default)]
+/// everything that can be said about a field that the struct itself can't
+struct Attrs {
+	label: Option<Cow<'static, str>>,
+	range: Option<(f64, f64)>,
+	step: Option<f64>,
+	slider: bool,
+}
+
+#[derive(Clone, Debug)]
+/// sets up a [SettingsForm]. fields are configured by their serde name (so after any `rename`s)
+pub struct SettingsFormBuilder<T> {
+	store: Store<T>,
+	text_size: i32,
+	submit_label: Cow<'static, str>,
+	attrs: Vec<(&'static str, Attrs)>,
+}
+impl<T: Serialize + DeserializeOwned + Clone + 'static> SettingsFormBuilder<T> {
+	fn attrs(&mut self, field: &'static str) -> &mut Attrs {
+		let i = match self.attrs.iter().position(|(name, _)| *name == field) {
+			Some(i) => i,
+			None => {
+				self.attrs.push((field, Attrs::default()));
+				self.attrs.len() - 1
+			}
+		};
+		&mut self.attrs[i].1
+	}
+
+	/// by default the label is the field's name with underscores turned into spaces
+	pub fn label(mut self, field: &'static str, label: impl Into<Cow<'static, str>>) -> Self {
+		self.attrs(field).label = Some(label.into());
+		self
+	}
+	/// limits a number field to `min..=max`. without this it can be anything its type can hold
+	pub fn range(mut self, field: &'static str, min: f64, max: f64) -> Self {
+		self.attrs(field).range = Some((min, max));
+		self
+	}
+	/// how much a number field changes per arrow key press. 1 for integers and 0.1 for floats by default
+	pub fn step(mut self, field: &'static str, step: f64) -> Self {
+		self.attrs(field).step = Some(step);
+		self
+	}
+	/// shows a number field as a [Slider](super::Slider) going from `min` to `max` instead of a spinner
+	pub fn slider(mut self, field: &'static str, min: f64, max: f64) -> Self {
+		let attrs = self.attrs(field);
+		attrs.range = Some((min, max));
+		attrs.slider = true;
+		self
+	}
+	pub fn submit_label(self, submit_label: impl Into<Cow<'static, str>>) -> Self {
+		Self {
+			submit_label: submit_label.into(),
+			..self
+		}
+	}
+
+	/// errors if `T` isn't a struct or has a field that isn't a bool, number, string or an enum without data,
+	/// or if a field that was configured isn't in `T`
+	pub fn build(self) -> Result<SettingsForm<T>, SettingsError> {
+		let schema = reflect::schema::<T>()?;
+		let unknown = (self.attrs.iter()).find(|(name, _)| !schema.iter().any(|f| f.name == *name));
+		if let Some((name, _)) = unknown {
+			return Err(SettingsError(format!(
+				"{name:?} was configured, but there's no field called that"
+			)));
+		}
+		let values = self.store.with_borrow(reflect::to_values)?;
+		reflect::check_variants::<T>(&schema, &values)?;
+
+		let mut form = Form::new(self.text_size).with_submit_label(self.submit_label);
+		let mut fields = Vec::with_capacity(schema.len());
+		for field in schema {
+			let name = field.name;
+			let Some((_, value)) = values.iter().find(|(n, _)| *n == name) else {
+				return Err(SettingsError(format!("{name:?} doesn't get serialized")));
+			};
+			let attrs = (self.attrs.iter())
+				.find(|(n, _)| *n == name)
+				.map(|(_, attrs)| attrs.clone())
+				.unwrap_or_default();
+			let label = attrs
+				.label
+				.clone()
+				.unwrap_or_else(|| Cow::Owned(default_label(name)));
+
+			let store = FieldStore::new(&field.kind, &attrs, value);
+			form = match (&store, &field.kind) {
+				(FieldStore::Bool(store), _) => form.check_field(name, label, store.clone()),
+				(FieldStore::Text(data), _) => form.text_field(name, label, data.clone()),
+				(FieldStore::Choice(store), Kind::Enum(variants)) => {
+					form.select_field(name, label, store.clone(), variants.iter().copied())
+				}
+				(FieldStore::Int(store), Kind::Int { min, max }) => {
+					let (min, max) = (attrs.range)
+						.map(|(min, max)| (min as i64, max as i64))
+						.unwrap_or((*min, *max));
+					let step = (attrs.step.unwrap_or(1.0) as i64).max(1);
+					form.int_field(name, label, store.clone(), min, max, step)
+				}
+				(FieldStore::Float(store), Kind::Float { min, max }) => {
+					let (min, max) = attrs.range.unwrap_or((*min, *max));
+					let step = attrs.step.unwrap_or(0.1);
+					form.float_field(name, label, store.clone(), min, max, step)
+				}
+				(FieldStore::Slider(store), kind) => {
+					let (min, max) = attrs.range.unwrap_or((0.0, 1.0));
+					let step = match kind {
+						Kind::Int { .. } => attrs.step.unwrap_or(1.0),
+						_ => attrs.step.unwrap_or(0.0),
+					};
+					let (min, max, step) = (min as f32, max as f32, step as f32);
+					form.slider_field(name, label, store.clone(), min, max, step)
+				}
+				_ => unreachable!("FieldStore::new picks the store from the kind"),
+			};
+			fields.push((name, store));
+		}
+
+		// so a submit can't go through if the values can't be turned back into a `T`
+		let form = form.validate_form(|values| {
+			reflect::from_values::<T>(values.iter())
+				.map(|_| ())
+				.map_err(|err| FieldError::new("", err.to_string()))
+		});
+		Ok(SettingsForm {
+			store: self.store,
+			form,
+			fields,
+			shown: values,
+		})
+	}
+}
+
+/// where a field's widget keeps its value
+#[derive(Clone, Debug)]
+enum FieldStore {
+	Bool(Store<bool>),
+	Text(Store<TypableData>),
+	Int(Store<i64>),
+	Float(Store<f64>),
+	Slider(Store<f32>),
+	/// the variant's name
+	Choice(Store<String>),
+}
+impl FieldStore {
+	fn new(kind: &Kind, attrs: &Attrs, value: &FieldValue) -> Self {
+		match (kind, value) {
+			(_, FieldValue::Bool(b)) => Self::Bool(Store::new(*b)),
+			(Kind::Enum(_), FieldValue::Text(variant)) => Self::Choice(Store::new(variant.clone())),
+			(_, FieldValue::Text(text)) => {
+				Self::Text(Store::new(TypableData::with_default(text.clone())))
+			}
+			(_, value) if attrs.slider => {
+				Self::Slider(Store::new(value.as_float().unwrap_or_default() as f32))
+			}
+			(_, FieldValue::Int(i)) => Self::Int(Store::new(*i)),
+			(_, FieldValue::Float(f)) => Self::Float(Store::new(*f)),
+		}
+	}
+	/// shows `value` without making a new widget
+	fn set(&self, value: &FieldValue) {
+		match (self, value) {
+			(Self::Bool(store), FieldValue::Bool(b)) => {
+				store.set(*b);
+			}
+			(Self::Text(data), FieldValue::Text(text)) => {
+				data.with_mut_borrow(|data| data.set_text(text.clone()))
+			}
+			(Self::Choice(store), FieldValue::Text(variant)) => {
+				store.set(variant.clone());
+			}
+			(Self::Int(store), FieldValue::Int(i)) => {
+				store.set(*i);
+			}
+			(Self::Float(store), value) => {
+				store.set(value.as_float().unwrap_or_default());
+			}
+			(Self::Slider(store), value) => {
+				store.set(value.as_float().unwrap_or_default() as f32);
+			}
+			_ => {}
+		}
+	}
+}
+
+#[derive(Clone, Debug)]
+/// a [Form] made out of a struct's fields, reading from and writing back to a `Store<T>`. \
+/// `String`s become text fields, `bool`s checkboxes, numbers spinners (or sliders, see
+/// [SettingsFormBuilder::slider]) and enums without data selects, with their variants as the options. \
+/// when submitted, the store is set to the new `T` and a [Changed<T>] is returned. \
+/// if the store gets changed from the outside, the fields follow it
+///
+/// ```ignore
+/// #[derive(Clone, Serialize, Deserialize)]
+/// struct Settings {
+/// 	player_name: String,
+/// 	vsync: bool,
+/// 	volume: f32,
+/// 	mode: WindowMode,
+/// }
+///
+/// let form = SettingsForm::builder(settings.clone(), 16)
+/// 	.slider("volume", 0.0, 1.0)
+/// 	.label("mode", "Window mode")
+/// 	.build()?;
+/// ```
+pub struct SettingsForm<T> {
+	store: Store<T>,
+	form: Form,
+	fields: Vec<(&'static str, FieldStore)>,
+	/// the values the fields were last set from
+	shown: Vec<(&'static str, FieldValue)>,
+}
+impl<T: Serialize + DeserializeOwned + Clone + 'static> SettingsForm<T> {
+	pub fn builder(store: Store<T>, text_size: i32) -> SettingsFormBuilder<T> {
+		SettingsFormBuilder {
+			store,
+			text_size,
+			submit_label: "Save".into(),
+			attrs: Vec::new(),
+		}
+	}
+	/// [Self::builder] with every field left as is
+	pub fn new(store: Store<T>, text_size: i32) -> Result<Self, SettingsError> {
+		Self::builder(store, text_size).build()
+	}
+
+	pub fn store(&self) -> &Store<T> {
+		&self.store
+	}
+	/// the form underneath, for things like its uid and errors
+	pub fn form(&self) -> &Form {
+		&self.form
+	}
+
+	/// throws away anything typed since the last save and shows what's in the store
+	pub fn reload(&mut self) {
+		let Ok(values) = self.store.with_borrow(reflect::to_values) else {
+			return;
+		};
+		for (name, store) in self.fields.iter() {
+			if let Some((_, value)) = values.iter().find(|(n, _)| n == name) {
+				store.set(value);
+			}
+		}
+		self.shown = values;
+	}
+}
+
+/// `"mouse_sensitivity"` -> `"Mouse sensitivity"`
+fn default_label(name: &str) -> String {
+	let name = name.replace('_', " ");
+	let mut chars = name.chars();
+	match chars.next() {
+		Some(first) => first.to_uppercase().chain(chars).collect(),
+		None => name,
+	}
+}
+
+impl<T: Serialize + DeserializeOwned + Clone + 'static> Layable for SettingsForm<T> {
+	fn size(&self) -> (i32, i32) {
+		self.form.size()
+	}
+	fn render(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		self.form.render(d, det, scale)
+	}
+
+	fn tick(&mut self) {
+		self.form.tick();
+
+		let changed = self.store.with_borrow(|value| {
+			reflect::to_values(value).is_ok_and(|values| values != self.shown)
+		});
+		if changed {
+			self.reload();
+		}
+	}
+	fn pass_events(
+		&mut self,
+		events: impl Iterator<Item = Event>,
+		det: Details,
+		scale: f32,
+		ret_events: &mut Vec<ReturnEvent>,
+	) {
+		let mut form_events = Vec::new();
+		self.form.pass_events(events, det, scale, &mut form_events);
+
+		for event in form_events {
+			if !event.can_take::<Submitted>() {
+				ret_events.push(event);
+				continue;
+			}
+			let Some(submitted) = event.take::<Submitted>() else {
+				continue;
+			};
+			// the form validator already made sure this works
+			let Ok(value) = reflect::from_values::<T>(submitted.values.iter()) else {
+				continue;
+			};
+			self.store.set(value.clone());
+			self.reload();
+			ret_events.push(Event::ret(Changed {
+				uid: submitted.uid,
+				value,
+			}));
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde::Deserialize;
+
+	use super::*;
+
+	#[derive(Clone, Serialize, Deserialize)]
+	struct Settings {
+		volume: f32,
+		#[serde(rename = "name")]
+		player: String,
+	}
+
+	#[test]
+	fn test_unknown_field() {
+		let store = Store::new(Settings {
+			volume: 0.5,
+			player: "player".into(),
+		});
+		let build = |builder: SettingsFormBuilder<Settings>| builder.build().map(|_| ());
+		let builder = || SettingsForm::builder(store.clone(), 16);
+
+		assert!(build(builder().slider("volume", 0.0, 1.0).label("name", "Name")).is_ok());
+		// misspelled, and the name before the rename
+		assert!(build(builder().step("volum", 0.1)).is_err());
+		assert!(build(builder().label("player", "Name")).is_err());
+	}
+
+	#[test]
+	fn test_default_label() {
+		assert_eq!(default_label("mouse_sensitivity"), "Mouse sensitivity");
+		assert_eq!(default_label("fov"), "Fov");
+		assert_eq!(default_label(""), "");
+	}
+}
@@ -0,0 +1,684 @@
+//! turning serde structs into form fields and back, without any help from the struct itself. \
+//! the field names and types come from running the struct's `Deserialize` impl against a deserializer that
+//! writes down what it gets asked for, its current values come from running its `Serialize` impl. \
+//! only flat structs of bools, numbers, strings and enums without data are supported
+
+use std::fmt::Display;
+
+use serde::{
+	de::{
+		self,
+		value::{BorrowedStrDeserializer, MapDeserializer},
+		DeserializeOwned, IntoDeserializer, Visitor,
+	},
+	forward_to_deserialize_any,
+	ser::{self, Impossible},
+	Serialize,
+};
+
+use crate::form::FieldValue;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// a struct that can't be turned into a form, or values that can't be turned back into one
+pub struct SettingsError(pub String);
+impl Display for SettingsError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(&self.0)
+	}
+}
+impl std::error::Error for SettingsError {}
+impl ser::Error for SettingsError {
+	fn custom<T: Display>(msg: T) -> Self {
+		Self(msg.to_string())
+	}
+}
+impl de::Error for SettingsError {
+	fn custom<T: Display>(msg: T) -> Self {
+		Self(msg.to_string())
+	}
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// what kind of field a struct field is
+pub enum Kind {
+	Bool,
+	/// bounds of the field's actual type
+	Int {
+		min: i64,
+		max: i64,
+	},
+	Float {
+		min: f64,
+		max: f64,
+	},
+	Text,
+	/// an enum where none of the variants hold data
+	Enum(&'static [&'static str]),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldSchema {
+	pub name: &'static str,
+	pub kind: Kind,
+}
+
+/// every field of `T` in order, with its kind. \
+/// only the first variant of an enum gets checked for data here, see [check_variants]
+pub fn schema<T: DeserializeOwned>() -> Result<Vec<FieldSchema>, SettingsError> {
+	let mut schema = Vec::new();
+	T::deserialize(Probe {
+		schema: &mut schema,
+	})?;
+	Ok(schema)
+}
+
+/// errors if any variant of an enum field holds data. \
+/// every variant gets swapped into `values` in turn and built back into a `T`,
+/// which only works for the variants without data
+pub fn check_variants<T: DeserializeOwned>(
+	schema: &[FieldSchema],
+	values: &[(&'static str, FieldValue)],
+) -> Result<(), SettingsError> {
+	for field in schema {
+		let Kind::Enum(variants) = field.kind else {
+			continue;
+		};
+		for variant in variants.iter() {
+			let variant = FieldValue::Text(variant.to_string());
+			let swapped = values
+				.iter()
+				.map(|(name, value)| match *name == field.name {
+					true => (*name, &variant),
+					false => (*name, value),
+				});
+			from_values::<T>(swapped)
+				.map_err(|_| unsupported(format_args!("{:?}, an enum with data,", field.name)))?;
+		}
+	}
+	Ok(())
+}
+
+/// the current value of every field of `value`, in order
+pub fn to_values<T: Serialize>(
+	value: &T,
+) -> Result<Vec<(&'static str, FieldValue)>, SettingsError> {
+	value.serialize(StructSerializer { values: Vec::new() })
+}
+
+/// builds a `T` out of field values. fields `T` doesn't have are ignored
+pub fn from_values<'a, T: DeserializeOwned>(
+	values: impl Iterator<Item = (&'a str, &'a FieldValue)>,
+) -> Result<T, SettingsError> {
+	let map = MapDeserializer::new(values.map(|(name, value)| (name, ValueDeserializer(value))));
+	T::deserialize(map)
+}
+
+fn unsupported(what: impl Display) -> SettingsError {
+	SettingsError(format!("{what} can't be turned into a form field"))
+}
+fn not_a_struct() -> SettingsError {
+	SettingsError("only structs with named fields can be turned into forms".into())
+}
+/// [StructSerializer] replaces this with which field it was
+fn unsupported_value() -> SettingsError {
+	SettingsError("unsupported value".into())
+}
+
+// --- schema
+
+/// only ever gets asked to deserialize the struct itself
+struct Probe<'s> {
+	schema: &'s mut Vec<FieldSchema>,
+}
+impl<'de, 's> de::Deserializer<'de> for Probe<'s> {
+	type Error = SettingsError;
+
+	fn deserialize_struct<V: Visitor<'de>>(
+		self,
+		_name: &'static str,
+		fields: &'static [&'static str],
+		visitor: V,
+	) -> Result<V::Value, Self::Error> {
+		visitor.visit_map(ProbeFields {
+			fields,
+			next: 0,
+			schema: self.schema,
+		})
+	}
+	fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+		Err(not_a_struct())
+	}
+
+	forward_to_deserialize_any! {
+		bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+		bytes byte_buf option unit unit_struct newtype_struct seq tuple
+		tuple_struct map enum identifier ignored_any
+	}
+}
+
+struct ProbeFields<'s> {
+	fields: &'static [&'static str],
+	next: usize,
+	schema: &'s mut Vec<FieldSchema>,
+}
+impl<'de, 's> de::MapAccess<'de> for ProbeFields<'s> {
+	type Error = SettingsError;
+
+	fn next_key_seed<K: de::DeserializeSeed<'de>>(
+		&mut self,
+		seed: K,
+	) -> Result<Option<K::Value>, Self::Error> {
+		let Some(name) = self.fields.get(self.next) else {
+			return Ok(None);
+		};
+		seed.deserialize(BorrowedStrDeserializer::new(name))
+			.map(Some)
+	}
+	fn next_value_seed<V: de::DeserializeSeed<'de>>(
+		&mut self,
+		seed: V,
+	) -> Result<V::Value, Self::Error> {
+		let name = self.fields[self.next];
+		self.next += 1;
+		seed.deserialize(ProbeField {
+			name,
+			schema: self.schema,
+		})
+	}
+}
+
+/// writes down the kind of the field it gets asked for and hands back a placeholder
+struct ProbeField<'s> {
+	name: &'static str,
+	schema: &'s mut Vec<FieldSchema>,
+}
+impl ProbeField<'_> {
+	fn push(self, kind: Kind) {
+		self.schema.push(FieldSchema {
+			name: self.name,
+			kind,
+		});
+	}
+}
+
+macro_rules! probe_ints {
+	($($method:ident $ty:ty),*) => {$(
+		fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+			self.push(Kind::Int {
+				min: <$ty>::MIN as i64,
+				max: (<$ty>::MAX as u64).min(i64::MAX as u64) as i64,
+			});
+			visitor.visit_i64(0)
+		}
+	)*};
+}
+
+impl<'de, 's> de::Deserializer<'de> for ProbeField<'s> {
+	type Error = SettingsError;
+
+	fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		self.push(Kind::Bool);
+		visitor.visit_bool(false)
+	}
+	probe_ints!(
+		deserialize_i8 i8, deserialize_i16 i16, deserialize_i32 i32, deserialize_i64 i64,
+		deserialize_u8 u8, deserialize_u16 u16, deserialize_u32 u32, deserialize_u64 u64
+	);
+	fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		self.push(Kind::Float {
+			min: f32::MIN as f64,
+			max: f32::MAX as f64,
+		});
+		visitor.visit_f64(0.0)
+	}
+	fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		self.push(Kind::Float {
+			min: f64::MIN,
+			max: f64::MAX,
+		});
+		visitor.visit_f64(0.0)
+	}
+	fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		self.push(Kind::Text);
+		visitor.visit_str("")
+	}
+	fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		self.deserialize_str(visitor)
+	}
+	fn deserialize_enum<V: Visitor<'de>>(
+		self,
+		name: &'static str,
+		variants: &'static [&'static str],
+		visitor: V,
+	) -> Result<V::Value, Self::Error> {
+		let Some(first) = variants.first() else {
+			return Err(unsupported(format_args!("the empty enum {name}")));
+		};
+		self.push(Kind::Enum(variants));
+		// errors if the first variant holds data, check_variants does the rest
+		visitor
+			.visit_enum((*first).into_deserializer())
+			.map_err(|_: SettingsError| unsupported(format_args!("{name}, an enum with data,")))
+	}
+	fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+		Err(unsupported(format_args!("the type of {:?}", self.name)))
+	}
+
+	forward_to_deserialize_any! {
+		i128 u128 char bytes byte_buf option unit unit_struct newtype_struct seq tuple
+		tuple_struct map struct identifier ignored_any
+	}
+}
+
+// --- values out
+
+struct StructSerializer {
+	values: Vec<(&'static str, FieldValue)>,
+}
+impl ser::SerializeStruct for StructSerializer {
+	type Ok = Vec<(&'static str, FieldValue)>;
+	type Error = SettingsError;
+
+	fn serialize_field<T: Serialize + ?Sized>(
+		&mut self,
+		key: &'static str,
+		value: &T,
+	) -> Result<(), Self::Error> {
+		let value = value
+			.serialize(ValueSerializer)
+			.map_err(|_| unsupported(format_args!("the type of {key:?}")))?;
+		self.values.push((key, value));
+		Ok(())
+	}
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(self.values)
+	}
+}
+
+macro_rules! reject {
+	($error:ident: $($method:ident($($arg:ty),*)),*) => {$(
+		fn $method(self, $(_: $arg),*) -> Result<Self::Ok, Self::Error> {
+			Err($error())
+		}
+	)*};
+}
+impl ser::Serializer for StructSerializer {
+	type Ok = Vec<(&'static str, FieldValue)>;
+	type Error = SettingsError;
+	type SerializeSeq = Impossible<Self::Ok, Self::Error>;
+	type SerializeTuple = Impossible<Self::Ok, Self::Error>;
+	type SerializeTupleStruct = Impossible<Self::Ok, Self::Error>;
+	type SerializeTupleVariant = Impossible<Self::Ok, Self::Error>;
+	type SerializeMap = Impossible<Self::Ok, Self::Error>;
+	type SerializeStruct = Self;
+	type SerializeStructVariant = Impossible<Self::Ok, Self::Error>;
+
+	fn serialize_struct(
+		self,
+		_name: &'static str,
+		_len: usize,
+	) -> Result<Self::SerializeStruct, Self::Error> {
+		Ok(self)
+	}
+
+	reject!(not_a_struct:
+		serialize_bool(bool), serialize_i8(i8), serialize_i16(i16), serialize_i32(i32),
+		serialize_i64(i64), serialize_u8(u8), serialize_u16(u16), serialize_u32(u32),
+		serialize_u64(u64), serialize_f32(f32), serialize_f64(f64), serialize_char(char),
+		serialize_str(&str), serialize_bytes(&[u8]), serialize_none(), serialize_unit(),
+		serialize_unit_struct(&'static str),
+		serialize_unit_variant(&'static str, u32, &'static str)
+	);
+	fn serialize_some<T: Serialize + ?Sized>(self, _: &T) -> Result<Self::Ok, Self::Error> {
+		Err(not_a_struct())
+	}
+	fn serialize_newtype_struct<T: Serialize + ?Sized>(
+		self,
+		_: &'static str,
+		_: &T,
+	) -> Result<Self::Ok, Self::Error> {
+		Err(not_a_struct())
+	}
+	fn serialize_newtype_variant<T: Serialize + ?Sized>(
+		self,
+		_: &'static str,
+		_: u32,
+		_: &'static str,
+		_: &T,
+	) -> Result<Self::Ok, Self::Error> {
+		Err(not_a_struct())
+	}
+	fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+		Err(not_a_struct())
+	}
+	fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Self::Error> {
+		Err(not_a_struct())
+	}
+	fn serialize_tuple_struct(
+		self,
+		_: &'static str,
+		_: usize,
+	) -> Result<Self::SerializeTupleStruct, Self::Error> {
+		Err(not_a_struct())
+	}
+	fn serialize_tuple_variant(
+		self,
+		_: &'static str,
+		_: u32,
+		_: &'static str,
+		_: usize,
+	) -> Result<Self::SerializeTupleVariant, Self::Error> {
+		Err(not_a_struct())
+	}
+	fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+		Err(not_a_struct())
+	}
+	fn serialize_struct_variant(
+		self,
+		_: &'static str,
+		_: u32,
+		_: &'static str,
+		_: usize,
+	) -> Result<Self::SerializeStructVariant, Self::Error> {
+		Err(not_a_struct())
+	}
+}
+
+/// serializes a single field. anything that isn't a bool, number, string or enum without data is an error
+struct ValueSerializer;
+
+macro_rules! serialize_as {
+	($($method:ident($ty:ty) => |$v:ident| $value:expr),* $(,)?) => {$(
+		fn $method(self, $v: $ty) -> Result<Self::Ok, Self::Error> {
+			Ok($value)
+		}
+	)*};
+}
+impl ser::Serializer for ValueSerializer {
+	type Ok = FieldValue;
+	type Error = SettingsError;
+	type SerializeSeq = Impossible<Self::Ok, Self::Error>;
+	type SerializeTuple = Impossible<Self::Ok, Self::Error>;
+	type SerializeTupleStruct = Impossible<Self::Ok, Self::Error>;
+	type SerializeTupleVariant = Impossible<Self::Ok, Self::Error>;
+	type SerializeMap = Impossible<Self::Ok, Self::Error>;
+	type SerializeStruct = Impossible<Self::Ok, Self::Error>;
+	type SerializeStructVariant = Impossible<Self::Ok, Self::Error>;
+
+	serialize_as!(
+		serialize_bool(bool) => |v| FieldValue::Bool(v),
+		serialize_i8(i8) => |v| FieldValue::Int(v as i64),
+		serialize_i16(i16) => |v| FieldValue::Int(v as i64),
+		serialize_i32(i32) => |v| FieldValue::Int(v as i64),
+		serialize_i64(i64) => |v| FieldValue::Int(v),
+		serialize_u8(u8) => |v| FieldValue::Int(v as i64),
+		serialize_u16(u16) => |v| FieldValue::Int(v as i64),
+		serialize_u32(u32) => |v| FieldValue::Int(v as i64),
+		serialize_f32(f32) => |v| FieldValue::Float(v as f64),
+		serialize_f64(f64) => |v| FieldValue::Float(v),
+		serialize_str(&str) => |v| FieldValue::Text(v.to_string()),
+	);
+	fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+		let v =
+			i64::try_from(v).map_err(|_| unsupported(format_args!("{v}, a number that big,")))?;
+		Ok(FieldValue::Int(v))
+	}
+	fn serialize_unit_variant(
+		self,
+		_: &'static str,
+		_: u32,
+		variant: &'static str,
+	) -> Result<Self::Ok, Self::Error> {
+		Ok(FieldValue::Text(variant.to_string()))
+	}
+
+	reject!(unsupported_value:
+		serialize_char(char), serialize_bytes(&[u8]), serialize_none(), serialize_unit(),
+		serialize_unit_struct(&'static str)
+	);
+	fn serialize_some<T: Serialize + ?Sized>(self, _: &T) -> Result<Self::Ok, Self::Error> {
+		Err(unsupported_value())
+	}
+	fn serialize_newtype_struct<T: Serialize + ?Sized>(
+		self,
+		_: &'static str,
+		_: &T,
+	) -> Result<Self::Ok, Self::Error> {
+		Err(unsupported_value())
+	}
+	fn serialize_newtype_variant<T: Serialize + ?Sized>(
+		self,
+		_: &'static str,
+		_: u32,
+		_: &'static str,
+		_: &T,
+	) -> Result<Self::Ok, Self::Error> {
+		Err(unsupported_value())
+	}
+	fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+		Err(unsupported_value())
+	}
+	fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Self::Error> {
+		Err(unsupported_value())
+	}
+	fn serialize_tuple_struct(
+		self,
+		_: &'static str,
+		_: usize,
+	) -> Result<Self::SerializeTupleStruct, Self::Error> {
+		Err(unsupported_value())
+	}
+	fn serialize_tuple_variant(
+		self,
+		_: &'static str,
+		_: u32,
+		_: &'static str,
+		_: usize,
+	) -> Result<Self::SerializeTupleVariant, Self::Error> {
+		Err(unsupported_value())
+	}
+	fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+		Err(unsupported_value())
+	}
+	fn serialize_struct(
+		self,
+		_: &'static str,
+		_: usize,
+	) -> Result<Self::SerializeStruct, Self::Error> {
+		Err(unsupported_value())
+	}
+	fn serialize_struct_variant(
+		self,
+		_: &'static str,
+		_: u32,
+		_: &'static str,
+		_: usize,
+	) -> Result<Self::SerializeStructVariant, Self::Error> {
+		Err(unsupported_value())
+	}
+}
+
+// --- values in
+
+macro_rules! whole_floats {
+	($($method:ident),*) => {$(
+		fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+			match self.0 {
+				// sliders only ever give floats, even for integer fields
+				FieldValue::Float(f) if f.fract() == 0.0 => visitor.visit_i64(*f as i64),
+				_ => self.deserialize_any(visitor),
+			}
+		}
+	)*};
+}
+
+struct ValueDeserializer<'a>(&'a FieldValue);
+impl<'de> IntoDeserializer<'de, SettingsError> for ValueDeserializer<'_> {
+	type Deserializer = Self;
+
+	fn into_deserializer(self) -> Self::Deserializer {
+		self
+	}
+}
+impl<'de> de::Deserializer<'de> for ValueDeserializer<'_> {
+	type Error = SettingsError;
+
+	fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		match self.0 {
+			FieldValue::Text(text) => visitor.visit_str(text),
+			FieldValue::Bool(b) => visitor.visit_bool(*b),
+			FieldValue::Int(i) => visitor.visit_i64(*i),
+			FieldValue::Float(f) => visitor.visit_f64(*f),
+		}
+	}
+	fn deserialize_enum<V: Visitor<'de>>(
+		self,
+		_name: &'static str,
+		_variants: &'static [&'static str],
+		visitor: V,
+	) -> Result<V::Value, Self::Error> {
+		match self.0 {
+			FieldValue::Text(variant) => visitor.visit_enum(variant.as_str().into_deserializer()),
+			_ => self.deserialize_any(visitor),
+		}
+	}
+	whole_floats!(
+		deserialize_i8,
+		deserialize_i16,
+		deserialize_i32,
+		deserialize_i64,
+		deserialize_u8,
+		deserialize_u16,
+		deserialize_u32,
+		deserialize_u64
+	);
+
+	forward_to_deserialize_any! {
+		bool i128 u128 f32 f64 char str string bytes byte_buf option unit unit_struct
+		newtype_struct seq tuple tuple_struct map struct identifier ignored_any
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde::{Deserialize, Serialize};
+
+	#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+	enum Mode {
+		Windowed,
+		Fullscreen,
+	}
+	#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+	struct Settings {
+		name: String,
+		vsync: bool,
+		fps: u16,
+		volume: f32,
+		#[serde(rename = "display_mode")]
+		mode: Mode,
+	}
+
+	#[test]
+	fn test_schema() {
+		let fields = schema::<Settings>().unwrap();
+		let kinds = fields.iter().map(|f| (f.name, &f.kind)).collect::<Vec<_>>();
+		assert_eq!(
+			kinds,
+			vec![
+				("name", &Kind::Text),
+				("vsync", &Kind::Bool),
+				("fps", &Kind::Int { min: 0, max: 65535 }),
+				(
+					"volume",
+					&Kind::Float {
+						min: f32::MIN as f64,
+						max: f32::MAX as f64
+					}
+				),
+				("display_mode", &Kind::Enum(&["Windowed", "Fullscreen"])),
+			]
+		);
+
+		#[derive(Deserialize)]
+		#[allow(dead_code)]
+		struct Nested {
+			settings: Settings,
+		}
+		assert!(schema::<Nested>().is_err());
+
+		#[derive(Deserialize)]
+		#[allow(dead_code)]
+		enum WithData {
+			Some(i32),
+		}
+		#[derive(Deserialize)]
+		#[allow(dead_code)]
+		struct HasData {
+			data: WithData,
+		}
+		assert!(schema::<HasData>().is_err());
+
+		// only the first variant is looked at by the probe
+		#[derive(Serialize, Deserialize)]
+		#[allow(dead_code)]
+		enum LaterData {
+			None,
+			Some(i32),
+		}
+		#[derive(Serialize, Deserialize)]
+		#[allow(dead_code)]
+		struct HasLaterData {
+			flag: bool,
+			data: LaterData,
+		}
+		let fields = schema::<HasLaterData>().unwrap();
+		let values = to_values(&HasLaterData {
+			flag: true,
+			data: LaterData::None,
+		})
+		.unwrap();
+		assert!(check_variants::<HasLaterData>(&fields, &values).is_err());
+		let values = to_values(&Settings {
+			name: "player".into(),
+			vsync: true,
+			fps: 60,
+			volume: 1.0,
+			mode: Mode::Windowed,
+		})
+		.unwrap();
+		assert!(check_variants::<Settings>(&schema::<Settings>().unwrap(), &values).is_ok());
+		assert!(schema::<(i32, i32)>().is_err());
+	}
+
+	#[test]
+	fn test_round_trip() {
+		let settings = Settings {
+			name: "player".into(),
+			vsync: true,
+			fps: 144,
+			volume: 0.5,
+			mode: Mode::Fullscreen,
+		};
+		let values = to_values(&settings).unwrap();
+		assert_eq!(values[2], ("fps", FieldValue::Int(144)));
+		assert_eq!(
+			values[4],
+			("display_mode", FieldValue::Text("Fullscreen".into()))
+		);
+
+		let back: Settings =
+			from_values(values.iter().map(|(name, value)| (*name, value))).unwrap();
+		assert_eq!(back, settings);
+
+		let mut values = values;
+		values[2].1 = FieldValue::Float(60.0);
+		let from_slider: Settings =
+			from_values(values.iter().map(|(name, value)| (*name, value))).unwrap();
+		assert_eq!(from_slider.fps, 60);
+
+		values[2].1 = FieldValue::Int(70000);
+		let too_big = from_values::<Settings>(values.iter().map(|(name, value)| (*name, value)));
+		assert!(too_big.is_err());
+	}
+}
@@ -0,0 +1,384 @@
+use std::borrow::Cow;
+
+use raylib::{ffi::KeyboardKey, prelude::RaylibDraw};
+
+use super::{
+	control::{self, Changed},
+	UniqueId,
+};
+use crate::{
+	core::{Event, MouseEvent, ReturnEvent, Store},
+	Details, Layable, Text,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub enum SliderOrientation {
+	#[default]
+	Horizontal,
+	/// the maximum is at the top
+	Vertical,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum SliderAction {
+	#[default]
+	None,
+	/// the value and the screen coord (x or y, depending on the orientation) the drag was initiated from
+	DraggingFrom { before: f32, drag_start_c: i32 },
+}
+
+#[derive(Clone, Debug)]
+/// a slider bound to a `Store<f32>`, going from `min` to `max` in increments of `step`. \
+/// the thumb can be dragged (like the handle of a [Scrollable](crate::comp::Scrollable)),
+/// clicking anywhere else on the track jumps there, and while it's focused the arrow keys move it by `step`,
+/// page up/down by 10 steps and home/end to the ends. \
+/// every change returns a [Changed<f32>]
+pub struct Slider {
+	store: Store<f32>,
+	uid: UniqueId,
+	min: f32,
+	max: f32,
+	/// 0 means continuous
+	step: f32,
+	orientation: SliderOrientation,
+	/// length of the track, unscaled
+	length: i32,
+	/// diameter of the thumb, unscaled
+	thickness: i32,
+	/// text size of the value label, if it's shown
+	label: Option<i32>,
+	precision: usize,
+
+	action: SliderAction,
+}
+impl Slider {
+	pub fn new(store: Store<f32>, min: f32, max: f32, length: i32) -> Self {
+		let slider = Self {
+			store,
+			uid: UniqueId::new(),
+			min: min.min(max),
+			max: max.max(min),
+			step: 0.0,
+			orientation: SliderOrientation::Horizontal,
+			length,
+			thickness: 16,
+			label: None,
+			precision: 2,
+			action: SliderAction::None,
+		};
+		let clamped = slider.snap(slider.store.get());
+		slider.store.set(clamped);
+		slider
+	}
+	/// also sets how many decimals the label shows to however many `step` has
+	pub fn with_step(self, step: f32) -> Self {
+		let step = step.abs();
+		let slider = Self {
+			step,
+			precision: decimals(step),
+			..self
+		};
+		let snapped = slider.snap(slider.store.get());
+		slider.store.set(snapped);
+		slider
+	}
+	pub fn vertical(self) -> Self {
+		Self {
+			orientation: SliderOrientation::Vertical,
+			..self
+		}
+	}
+	pub fn with_thickness(self, thickness: i32) -> Self {
+		Self { thickness, ..self }
+	}
+	/// shows the current value next to (or under, if vertical) the slider
+	pub fn with_label(self, size: i32) -> Self {
+		Self {
+			label: Some(size),
+			..self
+		}
+	}
+	/// number of decimals the label shows
+	pub fn with_precision(self, precision: usize) -> Self {
+		Self { precision, ..self }
+	}
+
+	pub fn uid(&self) -> UniqueId {
+		self.uid
+	}
+	pub fn store(&self) -> &Store<f32> {
+		&self.store
+	}
+	pub fn action(&self) -> SliderAction {
+		self.action
+	}
+
+	/// clamps `value` between min and max and rounds it to the nearest step
+	pub fn snap(&self, value: f32) -> f32 {
+		snap(value, self.min, self.max, self.step)
+	}
+
+	fn set(&mut self, value: f32, ret_events: &mut Vec<ReturnEvent>) {
+		let value = self.snap(value);
+		if self.store.get() == value {
+			return;
+		}
+		self.store.set(value);
+		ret_events.push(Event::ret(Changed {
+			uid: self.uid,
+			value,
+		}));
+	}
+
+	fn label_text(&self, value: f32, size: i32) -> Text<'static> {
+		Text::new(
+			Cow::Owned(format!("{value:.prec$}", prec = self.precision)),
+			size,
+		)
+	}
+	/// unscaled size of the widest label the slider could have
+	fn label_size(&self) -> Option<(i32, i32)> {
+		let size = self.label?;
+		let (min_w, h) = self.label_text(self.min, size).size();
+		let (max_w, _) = self.label_text(self.max, size).size();
+		Some((min_w.max(max_w), h))
+	}
+	/// unscaled space between the track and the label
+	fn gap(&self) -> i32 {
+		self.thickness / 2
+	}
+
+	/// scaled det of the track, including the half of the thumb that hangs off each end
+	fn track(&self, det: Details, scale: f32) -> Details {
+		let length = (self.length as f32 * scale) as i32;
+		let thickness = (self.thickness as f32 * scale) as i32;
+		match self.orientation {
+			SliderOrientation::Horizontal => Details {
+				x: det.x,
+				y: det.y,
+				aw: length,
+				ah: thickness,
+			},
+			SliderOrientation::Vertical => Details {
+				x: det.x,
+				y: det.y,
+				aw: thickness,
+				ah: length,
+			},
+		}
+	}
+	/// how far along the track the thumb's center can travel, in screen pixels
+	fn travel(&self, scale: f32) -> f32 {
+		((self.length - self.thickness) as f32 * scale).max(1.0)
+	}
+	/// 0 to 1, how far along the track `value` is
+	fn fraction(&self, value: f32) -> f32 {
+		if self.max == self.min {
+			return 0.0;
+		}
+		((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+	}
+	/// scaled center of the thumb
+	fn thumb_center(&self, det: Details, scale: f32) -> (i32, i32) {
+		let track = self.track(det, scale);
+		let radius = self.thickness as f32 * scale / 2.0;
+		let along = radius + self.fraction(self.store.get()) * self.travel(scale);
+		match self.orientation {
+			SliderOrientation::Horizontal => (
+				(track.x as f32 + along) as i32,
+				(track.y as f32 + radius) as i32,
+			),
+			SliderOrientation::Vertical => (
+				(track.x as f32 + radius) as i32,
+				(track.y as f32 + track.ah as f32 - along) as i32,
+			),
+		}
+	}
+	/// the value the thumb would have if its center was at (x, y)
+	fn value_at(&self, det: Details, scale: f32, (x, y): (i32, i32)) -> f32 {
+		let track = self.track(det, scale);
+		let radius = self.thickness as f32 * scale / 2.0;
+		let along = match self.orientation {
+			SliderOrientation::Horizontal => (x - track.x) as f32 - radius,
+			SliderOrientation::Vertical => (track.y + track.ah - y) as f32 - radius,
+		};
+		self.min + along / self.travel(scale) * (self.max - self.min)
+	}
+	/// the coord that matters for dragging
+	fn drag_c(&self, (x, y): (i32, i32)) -> i32 {
+		match self.orientation {
+			SliderOrientation::Horizontal => x,
+			SliderOrientation::Vertical => -y,
+		}
+	}
+}
+
+impl Layable for Slider {
+	fn size(&self) -> (i32, i32) {
+		let (w, h) = match self.orientation {
+			SliderOrientation::Horizontal => (self.length, self.thickness),
+			SliderOrientation::Vertical => (self.thickness, self.length),
+		};
+		match (self.label_size(), self.orientation) {
+			(None, _) => (w, h),
+			(Some((l_w, l_h)), SliderOrientation::Horizontal) => (w + self.gap() + l_w, h.max(l_h)),
+			(Some((l_w, l_h)), SliderOrientation::Vertical) => (w.max(l_w), h + self.gap() + l_h),
+		}
+	}
+	fn render(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		let focused = d.focus() == self.uid;
+		let track = self.track(det, scale);
+		let radius = self.thickness as f32 * scale / 2.0;
+		let line = ((self.thickness as f32 * scale) / 4.0).max(2.0) as i32;
+		let (thumb_x, thumb_y) = self.thumb_center(det, scale);
+
+		match self.orientation {
+			SliderOrientation::Horizontal => {
+				let y = track.y + (track.ah - line) / 2;
+				let (start, end) = (track.x + radius as i32, track.x + track.aw - radius as i32);
+				d.draw_rectangle(start, y, end - start, line, control::INACTIVE);
+				d.draw_rectangle(start, y, thumb_x - start, line, control::ACCENT);
+			}
+			SliderOrientation::Vertical => {
+				let x = track.x + (track.aw - line) / 2;
+				let (start, end) = (track.y + radius as i32, track.y + track.ah - radius as i32);
+				d.draw_rectangle(x, start, line, end - start, control::INACTIVE);
+				d.draw_rectangle(x, thumb_y, line, end - thumb_y, control::ACCENT);
+			}
+		}
+
+		let thumb_color = match (self.action, focused) {
+			(SliderAction::DraggingFrom { .. }, _) | (_, true) => control::ACCENT,
+			_ => control::OUTLINE,
+		};
+		d.draw_circle(thumb_x, thumb_y, radius, thumb_color);
+
+		if let (Some(size), Some((l_w, l_h))) = (self.label, self.label_size()) {
+			let label = self.label_text(self.store.get(), size);
+			let (l_w, l_h) = ((l_w as f32 * scale) as i32, (l_h as f32 * scale) as i32);
+			let gap = (self.gap() as f32 * scale) as i32;
+			let label_det = match self.orientation {
+				SliderOrientation::Horizontal => Details {
+					x: track.x + track.aw + gap,
+					y: track.y + (track.ah - l_h) / 2,
+					aw: l_w,
+					ah: l_h,
+				},
+				SliderOrientation::Vertical => Details {
+					x: track.x + (track.aw - l_w) / 2,
+					y: track.y + track.ah + gap,
+					aw: l_w,
+					ah: l_h,
+				},
+			};
+			label.render(d, label_det, scale);
+		}
+	}
+
+	fn pass_events(
+		&mut self,
+		events: impl Iterator<Item = Event>,
+		det: Details,
+		scale: f32,
+		ret_events: &mut Vec<ReturnEvent>,
+	) {
+		let track = self.track(det, scale);
+		for event in events {
+			match event {
+				Event::MouseEvent(MouseEvent::MouseClick { x, y }) if track.is_inside(x, y) => {
+					ret_events.push(Event::ret(super::FocusCommand::Request(self.uid)));
+
+					let (thumb_x, thumb_y) = self.thumb_center(det, scale);
+					let radius = self.thickness as f32 * scale / 2.0;
+					let (dx, dy) = ((x - thumb_x) as f32, (y - thumb_y) as f32);
+					if dx * dx + dy * dy > radius * radius {
+						// clicked the track, not the thumb
+						self.set(self.value_at(det, scale, (x, y)), ret_events);
+					}
+					self.action = SliderAction::DraggingFrom {
+						before: self.store.get(),
+						drag_start_c: self.drag_c((x, y)),
+					};
+				}
+				Event::MouseEvent(MouseEvent::MouseHeld { x, y }) => {
+					if let SliderAction::DraggingFrom {
+						before,
+						drag_start_c,
+					} = self.action
+					{
+						// the inverse of how thumb_center works out where the thumb is
+						let moved = (self.drag_c((x, y)) - drag_start_c) as f32;
+						let value = before + moved / self.travel(scale) * (self.max - self.min);
+						self.set(value, ret_events);
+					}
+				}
+				Event::MouseEvent(MouseEvent::MouseRelease { .. }) => {
+					self.action = SliderAction::None;
+				}
+				event => {
+					let step = if self.step > 0.0 {
+						self.step
+					} else {
+						(self.max - self.min) / 100.0
+					};
+					let value = self.store.get();
+					let value = match control::pressed_key(&event, self.uid) {
+						Some(KeyboardKey::KEY_RIGHT | KeyboardKey::KEY_UP) => value + step,
+						Some(KeyboardKey::KEY_LEFT | KeyboardKey::KEY_DOWN) => value - step,
+						Some(KeyboardKey::KEY_PAGE_UP) => value + step * 10.0,
+						Some(KeyboardKey::KEY_PAGE_DOWN) => value - step * 10.0,
+						Some(KeyboardKey::KEY_HOME) => self.min,
+						Some(KeyboardKey::KEY_END) => self.max,
+						_ => continue,
+					};
+					self.set(value, ret_events);
+				}
+			}
+		}
+	}
+}
+
+/// clamps `value` between `min` and `max` and rounds it to the nearest multiple of `step` from `min`. \
+/// a `step` of 0 doesn't round
+pub fn snap(value: f32, min: f32, max: f32, step: f32) -> f32 {
+	let value = if step > 0.0 {
+		min + ((value - min) / step).round() * step
+	} else {
+		value
+	};
+	value.clamp(min, max)
+}
+
+/// how many decimals `step` has, so 0.25 has 2 and 5 has none. \
+/// goes by the shortest way to write it down, so 0.1 is 1 even though it isn't exactly 0.1 as an f32
+pub fn decimals(step: f32) -> usize {
+	let written = step.abs().to_string();
+	written.split_once('.').map_or(0, |(_, fract)| fract.len())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_decimals() {
+		assert_eq!(decimals(0.25), 2);
+		assert_eq!(decimals(0.1), 1);
+		assert_eq!(decimals(0.05), 2);
+		assert_eq!(decimals(2.5), 1);
+		assert_eq!(decimals(5.0), 0);
+		assert_eq!(decimals(0.0), 0);
+	}
+
+	#[test]
+	fn test_snap() {
+		assert_eq!(snap(0.26, 0.0, 1.0, 0.25), 0.25);
+		assert_eq!(snap(0.4, 0.0, 1.0, 0.25), 0.5);
+		assert_eq!(snap(7.0, 1.0, 11.0, 2.0), 7.0);
+		assert_eq!(snap(7.9, 1.0, 11.0, 2.0), 7.0);
+		assert_eq!(snap(8.1, 1.0, 11.0, 2.0), 9.0);
+		assert_eq!(snap(-3.0, 0.0, 1.0, 0.0), 0.0);
+		assert_eq!(snap(0.123, 0.0, 1.0, 0.0), 0.123);
+		assert_eq!(snap(12.0, 0.0, 10.0, 5.0), 10.0);
+	}
+}
@@ -0,0 +1,421 @@
+use std::{cell::Cell, fmt::Debug, str::FromStr};
+
+use raylib::{ffi::KeyboardKey, math::Vector2, prelude::RaylibDraw};
+
+use super::{
+	control::{self, Changed},
+	typable::{TypableData, BACKSPACE},
+	FocusCommand, Typable, UniqueId,
+};
+use crate::{
+	core::{Event, KeyboardEvent, MouseEvent, ReturnEvent, Store},
+	Color, Details, Layable,
+};
+
+const PADDING: i32 = 4;
+const BACKGROUND: Color = crate::color(40, 40, 40, 255);
+const BUTTON: Color = crate::color(60, 60, 60, 255);
+const INVALID: Color = crate::color(230, 80, 80, 255);
+
+/// a number a [Spinner] can edit
+pub trait Numeric: Copy + PartialOrd + Debug + FromStr + 'static {
+	/// whether `c` can be typed into a spinner for this type
+	fn allows(c: char) -> bool;
+	/// `self` moved by `steps` times `step`
+	fn offset(self, step: Self, steps: i64) -> Self;
+	fn format(self, precision: usize) -> String;
+}
+impl Numeric for i64 {
+	fn allows(c: char) -> bool {
+		c.is_ascii_digit() || c == '-'
+	}
+	fn offset(self, step: Self, steps: i64) -> Self {
+		self.saturating_add(step.saturating_mul(steps))
+	}
+	fn format(self, _precision: usize) -> String {
+		self.to_string()
+	}
+}
+impl Numeric for f64 {
+	fn allows(c: char) -> bool {
+		c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')
+	}
+	fn offset(self, step: Self, steps: i64) -> Self {
+		self + step * steps as f64
+	}
+	fn format(self, precision: usize) -> String {
+		format!("{self:.precision$}")
+	}
+}
+
+/// parses `text` and clamps it between `min` and `max`. \
+/// returns None if it isn't a number
+pub fn parse<N: Numeric>(text: &str, min: N, max: N) -> Option<N> {
+	let value = text.trim().parse::<N>().ok()?;
+	// NaN isn't comparable with anything so it'd slip through clamping
+	value.partial_cmp(&value)?;
+	Some(clamp(value, min, max))
+}
+fn clamp<N: Numeric>(value: N, min: N, max: N) -> N {
+	if value < min {
+		min
+	} else if value > max {
+		max
+	} else {
+		value
+	}
+}
+
+pub type IntSpinner = Spinner<i64>;
+pub type FloatSpinner = Spinner<f64>;
+
+#[derive(Clone, Debug)]
+/// a number input bound to a `Store<i64>` or a `Store<f64>`, built on [Typable]. \
+/// the value can be typed (it gets parsed and clamped when enter or tab is pressed, or when the spinner
+/// loses focus), changed with the arrow buttons, the mouse wheel, or the up/down and page up/down keys. \
+/// text that doesn't parse gets outlined red, and reverted if focus is lost or escape is pressed. \
+/// every change returns a [Changed<N>]
+pub struct Spinner<N: Numeric> {
+	store: Store<N>,
+	text: Store<TypableData>,
+	typable: Typable,
+	min: N,
+	max: N,
+	step: N,
+	precision: usize,
+	text_size: i32,
+	/// width of the text area, unscaled
+	width: i32,
+
+	/// the value the text was last set from, to notice if the store gets changed from the outside
+	shown: N,
+	/// whether the text has been typed into since it was last set
+	dirty: bool,
+	invalid: bool,
+	/// whether the spinner was focused the last time it was rendered
+	focused: Cell<bool>,
+	was_focused: bool,
+}
+impl<N: Numeric> Spinner<N> {
+	pub fn new(store: Store<N>, min: N, max: N, step: N, text_size: i32) -> Self {
+		let (min, max) = if min > max { (max, min) } else { (min, max) };
+		let value = clamp(store.get(), min, max);
+		store.set(value);
+
+		let text = Store::new(TypableData::with_default(value.format(2)));
+		Self {
+			typable: Typable::new(text.clone(), text_size),
+			store,
+			text,
+			min,
+			max,
+			step,
+			precision: 2,
+			text_size,
+			width: text_size * 4,
+			shown: value,
+			dirty: false,
+			invalid: false,
+			focused: Cell::new(false),
+			was_focused: false,
+		}
+	}
+	/// number of decimals shown, only matters for floats
+	pub fn with_precision(mut self, precision: usize) -> Self {
+		self.precision = precision;
+		self.reset_text();
+		self
+	}
+	/// width of the text area, unscaled
+	pub fn with_width(self, width: i32) -> Self {
+		Self { width, ..self }
+	}
+
+	/// the uid keyboard events have to be sent to
+	pub fn uid(&self) -> UniqueId {
+		self.text.with_borrow(|data| data.uid)
+	}
+	pub fn store(&self) -> &Store<N> {
+		&self.store
+	}
+	/// whether the text typed in doesn't parse
+	pub fn is_invalid(&self) -> bool {
+		self.invalid
+	}
+
+	/// sets the text to the store's value, throwing away whatever's been typed
+	fn reset_text(&mut self) {
+		let value = self.store.get();
+		self.text
+			.with_mut_borrow(|data| data.set_text(value.format(self.precision)));
+		self.shown = value;
+		self.dirty = false;
+		self.invalid = false;
+	}
+	fn set(&mut self, value: N, ret_events: &mut Vec<ReturnEvent>) {
+		let value = clamp(value, self.min, self.max);
+		let changed = self.store.get().partial_cmp(&value) != Some(std::cmp::Ordering::Equal);
+		self.store.set(value);
+		self.reset_text();
+		if changed {
+			ret_events.push(Event::ret(Changed {
+				uid: self.uid(),
+				value,
+			}));
+		}
+	}
+	/// parses whatever's been typed. returns false if it didn't parse
+	fn commit(&mut self, ret_events: &mut Vec<ReturnEvent>) -> bool {
+		if !self.dirty {
+			return true;
+		}
+		let parsed = self
+			.text
+			.with_borrow(|data| parse(&data.text, self.min, self.max));
+		match parsed {
+			Some(value) => {
+				self.set(value, ret_events);
+				true
+			}
+			None => {
+				self.invalid = true;
+				false
+			}
+		}
+	}
+	fn step_by(&mut self, steps: i64, ret_events: &mut Vec<ReturnEvent>) {
+		if !self.commit(ret_events) {
+			self.reset_text();
+		}
+		let value = self.store.get().offset(self.step, steps);
+		self.set(value, ret_events);
+	}
+
+	fn bounds(&self, det: Details, scale: f32) -> Details {
+		let (w, h) = self.size();
+		Details {
+			aw: (w as f32 * scale) as i32,
+			ah: (h as f32 * scale) as i32,
+			..det
+		}
+	}
+	/// scaled (up, down) button dets
+	fn buttons(&self, det: Details, scale: f32) -> (Details, Details) {
+		let bounds = self.bounds(det, scale);
+		let button_w = (self.button_w() as f32 * scale) as i32;
+		let x = bounds.x + bounds.aw - button_w;
+		let half = bounds.ah / 2;
+		(
+			Details {
+				x,
+				y: bounds.y,
+				aw: button_w,
+				ah: half,
+			},
+			Details {
+				x,
+				y: bounds.y + half,
+				aw: button_w,
+				ah: bounds.ah - half,
+			},
+		)
+	}
+	/// where the text goes, scaled
+	fn text_det(&self, det: Details, scale: f32) -> Details {
+		let bounds = self.bounds(det, scale);
+		let (up, _) = self.buttons(det, scale);
+		let padding = (PADDING as f32 * scale) as i32;
+		Details {
+			x: bounds.x + padding,
+			y: bounds.y + padding,
+			aw: up.x - bounds.x - padding * 2,
+			ah: bounds.ah - padding * 2,
+		}
+	}
+	/// unscaled width of the arrow buttons
+	fn button_w(&self) -> i32 {
+		(self.text_size + PADDING * 2) * 3 / 4
+	}
+}
+
+impl<N: Numeric> Layable for Spinner<N> {
+	fn size(&self) -> (i32, i32) {
+		(
+			self.width + PADDING * 2 + self.button_w(),
+			self.text_size + PADDING * 2,
+		)
+	}
+	fn render(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		let focused = d.focus() == self.uid();
+		self.focused.set(focused);
+
+		let bounds = self.bounds(det, scale);
+		d.draw_rectangle(bounds.x, bounds.y, bounds.aw, bounds.ah, BACKGROUND);
+
+		let (up, down) = self.buttons(det, scale);
+		self.typable.render(d, self.text_det(det, scale), scale);
+
+		for (button, pointing_up) in [(up, true), (down, false)] {
+			d.draw_rectangle(button.x, button.y, button.aw, button.ah, BUTTON);
+			let (cx, cy) = (
+				button.x as f32 + button.aw as f32 / 2.0,
+				button.y as f32 + button.ah as f32 / 2.0,
+			);
+			let r = button.ah.min(button.aw) as f32 / 4.0;
+			let (tip, base) = if pointing_up {
+				(cy - r, cy + r)
+			} else {
+				(cy + r, cy - r)
+			};
+			let (left, right) = (Vector2::new(cx - r, base), Vector2::new(cx + r, base));
+			let tip = Vector2::new(cx, tip);
+			// raylib only draws counter-clockwise triangles
+			if pointing_up {
+				d.draw_triangle(tip, left, right, control::OUTLINE);
+			} else {
+				d.draw_triangle(tip, right, left, control::OUTLINE);
+			}
+		}
+
+		let outline = if self.invalid {
+			INVALID
+		} else if focused {
+			control::ACCENT
+		} else {
+			control::OUTLINE
+		};
+		d.draw_rectangle_lines(bounds.x, bounds.y, bounds.aw, bounds.ah, outline);
+	}
+
+	fn tick(&mut self) {
+		self.typable.tick();
+		if !self.dirty
+			&& self.store.get().partial_cmp(&self.shown) != Some(std::cmp::Ordering::Equal)
+		{
+			self.reset_text();
+		}
+	}
+	fn pass_events(
+		&mut self,
+		events: impl Iterator<Item = Event>,
+		det: Details,
+		scale: f32,
+		ret_events: &mut Vec<ReturnEvent>,
+	) {
+		let focused = self.focused.get();
+		if self.was_focused && !focused && !self.commit(ret_events) {
+			self.reset_text();
+		}
+		self.was_focused = focused;
+
+		let uid = self.uid();
+		let bounds = self.bounds(det, scale);
+		let (up, down) = self.buttons(det, scale);
+		let text_det = self.text_det(det, scale);
+		let events = events.collect::<Vec<_>>();
+		// the typable gets events one at a time, with the held keys so it knows about shift and ctrl
+		let held = (events.iter().copied())
+			.filter(|event| matches!(event, Event::KeyboardEvent(_, KeyboardEvent::KeyDown(_))))
+			.collect::<Vec<_>>();
+		let to_typable =
+			|typable: &mut Typable, event: Event, ret_events: &mut Vec<ReturnEvent>| {
+				let events = held.iter().copied().chain(std::iter::once(event));
+				typable.pass_events(events, text_det, scale, ret_events);
+			};
+
+		for event in events.iter().copied() {
+			match event {
+				Event::MouseEvent(MouseEvent::MouseClick { x, y }) if bounds.is_inside(x, y) => {
+					ret_events.push(Event::ret(FocusCommand::Request(uid)));
+					if up.is_inside(x, y) {
+						self.step_by(1, ret_events);
+					} else if down.is_inside(x, y) {
+						self.step_by(-1, ret_events);
+					} else {
+						to_typable(&mut self.typable, event, ret_events);
+					}
+				}
+				Event::MouseEvent(MouseEvent::Scroll { x, y, amount })
+					if bounds.is_inside(x, y) =>
+				{
+					self.step_by(amount.signum() as i64, ret_events);
+				}
+				Event::KeyboardEvent(this_uid, KeyboardEvent::CharPressed(c))
+					if this_uid == uid =>
+				{
+					if c == '\n' {
+						self.commit(ret_events);
+					} else if c == BACKSPACE || N::allows(c) {
+						self.dirty = true;
+						self.invalid = false;
+						to_typable(&mut self.typable, event, ret_events);
+					}
+				}
+				event => match control::pressed_key(&event, uid) {
+					Some(KeyboardKey::KEY_UP) => self.step_by(1, ret_events),
+					Some(KeyboardKey::KEY_DOWN) => self.step_by(-1, ret_events),
+					Some(KeyboardKey::KEY_PAGE_UP) => self.step_by(10, ret_events),
+					Some(KeyboardKey::KEY_PAGE_DOWN) => self.step_by(-10, ret_events),
+					Some(KeyboardKey::KEY_TAB) => {
+						self.commit(ret_events);
+					}
+					Some(KeyboardKey::KEY_ESCAPE) => self.reset_text(),
+					// delete, the arrows, home, end, ctrl+a and ctrl+c are for the typable
+					Some(key) => {
+						if key == KeyboardKey::KEY_DELETE {
+							self.dirty = true;
+							self.invalid = false;
+						}
+						to_typable(&mut self.typable, event, ret_events);
+					}
+					None => {}
+				},
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse() {
+		assert_eq!(parse::<i64>("42", 0, 100), Some(42));
+		assert_eq!(parse::<i64>(" 42 ", 0, 100), Some(42));
+		assert_eq!(parse::<i64>("420", 0, 100), Some(100));
+		assert_eq!(parse::<i64>("-5", 0, 100), Some(0));
+		assert_eq!(parse::<i64>("4-2", 0, 100), None);
+		assert_eq!(parse::<i64>("", 0, 100), None);
+
+		assert_eq!(parse::<f64>("0.5", 0.0, 1.0), Some(0.5));
+		assert_eq!(parse::<f64>("5e-1", 0.0, 1.0), Some(0.5));
+		assert_eq!(parse::<f64>("1.5", 0.0, 1.0), Some(1.0));
+		assert_eq!(parse::<f64>("NaN", 0.0, 1.0), None);
+		assert_eq!(parse::<f64>("1..5", 0.0, 1.0), None);
+	}
+
+	#[test]
+	fn test_editing() {
+		let mut spinner = IntSpinner::new(Store::new(42), 0, 100, 1, 16);
+		let uid = spinner.uid();
+		let key = |key| Event::KeyboardEvent(uid, KeyboardEvent::KeyPressed(key));
+		let events = [
+			key(KeyboardKey::KEY_HOME),
+			key(KeyboardKey::KEY_DELETE),
+			key(KeyboardKey::KEY_END),
+			Event::KeyboardEvent(uid, KeyboardEvent::CharPressed('7')),
+			Event::KeyboardEvent(uid, KeyboardEvent::CharPressed('\n')),
+		];
+		let det = Details::new(0, 0, 100, 30);
+		spinner.pass_events(events.into_iter(), det, 1.0, &mut Vec::new());
+		assert_eq!(spinner.store().get(), 27);
+	}
+
+	#[test]
+	fn test_offset() {
+		assert_eq!(5i64.offset(2, 3), 11);
+		assert_eq!(i64::MAX.offset(1, 1), i64::MAX);
+		assert_eq!(0.5f64.offset(0.25, -2), 0.0);
+	}
+}
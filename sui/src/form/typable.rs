@@ -1,11 +1,12 @@
-use raylib::{color::Color, prelude::RaylibDraw};
+use raylib::{color::Color, ffi::KeyboardKey, prelude::RaylibDraw};
 
 use crate::{
-	core::{Event, KeyboardEvent, ReturnEvent, Store},
-	Layable, Text,
+	comp::text::{measure_line, selectable::DEFAULT_HIGHLIGHT, SPACING},
+	core::{Event, KeyboardEvent, MouseEvent, ReturnEvent, Store},
+	Details, Layable, Text,
 };
 
-use super::UniqueId;
+use super::{control, selection::Selection, UniqueId};
 
 pub const BACKSPACE: char = '\x08';
 
@@ -18,25 +19,104 @@ pub enum TypeEvent {
 pub struct TypableData {
 	pub uid: UniqueId,
 	pub text: String,
+	/// where the cursor is and what's selected, typing replaces the selection. \
+	/// it gets clamped to `text` whenever it's used, so `text` can be set directly,
+	/// but [TypableData::set_text] moves the cursor to the end too
+	pub selection: Selection,
 }
 impl TypableData {
 	pub fn new() -> Self {
 		Self {
 			uid: UniqueId::new(),
 			text: String::new(),
+			selection: Selection::default(),
 		}
 	}
 	pub fn with_default(default: String) -> Self {
 		Self {
 			uid: UniqueId::new(),
+			selection: Selection::caret(default.len()),
 			text: default,
 		}
 	}
+
+	/// replaces the text, with the cursor at the end of it
+	pub fn set_text(&mut self, text: String) {
+		self.selection = Selection::caret(text.len());
+		self.text = text;
+	}
+	/// the selection, clamped to the text
+	pub fn selection(&self) -> Selection {
+		self.selection.clamp(&self.text)
+	}
+	pub fn selected_text(&self) -> &str {
+		self.selection.slice(&self.text)
+	}
+
+	/// types `c` in place of the selection
+	pub fn insert(&mut self, c: char) {
+		let range = self.selection().range();
+		self.text
+			.replace_range(range.clone(), c.encode_utf8(&mut [0; 4]));
+		self.selection = Selection::caret(range.start + c.len_utf8());
+	}
+	/// deletes the selection, or the character before the cursor if nothing's selected
+	pub fn backspace(&mut self) {
+		let selection = self.selection();
+		if selection.is_empty() {
+			self.selection = selection.extend_to(self.prev_boundary(selection.cursor));
+		}
+		self.delete_selection();
+	}
+	/// deletes the selection, or the character after the cursor if nothing's selected
+	pub fn delete(&mut self) {
+		let selection = self.selection();
+		if selection.is_empty() {
+			self.selection = selection.extend_to(self.next_boundary(selection.cursor));
+		}
+		self.delete_selection();
+	}
+	/// moves the cursor to `at`. if `extend`, the selection is extended to it, otherwise it's dropped
+	pub fn move_cursor(&mut self, at: usize, extend: bool) {
+		let at = Selection::caret(at).clamp(&self.text).cursor;
+		self.selection = if extend {
+			self.selection().extend_to(at)
+		} else {
+			Selection::caret(at)
+		};
+	}
+	/// left and right arrows: drop the selection on the side it's going towards,
+	/// or move by a character
+	fn step(&mut self, forward: bool, extend: bool) {
+		let selection = self.selection();
+		let at = match (forward, extend || selection.is_empty()) {
+			(false, true) => self.prev_boundary(selection.cursor),
+			(true, true) => self.next_boundary(selection.cursor),
+			(false, false) => selection.range().start,
+			(true, false) => selection.range().end,
+		};
+		self.move_cursor(at, extend);
+	}
+
+	fn delete_selection(&mut self) {
+		let range = self.selection().range();
+		self.text.replace_range(range.clone(), "");
+		self.selection = Selection::caret(range.start);
+	}
+	fn prev_boundary(&self, i: usize) -> usize {
+		(self.text[..i].char_indices().next_back()).map_or(0, |(i, _)| i)
+	}
+	fn next_boundary(&self, i: usize) -> usize {
+		(self.text[i..].chars().next()).map_or(i, |c| i + c.len_utf8())
+	}
 }
 
 #[derive(Clone, Debug)]
 /// this component is not a fully featured textbox. \
-/// it just renders a flashing pointer and the text currently being written.
+/// it just renders the text currently being written, with a flashing cursor and the selection. \
+/// the arrow keys, home and end move the cursor (holding shift selects), ctrl+a selects everything,
+/// ctrl+c copies and clicking puts the cursor where the click was (shift+click selects up to it). \
+/// modifier keys only count if they're held down in the same batch of events
 ///
 /// you can take out the text written by reading the store passed to [Typable::new]
 pub struct Typable {
@@ -55,52 +135,201 @@ impl Typable {
 		self.store
 			.with_borrow(|data| f(Text::new(&data.text, self.text_size)))
 	}
+
+	/// x of the byte index `i` in screen pixels, relative to det.x
+	fn x_at(&self, text: &str, i: usize, scale: f32) -> i32 {
+		if i == 0 {
+			return 0;
+		}
+		let size = (self.text_size as f32 * scale) as i32;
+		measure_line(&text[..i], size).0 + SPACING as i32
+	}
+	/// the char boundary closest to `x`, which is relative to det.x
+	fn hit(&self, text: &str, x: i32, scale: f32) -> usize {
+		let boundaries = (text.char_indices().map(|(i, _)| i)).chain(std::iter::once(text.len()));
+		let mut prev: Option<(usize, i32)> = None;
+		for i in boundaries {
+			let i_x = self.x_at(text, i, scale);
+			if i_x >= x {
+				// whichever side of the character is closer
+				return match prev {
+					Some((prev_i, prev_x)) if x - prev_x < i_x - x => prev_i,
+					_ => i,
+				};
+			}
+			prev = Some((i, i_x));
+		}
+		text.len()
+	}
+
+	fn copy(&self) {
+		let text = self
+			.store
+			.with_borrow(|data| data.selected_text().to_owned());
+		let Ok(text) = std::ffi::CString::new(text) else {
+			return;
+		};
+		unsafe { raylib::ffi::SetClipboardText(text.as_ptr()) };
+	}
 }
 impl Layable for Typable {
 	fn size(&self) -> (i32, i32) {
 		self.with_text(|a| a.size())
 	}
-	fn render(&self, d: &mut crate::Handle, det: crate::Details, scale: f32) {
-		self.with_text(|a| a.render(d, det, scale));
+	fn render(&self, d: &mut crate::Handle, det: Details, scale: f32) {
+		let (uid, text, selection) =
+			(self.store).with_borrow(|data| (data.uid, data.text.clone(), data.selection()));
+		let focused = d.focus() == uid;
+		let h = (self.text_size as f32 * scale) as i32;
 
-		if d.focus() == self.store.with_borrow(|data| data.uid) {
+		if focused && !selection.is_empty() {
+			let range = selection.range();
+			let (x0, x1) = (
+				self.x_at(&text, range.start, scale),
+				self.x_at(&text, range.end, scale),
+			);
+			d.draw_rectangle(det.x + x0, det.y, x1 - x0, h, DEFAULT_HIGHLIGHT);
+		}
+		Text::new(&text, self.text_size).render(d, det, scale);
+
+		if focused {
 			let should_draw_blinker = d.get_time() * 2.0;
 			let should_draw_blinker = should_draw_blinker - should_draw_blinker.floor();
 
 			if should_draw_blinker < 0.5 {
 				d.draw_rectangle(
-					det.x,
+					det.x + self.x_at(&text, selection.cursor, scale),
 					det.y,
 					(3.0 * scale) as i32,
-					(self.text_size as f32 * scale) as i32,
+					h,
 					Color::WHITE,
 				);
 			}
 		}
 	}
+
 	fn pass_events(
 		&mut self,
-		event: impl Iterator<Item = Event>,
-		_det: crate::Details,
-		_scale: f32,
+		events: impl Iterator<Item = Event>,
+		det: Details,
+		scale: f32,
 		ret_events: &mut Vec<ReturnEvent>,
 	) {
-		for event in event {
-			let self_uiq = self.store.with_borrow(|a| a.uid);
+		let events = events.collect::<Vec<_>>();
+		let self_uiq = self.store.with_borrow(|a| a.uid);
+		let held = |keys: [KeyboardKey; 2]| {
+			events.iter().any(|event| {
+				matches!(event, Event::KeyboardEvent(uid, KeyboardEvent::KeyDown(key)) if *uid == self_uiq && keys.contains(key))
+			})
+		};
+		let shift = held([KeyboardKey::KEY_LEFT_SHIFT, KeyboardKey::KEY_RIGHT_SHIFT]);
+		let ctrl = held([
+			KeyboardKey::KEY_LEFT_CONTROL,
+			KeyboardKey::KEY_RIGHT_CONTROL,
+		]);
+
+		for event in events.iter().copied() {
 			match event {
 				Event::KeyboardEvent(this_uiq, KeyboardEvent::CharPressed(key))
 					if this_uiq == self_uiq =>
 				{
 					self.store.with_mut_borrow(|data| match key {
-						BACKSPACE => {
-							data.text.pop();
-						}
-						_ => data.text.push(key),
+						BACKSPACE => data.backspace(),
+						_ => data.insert(key),
 					});
 					ret_events.push(ReturnEvent::new(TypeEvent::Handled))
 				}
-				_ => (),
+				Event::MouseEvent(MouseEvent::MouseClick { x, y }) if det.is_inside(x, y) => {
+					let at = self
+						.store
+						.with_borrow(|data| self.hit(&data.text, x - det.x, scale));
+					self.store
+						.with_mut_borrow(|data| data.move_cursor(at, shift));
+				}
+				event => {
+					let Some(key) = control::pressed_key(&event, self_uiq) else {
+						continue;
+					};
+					match key {
+						KeyboardKey::KEY_C if ctrl => self.copy(),
+						_ => self.store.with_mut_borrow(|data| match key {
+							KeyboardKey::KEY_LEFT => data.step(false, shift),
+							KeyboardKey::KEY_RIGHT => data.step(true, shift),
+							KeyboardKey::KEY_HOME => data.move_cursor(0, shift),
+							KeyboardKey::KEY_END => data.move_cursor(data.text.len(), shift),
+							KeyboardKey::KEY_DELETE => data.delete(),
+							KeyboardKey::KEY_A if ctrl => {
+								data.selection = Selection::all(&data.text)
+							}
+							_ => {}
+						}),
+					}
+				}
 			}
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_editing() {
+		let mut data = TypableData::with_default("héllo".into());
+		data.insert('!');
+		assert_eq!(data.text, "héllo!");
+
+		// moving back over the two byte é
+		data.move_cursor(3, false);
+		data.step(false, true);
+		assert_eq!(data.selected_text(), "é");
+		data.insert('e');
+		assert_eq!(data.text, "hello!");
+		assert_eq!(data.selection, Selection::caret(2));
+
+		data.backspace();
+		data.delete();
+		assert_eq!(data.text, "hlo!");
+
+		// a selection gets deleted as a whole, and the arrows drop it on their side
+		data.move_cursor(3, true);
+		assert_eq!(data.selected_text(), "lo");
+		data.step(true, false);
+		assert_eq!(data.selection, Selection::caret(3));
+		data.move_cursor(0, true);
+		data.backspace();
+		assert_eq!(data.text, "!");
+
+		// setting the text directly leaves the selection where it was, clamped when it's used
+		data.selection = Selection::new(0, 100);
+		assert_eq!(data.selected_text(), "!");
+		data.set_text("new".into());
+		assert_eq!(data.selection, Selection::caret(3));
+	}
+
+	#[test]
+	fn test_shift_click() {
+		let store = Store::new(TypableData::with_default("hello".into()));
+		let uid = store.with_borrow(|data| data.uid);
+		let mut typable = Typable::new(store.clone(), 16);
+		let det = Details::new(0, 0, 100, 20);
+		let click = Event::MouseEvent(MouseEvent::MouseClick { x: 0, y: 5 });
+
+		// the click comes before the shift in the batch, like it does from the root context
+		let shift = Event::KeyboardEvent(uid, KeyboardEvent::KeyDown(KeyboardKey::KEY_LEFT_SHIFT));
+		typable.pass_events([click, shift].into_iter(), det, 1.0, &mut Vec::new());
+		assert_eq!(
+			store.with_borrow(|data| data.selection),
+			Selection::new(5, 0)
+		);
+
+		// shift from the frame before doesn't count
+		typable.tick();
+		typable.pass_events([click].into_iter(), det, 1.0, &mut Vec::new());
+		assert_eq!(
+			store.with_borrow(|data| data.selection),
+			Selection::caret(0)
+		);
+	}
+}
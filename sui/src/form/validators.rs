@@ -0,0 +1,48 @@
+//! common validators for [Form](super::Form) fields. \
+//! any `Fn(&FieldValue) -> Result<(), String>` works, these are just the ones everyone needs
+
+use std::str::FromStr;
+
+use super::container::FieldValue;
+
+/// text can't be empty (or only whitespace), checkboxes have to be checked
+pub fn required(value: &FieldValue) -> Result<(), String> {
+	match value {
+		FieldValue::Text(text) if text.trim().is_empty() => Err("required".into()),
+		FieldValue::Bool(false) => Err("required".into()),
+		_ => Ok(()),
+	}
+}
+
+/// text has to be at least `len` characters long
+pub fn min_length(len: usize) -> impl Fn(&FieldValue) -> Result<(), String> {
+	move |value| match value {
+		FieldValue::Text(text) if text.chars().count() < len => {
+			Err(format!("has to be at least {len} characters"))
+		}
+		_ => Ok(()),
+	}
+}
+/// text can't be longer than `len` characters
+pub fn max_length(len: usize) -> impl Fn(&FieldValue) -> Result<(), String> {
+	move |value| match value {
+		FieldValue::Text(text) if text.chars().count() > len => {
+			Err(format!("can't be longer than {len} characters"))
+		}
+		_ => Ok(()),
+	}
+}
+
+/// text has to parse as `T`. empty text is fine, combine with [required] if it isn't
+pub fn parses<T: FromStr>(value: &FieldValue) -> Result<(), String> {
+	match value {
+		FieldValue::Text(text) if !text.trim().is_empty() && text.trim().parse::<T>().is_err() => {
+			Err(format!("has to be a valid {}", short_type_name::<T>()))
+		}
+		_ => Ok(()),
+	}
+}
+fn short_type_name<T>() -> &'static str {
+	let name = std::any::type_name::<T>();
+	name.rsplit("::").next().unwrap_or(name)
+}
@@ -0,0 +1,178 @@
+//! localization. \
+//! string tables get registered per locale with [add_table] (see `asset_provider_l10n` for loading them
+//! through assets), and looked up in the active locale with [tr], [tr_args] and [tr_count]. \
+//! [set_locale] switches the locale at runtime, [comp::LocalizedText](crate::comp::LocalizedText)
+//! picks the change up on its next tick
+//!
+//! lookups fall back to the fallback locale ([set_fallback_locale], `en` by default),
+//! then to the key itself, so a missing string is visible but never fatal
+
+use std::{
+	borrow::Cow,
+	collections::BTreeMap,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Mutex, MutexGuard,
+	},
+};
+
+pub mod plural;
+pub use plural::{plural_category, PluralCategory};
+
+pub mod table;
+pub use table::{interpolate, Message, StringTable};
+
+static CATALOG: Mutex<Catalog> = Mutex::new(Catalog {
+	tables: BTreeMap::new(),
+	locale: Cow::Borrowed("en"),
+	fallback: Cow::Borrowed("en"),
+});
+/// bumped every time what [tr] would return might've changed
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+struct Catalog {
+	tables: BTreeMap<String, StringTable>,
+	locale: Cow<'static, str>,
+	fallback: Cow<'static, str>,
+}
+
+fn lock() -> MutexGuard<'static, Catalog> {
+	match CATALOG.lock() {
+		Ok(a) => a,
+		Err(err) => err.into_inner(),
+	}
+}
+fn bump() {
+	GENERATION.fetch_add(1, Ordering::Relaxed);
+}
+
+/// changes every time the active strings might have changed (locale switched, tables added). \
+/// anything that caches translated strings should re-translate when this changes
+pub fn generation() -> u64 {
+	GENERATION.load(Ordering::Relaxed)
+}
+
+/// adds `table` to the strings of `locale`, overwriting existing keys
+pub fn add_table(locale: impl Into<String>, table: StringTable) {
+	let locale = locale.into();
+	{
+		let mut lock = lock();
+		match lock.tables.get_mut(&locale) {
+			Some(existing) => existing.extend(table),
+			None => {
+				lock.tables.insert(locale, table);
+			}
+		}
+	}
+	bump();
+}
+/// whether any strings were added for `locale`
+pub fn has_locale(locale: &str) -> bool {
+	lock().tables.contains_key(locale)
+}
+
+/// switches the active locale. doesn't require the locale to have any strings yet
+pub fn set_locale(locale: impl Into<Cow<'static, str>>) {
+	let locale = locale.into();
+	{
+		let mut lock = lock();
+		if lock.locale == locale {
+			return;
+		}
+		lock.locale = locale;
+	}
+	bump();
+}
+pub fn locale() -> String {
+	lock().locale.to_string()
+}
+/// the locale strings missing from the active locale are looked up in
+pub fn set_fallback_locale(locale: impl Into<Cow<'static, str>>) {
+	lock().fallback = locale.into();
+	bump();
+}
+
+/// the string for `key` in the active locale
+pub fn tr(key: &str) -> String {
+	translate(key, None, &[])
+}
+/// the string for `key` in the active locale, with `{name}` placeholders replaced by `args`. see [interpolate]
+pub fn tr_args(key: &str, args: &[(&str, &str)]) -> String {
+	translate(key, None, args)
+}
+/// the plural variant of `key` for `count` items. `{count}` is filled in automatically,
+/// unless it's in `args` too
+pub fn tr_count(key: &str, count: i64, args: &[(&str, &str)]) -> String {
+	translate(key, Some(count), args)
+}
+
+fn translate(key: &str, count: Option<i64>, args: &[(&str, &str)]) -> String {
+	let count_str = count.map(|n| n.to_string());
+	let mut all_args = args.to_vec();
+	if let Some(count_str) = &count_str {
+		if !args.iter().any(|(name, _)| *name == "count") {
+			all_args.push(("count", count_str.as_str()));
+		}
+	}
+
+	let lock = lock();
+	let template = [&*lock.locale, &*lock.fallback]
+		.into_iter()
+		.find_map(|locale| {
+			let message = lock.tables.get(locale)?.get(key)?;
+			message.template(locale, count)
+		})
+		.unwrap_or(key);
+
+	interpolate(template, &all_args).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_plural_category() {
+		use PluralCategory::*;
+
+		assert_eq!(plural_category("en", 1), One);
+		assert_eq!(plural_category("en-US", 0), Other);
+		assert_eq!(plural_category("fr", 0), One);
+		assert_eq!(plural_category("ja", 1), Other);
+		assert_eq!(plural_category("ru", 21), One);
+		assert_eq!(plural_category("ru", 22), Few);
+		assert_eq!(plural_category("ru", 12), Many);
+		assert_eq!(plural_category("pl", 1), One);
+		assert_eq!(plural_category("pl", 21), Many);
+		assert_eq!(plural_category("ar", 103), Few);
+	}
+
+	#[test]
+	fn test_interpolate() {
+		let args = [("name", "world"), ("n", "3")];
+		assert_eq!(interpolate("hello, {name}!", &args), "hello, world!");
+		assert_eq!(interpolate("{n} {missing}", &args), "3 {missing}");
+		assert_eq!(interpolate("{{name}} }}", &args), "{name} }");
+		assert_eq!(interpolate("unclosed {name", &args), "unclosed {name");
+	}
+
+	#[test]
+	fn test_translate() {
+		let mut plural = std::collections::HashMap::new();
+		plural.insert(PluralCategory::One, "{count} file".to_string());
+		plural.insert(PluralCategory::Other, "{count} files".to_string());
+
+		let mut table = StringTable::new();
+		table.insert("test_translate.greeting", "hi {name}");
+		table.insert("test_translate.files", Message::Plural(plural));
+		add_table("en", table);
+
+		assert_eq!(
+			tr_args("test_translate.greeting", &[("name", "you")]),
+			"hi you"
+		);
+		assert_eq!(tr_count("test_translate.files", 1, &[]), "1 file");
+		assert_eq!(tr_count("test_translate.files", 5, &[]), "5 files");
+		assert_eq!(tr("test_translate.missing"), "test_translate.missing");
+	}
+}
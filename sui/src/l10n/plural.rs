@@ -0,0 +1,109 @@
+/// cldr plural categories. every language has [PluralCategory::Other], the rest depend on the language
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PluralCategory {
+	Zero,
+	One,
+	Two,
+	Few,
+	Many,
+	Other,
+}
+impl PluralCategory {
+	pub const ALL: [Self; 6] = [
+		Self::Zero,
+		Self::One,
+		Self::Two,
+		Self::Few,
+		Self::Many,
+		Self::Other,
+	];
+
+	/// the name cldr (and string tables) use for this category, like `"one"` or `"few"`
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::Zero => "zero",
+			Self::One => "one",
+			Self::Two => "two",
+			Self::Few => "few",
+			Self::Many => "many",
+			Self::Other => "other",
+		}
+	}
+	pub fn from_name(name: &str) -> Option<Self> {
+		Self::ALL.into_iter().find(|cat| cat.name() == name)
+	}
+}
+
+/// cardinal plural category of the integer `n` in `locale` (like `en`, `en-US` or `pt_BR`). \
+/// covers the languages we've needed so far, everything else gets english rules
+pub fn plural_category(locale: &str, n: i64) -> PluralCategory {
+	use PluralCategory::*;
+
+	let mut parts = locale.split(['-', '_']);
+	let lang = parts.next().unwrap_or_default().to_ascii_lowercase();
+	let region = parts.next().unwrap_or_default().to_ascii_uppercase();
+
+	let n = n.unsigned_abs();
+	let (n10, n100) = (n % 10, n % 100);
+
+	match lang.as_str() {
+		"ja" | "zh" | "ko" | "th" | "vi" | "id" | "ms" | "lo" | "my" => Other,
+		"fr" => match n {
+			0 | 1 => One,
+			_ => Other,
+		},
+		"pt" if region != "PT" => match n {
+			0 | 1 => One,
+			_ => Other,
+		},
+		"ru" | "uk" | "be" | "sr" | "hr" | "bs" => {
+			if n10 == 1 && n100 != 11 {
+				One
+			} else if (2..=4).contains(&n10) && !(12..=14).contains(&n100) {
+				Few
+			} else {
+				Many
+			}
+		}
+		"pl" => {
+			if n == 1 {
+				One
+			} else if (2..=4).contains(&n10) && !(12..=14).contains(&n100) {
+				Few
+			} else {
+				Many
+			}
+		}
+		"cs" | "sk" => match n {
+			1 => One,
+			2..=4 => Few,
+			_ => Other,
+		},
+		"ar" => match (n, n100) {
+			(0, _) => Zero,
+			(1, _) => One,
+			(2, _) => Two,
+			(_, 3..=10) => Few,
+			(_, 11..=99) => Many,
+			_ => Other,
+		},
+		"he" => match n {
+			1 => One,
+			2 => Two,
+			_ => Other,
+		},
+		"lv" => {
+			if n10 == 0 || (11..=19).contains(&n100) {
+				Zero
+			} else if n10 == 1 && n100 != 11 {
+				One
+			} else {
+				Other
+			}
+		}
+		_ => match n {
+			1 => One,
+			_ => Other,
+		},
+	}
+}
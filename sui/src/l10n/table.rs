@@ -0,0 +1,118 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use super::{plural_category, PluralCategory};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Message {
+	Simple(String),
+	/// one variant per plural category. [PluralCategory::Other] is used for every missing category
+	Plural(HashMap<PluralCategory, String>),
+}
+impl Message {
+	/// the template for `count` items, or the template itself if this isn't a plural message
+	pub fn template(&self, locale: &str, count: Option<i64>) -> Option<&str> {
+		match self {
+			Self::Simple(s) => Some(s),
+			Self::Plural(variants) => {
+				let category = count
+					.map(|n| plural_category(locale, n))
+					.unwrap_or(PluralCategory::Other);
+				variants
+					.get(&category)
+					.or_else(|| variants.get(&PluralCategory::Other))
+					.map(String::as_str)
+			}
+		}
+	}
+}
+impl From<String> for Message {
+	fn from(value: String) -> Self {
+		Self::Simple(value)
+	}
+}
+impl From<&str> for Message {
+	fn from(value: &str) -> Self {
+		Self::Simple(value.into())
+	}
+}
+
+/// every string of one locale, keyed by dotted keys like `menu.settings.title`
+#[derive(Clone, Debug, Default)]
+pub struct StringTable {
+	messages: HashMap<String, Message>,
+}
+impl StringTable {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn insert(&mut self, key: impl Into<String>, message: impl Into<Message>) {
+		self.messages.insert(key.into(), message.into());
+	}
+	pub fn get(&self, key: &str) -> Option<&Message> {
+		self.messages.get(key)
+	}
+	pub fn len(&self) -> usize {
+		self.messages.len()
+	}
+	pub fn is_empty(&self) -> bool {
+		self.messages.is_empty()
+	}
+
+	/// adds every message from `other`, overwriting the ones with the same key
+	pub fn extend(&mut self, other: StringTable) {
+		self.messages.extend(other.messages);
+	}
+}
+impl<K: Into<String>, M: Into<Message>> FromIterator<(K, M)> for StringTable {
+	fn from_iter<T: IntoIterator<Item = (K, M)>>(iter: T) -> Self {
+		let messages = iter
+			.into_iter()
+			.map(|(k, m)| (k.into(), m.into()))
+			.collect();
+		Self { messages }
+	}
+}
+
+/// replaces every `{name}` in `template` with the matching arg. \
+/// `{{` and `}}` are literal braces, placeholders without a matching arg are left as they are
+pub fn interpolate<'a>(template: &'a str, args: &[(&str, &str)]) -> Cow<'a, str> {
+	if !template.contains(['{', '}']) {
+		return Cow::Borrowed(template);
+	}
+
+	let mut out = String::with_capacity(template.len());
+	let mut rest = template;
+	while let Some(i) = rest.find(['{', '}']) {
+		out.push_str(&rest[..i]);
+		let tail = &rest[i..];
+
+		if tail.starts_with("{{") || tail.starts_with("}}") {
+			out.push_str(&tail[..1]);
+			rest = &tail[2..];
+			continue;
+		}
+		if let Some(after) = tail.strip_prefix('}') {
+			out.push('}');
+			rest = after;
+			continue;
+		}
+
+		match tail.find('}') {
+			Some(end) => {
+				let name = tail[1..end].trim();
+				match args.iter().find(|(arg, _)| *arg == name) {
+					Some((_, value)) => out.push_str(value),
+					None => out.push_str(&tail[..=end]),
+				}
+				rest = &tail[end + 1..];
+			}
+			None => {
+				out.push_str(tail);
+				rest = "";
+			}
+		}
+	}
+	out.push_str(rest);
+	Cow::Owned(out)
+}
@@ -0,0 +1,210 @@
+//! layers are for things that have to render outside of their parent's [Details] and on top of everything else,
+//! like popups and dropdown lists. \
+//! a component opens a layer by calling [Handle::layer] while rendering. layers get rendered after the whole
+//! [RootContext](crate::RootContext) is done, outside of any [Crop](crate::comp::Crop), in the order they were opened. \
+//! clicks, moves and scrolls inside a layer don't get passed as regular mouse events (so whatever's under the
+//! layer doesn't react to them), they're sent as [Event::LayerEvent] to the component that owns the layer instead.
+//! [MouseEvent::MouseHeld] and [MouseEvent::MouseRelease] stay regular mouse events, so drags that go over a
+//! layer still end. \
+//! layer events are always in window coords, and layers only get events if they were open the frame before. \
+//! [dialogs](crate::dialog) cover the layers opened under them: those get drawn under the dialogs, and mouse
+//! events on a dialog (or anywhere, if it's modal) stay regular mouse events for the dialog to get
+
+use std::cell::RefCell;
+
+use crate::{
+	core::{Event, LayerEvent, MouseEvent},
+	form::UniqueId,
+	Details, Handle,
+};
+
+pub type LayerRender = Box<dyn FnOnce(&mut Handle<'_>)>;
+
+pub(crate) struct Layer {
+	pub owner: UniqueId,
+	pub det: Details,
+	pub render: LayerRender,
+}
+impl std::fmt::Debug for Layer {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("Layer")
+			.field("owner", &self.owner)
+			.field("det", &self.det)
+			.finish()
+	}
+}
+
+thread_local! {
+	/// (owner, det) of every layer rendered this frame, topmost last. \
+	/// dialogs are in here too, owned by [UniqueId::null]
+	static OPEN: RefCell<Vec<(UniqueId, Details)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// forgets the layers from the previous frame, called whenever a new [Handle] is created
+pub(crate) fn clear() {
+	OPEN.with_borrow_mut(|open| open.clear());
+}
+
+/// renders every layer opened on `d` so far. \
+/// layers can open more layers while rendering, those get rendered on top
+pub(crate) fn render_layers(d: &mut Handle) {
+	while let Some(layers) = d.take_layers() {
+		for Layer { owner, det, render } in layers {
+			OPEN.with_borrow_mut(|open| open.push((owner, det)));
+			render(d);
+		}
+	}
+}
+
+/// marks `det` as covered by a dialog, so the layers opened before it don't get mouse events there. \
+/// the layers opened before it should be rendered already
+pub(crate) fn cover(det: Details) {
+	OPEN.with_borrow_mut(|open| open.push((UniqueId::null(), det)));
+}
+
+/// (owner, det) of every layer that's open right now, topmost last
+pub fn open_layers() -> Vec<(UniqueId, Details)> {
+	OPEN.with_borrow(|open| {
+		(open.iter().copied())
+			.filter(|(owner, _)| *owner != UniqueId::null())
+			.collect()
+	})
+}
+/// whether `owner` has a layer open
+pub fn is_open(owner: UniqueId) -> bool {
+	OPEN.with_borrow(|open| open.iter().any(|(uid, _)| *uid == owner))
+}
+
+/// turns clicks, moves and scrolls inside a layer into [Event::LayerEvent]s for its owner,
+/// and tells every layer about clicks outside of it
+pub(crate) fn route(events: impl Iterator<Item = Event>) -> Vec<Event> {
+	let open = OPEN.with_borrow(|open| open.clone());
+	if open.is_empty() {
+		return events.collect();
+	}
+	route_with(&open, events)
+}
+pub(crate) fn route_with(
+	open: &[(UniqueId, Details)],
+	events: impl Iterator<Item = Event>,
+) -> Vec<Event> {
+	let mut routed = Vec::new();
+	for event in events {
+		let Event::MouseEvent(m_event) = event else {
+			routed.push(event);
+			continue;
+		};
+		// whatever's being dragged has to hear about it, wherever it goes
+		if let MouseEvent::MouseHeld { .. } | MouseEvent::MouseRelease { .. } = m_event {
+			routed.push(event);
+			continue;
+		}
+		let (x, y) = m_event.at();
+
+		let topmost = open.iter().rposition(|(_, det)| det.is_inside(x, y));
+		if let MouseEvent::MouseClick { .. } | MouseEvent::RightClick { .. } = m_event {
+			// a component with more than one layer (like a menu and its submenu) only hears about it once,
+			// and only if the click wasn't in any of its layers. dialogs don't hear about it at all
+			let mut told = vec![UniqueId::null()];
+			told.extend(topmost.map(|i| open[i].0));
+			for (owner, _) in open.iter() {
+				if !told.contains(owner) {
+					told.push(*owner);
+					routed.push(Event::LayerEvent(
+						*owner,
+						LayerEvent::ClickedOutside { x, y },
+					));
+				}
+			}
+		}
+
+		match topmost {
+			Some(i) if open[i].0 != UniqueId::null() => {
+				routed.push(Event::LayerEvent(open[i].0, LayerEvent::Mouse(m_event)))
+			}
+			_ => routed.push(event),
+		}
+	}
+	routed
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_route() {
+		let (a, b) = (UniqueId::new(), UniqueId::new());
+		let open = [
+			(a, Details::new(0, 0, 100, 100)),
+			(b, Details::new(50, 50, 100, 100)),
+		];
+
+		let click = |x, y| Event::MouseEvent(MouseEvent::MouseClick { x, y });
+
+		// outside everything
+		let routed = route_with(&open, [click(200, 200)].into_iter());
+		assert_eq!(
+			routed,
+			vec![
+				Event::LayerEvent(a, LayerEvent::ClickedOutside { x: 200, y: 200 }),
+				Event::LayerEvent(b, LayerEvent::ClickedOutside { x: 200, y: 200 }),
+				click(200, 200)
+			]
+		);
+
+		// where they overlap, b's on top
+		let routed = route_with(&open, [click(75, 75)].into_iter());
+		assert_eq!(
+			routed,
+			vec![
+				Event::LayerEvent(a, LayerEvent::ClickedOutside { x: 75, y: 75 }),
+				Event::LayerEvent(
+					b,
+					LayerEvent::Mouse(MouseEvent::MouseClick { x: 75, y: 75 })
+				),
+			]
+		);
+
+		let key = Event::KeyboardEvent(a, crate::core::KeyboardEvent::CharPressed('a'));
+		assert_eq!(route_with(&open, [key].into_iter()), vec![key]);
+
+		// a drag that started outside ends over a layer, it doesn't get taken
+		let held = Event::MouseEvent(MouseEvent::MouseHeld { x: 75, y: 75 });
+		let release = Event::MouseEvent(MouseEvent::MouseRelease { x: 75, y: 75 });
+		let routed = route_with(&open, [held, release].into_iter());
+		assert_eq!(routed, vec![held, release]);
+		let scroll = MouseEvent::Scroll {
+			x: 75,
+			y: 75,
+			amount: 1.0,
+		};
+		let routed = route_with(&open, [Event::MouseEvent(scroll)].into_iter());
+		assert_eq!(
+			routed,
+			vec![Event::LayerEvent(b, LayerEvent::Mouse(scroll))]
+		);
+
+		// a dialog opened after a covers it, the click is for the dialog
+		let open = [
+			(a, Details::new(0, 0, 100, 100)),
+			(UniqueId::null(), Details::new(50, 50, 100, 100)),
+		];
+		let routed = route_with(&open, [click(75, 75)].into_iter());
+		assert_eq!(
+			routed,
+			vec![
+				Event::LayerEvent(a, LayerEvent::ClickedOutside { x: 75, y: 75 }),
+				click(75, 75)
+			]
+		);
+		let routed = route_with(&open, [click(25, 25)].into_iter());
+		assert_eq!(
+			routed,
+			vec![Event::LayerEvent(
+				a,
+				LayerEvent::Mouse(MouseEvent::MouseClick { x: 25, y: 25 })
+			)]
+		);
+	}
+}
@@ -8,10 +8,14 @@ pub use comp::{Comp, Compatible, Div, Text};
 
 pub mod dialog;
 
+pub mod layer;
+
 pub mod tex;
 
 pub mod form;
 
+pub mod l10n;
+
 pub(crate) mod root_ctx;
 pub mod ui;
 pub use ui::*;
@@ -30,6 +30,7 @@ impl<L: Layable> RootContext<L> {
 
 	pub fn render(&self, d: &mut crate::Handle) {
 		self.layable.render(d, self.det, self.scale);
+		crate::layer::render_layers(d);
 	}
 
 	pub fn tick(&mut self) {
@@ -50,12 +51,22 @@ impl<L: Layable> RootContext<L> {
 		let (ptr_x, ptr_y) = (rl.get_mouse_x(), rl.get_mouse_y());
 
 		if ptr_x as f32 > self.det.x as f32 && ptr_y as f32 > self.det.y as f32 {
+			events_to_fire.push(Event::MouseEvent(MouseEvent::MouseMove {
+				x: ptr_x,
+				y: ptr_y,
+			}));
 			if rl.is_mouse_button_pressed(MouseButton::MOUSE_BUTTON_LEFT) {
 				events_to_fire.push(Event::MouseEvent(MouseEvent::MouseClick {
 					x: ptr_x,
 					y: ptr_y,
 				}))
 			};
+			if rl.is_mouse_button_pressed(MouseButton::MOUSE_BUTTON_RIGHT) {
+				events_to_fire.push(Event::MouseEvent(MouseEvent::RightClick {
+					x: ptr_x,
+					y: ptr_y,
+				}))
+			};
 			if rl.is_mouse_button_down(MouseButton::MOUSE_BUTTON_LEFT) {
 				events_to_fire.push(Event::MouseEvent(MouseEvent::MouseHeld {
 					x: ptr_x,
@@ -109,7 +120,21 @@ impl<L: Layable> RootContext<L> {
 				None
 			}
 		});
-		let events_to_fire = events_to_fire.into_iter().chain(key_downs);
+		let key_presses = keys_to_poll().filter_map(|key| {
+			if rl.is_key_pressed(key) {
+				Some(Event::KeyboardEvent(
+					focus.get(),
+					KeyboardEvent::KeyPressed(key),
+				))
+			} else {
+				None
+			}
+		});
+		let events_to_fire = events_to_fire
+			.into_iter()
+			.chain(key_downs)
+			.chain(key_presses);
+		let events_to_fire = crate::layer::route(events_to_fire).into_iter();
 
 		let mut ret_events = Vec::new();
 		self.layable
@@ -28,6 +28,11 @@ pub fn div_h<D: comp::div::DivComponents>(components: D) -> comp::Div<D> {
 pub fn text<'a, T: Into<Cow<'a, str>>>(text: T, size: i32) -> Comp<'a> {
 	comp::Text::new(text, size).into_comp()
 }
+/// text for a localization key, see [crate::l10n]. \
+/// use [comp::LocalizedText] directly for args and counts
+pub fn text_tr<K: Into<Cow<'static, str>>>(key: K, size: i32) -> Comp<'static> {
+	custom(comp::LocalizedText::new(key, size)).into_comp()
+}
 
 /// LayableExt provides associated functions for most comp::*::new calls
 pub trait LayableExt: Layable + Sized {
@@ -128,6 +133,14 @@ pub trait LayableExt: Layable + Sized {
 		comp::Scrollable::new(state, ScrollableMode::Both, self)
 	}
 
+	/// see [comp::text::Selectable]
+	fn selectable(self) -> comp::text::Selectable<Self>
+	where
+		Self: comp::text::TextLayout,
+	{
+		comp::text::Selectable::new(self)
+	}
+
 	/// see [comp::Clickable]
 	fn clickable<T: 'static, F: FnMut((i32, i32)) -> T>(
 		self,
@@ -142,6 +155,10 @@ pub trait LayableExt: Layable + Sized {
 	) -> comp::Clickable<Self, F, T> {
 		comp::Clickable::new_fallback(gen_ret, self)
 	}
+	/// see [comp::Button]
+	fn button<T: 'static, F: FnMut() -> T>(self, on_press: F) -> comp::Button<Self, F, T> {
+		comp::Button::new(self, on_press)
+	}
 	/// see [comp::OptionalClickable]
 	fn clickable_optional<F: FnMut() -> Option<ReturnEvent>>(
 		self,
@@ -150,6 +167,16 @@ pub trait LayableExt: Layable + Sized {
 		comp::OptionalClickable::new(self, gen_f)
 	}
 
+	/// see [comp::Tooltip]
+	fn tooltip<T: Layable + Clone + 'static>(self, content: T) -> comp::Tooltip<Self, T> {
+		comp::Tooltip::new(self, content)
+	}
+
+	/// see [comp::ContextMenu]
+	fn context_menu<T: Clone + 'static>(self, menu: comp::Menu<T>) -> comp::ContextMenu<Self, T> {
+		comp::ContextMenu::new(self, menu)
+	}
+
 	/// see [comp::Debug]
 	fn debug(self) -> comp::Debug<Self> {
 		comp::Debug::new(self)
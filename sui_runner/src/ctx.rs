@@ -28,6 +28,7 @@ from!(Type, sui::core::TypeCommand);
 #[derive(Debug)]
 pub struct Context<L: Layable> {
 	pub l: L,
+	pub dialogs: sui::dialog::Handler,
 	pub rl: RaylibHandle,
 	pub thread: RaylibThread,
 }
@@ -35,6 +36,7 @@ impl<L: Layable> Context<L> {
 	pub fn new(layable: L, rl: RaylibHandle, thread: RaylibThread) -> Self {
 		Self {
 			l: layable,
+			dialogs: Default::default(),
 			rl,
 			thread,
 		}
@@ -52,16 +54,20 @@ impl<L: Layable> Context<L> {
 	}
 	pub fn tick(&mut self, focus: &mut Store<UniqueId>) {
 		let screen = sui::Details::rl_window(&self.rl);
-		let mut ctx = self.l.root_context(screen, 1.0);
+		let mut layable = self.dialogs.wrap(&mut self.l);
+		let mut ctx = layable.root_context(screen, 1.0);
 
 		let mut r = &mut self.rl;
 		let r = &mut r;
 
 		ctx.tick();
 
+		// the dialogs are borrowed by ctx until it's rendered
+		let mut dialog_commands = Vec::new();
 		for event in ctx.handle_input(r, focus) {
 			match event {
 				Ok(Event::Focus(cmd)) => cmd.apply(focus),
+				Ok(Event::Dialog(cmd)) => dialog_commands.push(cmd),
 				_ => eprintln!("dropped event {event:?}"),
 			}
 		}
@@ -72,5 +78,9 @@ impl<L: Layable> Context<L> {
 		let mut d = sui::Handle::new(d, &self.thread, focus);
 
 		ctx.render(&mut d);
+
+		for cmd in dialog_commands {
+			self.dialogs.run(cmd);
+		}
 	}
 }